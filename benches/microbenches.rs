@@ -1,9 +1,10 @@
 use criterion::{self, criterion_group, criterion_main, Criterion};
 use pretty_assertions::assert_eq;
-use quick_xml::escape::{escape, unescape};
-use quick_xml::events::Event;
+use quick_xml::escape::{escape, needs_escaping, needs_unescaping, unescape};
+use quick_xml::events::{BytesText, Event};
 use quick_xml::name::QName;
 use quick_xml::reader::{NsReader, Reader};
+use quick_xml::writer::Writer;
 
 static SAMPLE: &str = include_str!("../tests/documents/sample_rss.xml");
 static PLAYERS: &str = include_str!("../tests/documents/players.xml");
@@ -242,6 +243,32 @@ fn attributes(c: &mut Criterion) {
             assert_eq!(count, 150);
         })
     });
+
+    group.bench_function("parse_attributes", |b| {
+        b.iter(|| {
+            let mut r = Reader::from_str(PLAYERS);
+            r.config_mut().check_end_names = false;
+            let mut count = criterion::black_box(0);
+            loop {
+                match r.read_event() {
+                    Ok(Event::Empty(e)) if e.name() == QName(b"player") => {
+                        let attrs = e.parse_attributes().unwrap();
+                        for name in ["num", "status", "avg"] {
+                            if attrs.iter().any(|(key, _)| key.as_ref() == name.as_bytes()) {
+                                count += 1
+                            }
+                        }
+                        assert!(!attrs
+                            .iter()
+                            .any(|(key, _)| key.as_ref() == b"attribute-that-doesn't-exist"));
+                    }
+                    Ok(Event::Eof) => break,
+                    _ => (),
+                }
+            }
+            assert_eq!(count, 150);
+        })
+    });
     group.finish();
 }
 
@@ -261,6 +288,38 @@ fn escaping(c: &mut Criterion) {
         })
     });
 
+    group.bench_function("needs_escaping_long", |b| {
+        b.iter(|| {
+            criterion::black_box(needs_escaping(LOREM_IPSUM_TEXT.as_bytes()));
+        })
+    });
+
+    // Text without special characters is borrowed by `escape()` and written
+    // without allocations
+    group.bench_function("write_clean_text", |b| {
+        let mut writer = Writer::new(Vec::with_capacity(LOREM_IPSUM_TEXT.len()));
+        b.iter(|| {
+            writer.get_mut().clear();
+            writer
+                .write_event(Event::Text(BytesText::new(LOREM_IPSUM_TEXT)))
+                .unwrap();
+            criterion::black_box(writer.get_ref());
+        })
+    });
+
+    // The same text written without escaping, to measure the cost of the search
+    // of characters to escape in `write_clean_text`
+    group.bench_function("write_clean_text_unescaped", |b| {
+        let mut writer = Writer::new(Vec::with_capacity(LOREM_IPSUM_TEXT.len()));
+        b.iter(|| {
+            writer.get_mut().clear();
+            writer
+                .write_event(Event::Text(BytesText::from_escaped(LOREM_IPSUM_TEXT)))
+                .unwrap();
+            criterion::black_box(writer.get_ref());
+        })
+    });
+
     group.bench_function("escaped_chars_short", |b| {
         b.iter(|| {
             criterion::black_box(escape("age > 72 && age < 21"));
@@ -288,6 +347,20 @@ purus. Consequat id porta nibh venenatis cras sed felis.";
             criterion::black_box(escape(lorem_ipsum_with_escape_chars));
         })
     });
+
+    group.bench_function("sparse_chars_to_escape_1mb", |b| {
+        // ~1 MB of text with one special character per ~1 KB
+        let mut text = LOREM_IPSUM_TEXT.repeat(1000);
+        for i in (0..text.len()).step_by(1024) {
+            if text.is_char_boundary(i) && text.is_char_boundary(i + 1) {
+                text.replace_range(i..i + 1, "&");
+            }
+        }
+
+        b.iter(|| {
+            criterion::black_box(escape(text.as_str()));
+        })
+    });
     group.finish();
 }
 
@@ -307,6 +380,12 @@ fn unescaping(c: &mut Criterion) {
         })
     });
 
+    group.bench_function("needs_unescaping_long", |b| {
+        b.iter(|| {
+            criterion::black_box(needs_unescaping(LOREM_IPSUM_TEXT.as_bytes()));
+        })
+    });
+
     group.bench_function("char_reference", |b| {
         b.iter(|| {
             let text = "prefix &#34;some stuff&#34;,&#x22;more stuff&#x22;";
@@ -348,6 +427,115 @@ purus. Consequat id porta nibh venenatis cras sed felis.";
     group.finish();
 }
 
+/// Benchmarks copying of events from a reader to a writer when event is used
+/// after writing
+fn transform(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transform");
+    group.bench_function("write_event(into_owned)", |b| {
+        b.iter(|| {
+            let mut r = Reader::from_reader(SAMPLE.as_bytes());
+            let mut w = Writer::new(Vec::with_capacity(SAMPLE.len()));
+            let mut buf = Vec::new();
+            let mut count = criterion::black_box(0);
+            loop {
+                let event = r.read_event_into(&mut buf).unwrap();
+                w.write_event(event.clone().into_owned()).unwrap();
+                match event {
+                    Event::Start(_) | Event::Empty(_) => count += 1,
+                    Event::Eof => break,
+                    _ => (),
+                }
+                buf.clear();
+            }
+            assert_eq!(
+                count, 1550,
+                "Overall tag count in ./tests/documents/sample_rss.xml"
+            );
+        })
+    });
+
+    group.bench_function("write_event_ref", |b| {
+        b.iter(|| {
+            let mut r = Reader::from_reader(SAMPLE.as_bytes());
+            let mut w = Writer::new(Vec::with_capacity(SAMPLE.len()));
+            let mut buf = Vec::new();
+            let mut count = criterion::black_box(0);
+            loop {
+                let event = r.read_event_into(&mut buf).unwrap();
+                w.write_event_ref(&event).unwrap();
+                match event {
+                    Event::Start(_) | Event::Empty(_) => count += 1,
+                    Event::Eof => break,
+                    _ => (),
+                }
+                buf.clear();
+            }
+            assert_eq!(
+                count, 1550,
+                "Overall tag count in ./tests/documents/sample_rss.xml"
+            );
+        })
+    });
+    group.finish();
+}
+
+/// Benchmarks skipping of large elements using the `Reader::read_to_end` and
+/// `Reader::skip_element` functions
+fn skip_element(c: &mut Criterion) {
+    let mut xml = String::from("<root>");
+    for _ in 0..100 {
+        xml.push_str("<skip>");
+        for i in 0..100 {
+            xml.push_str(&format!(
+                r#"<item id="{}" kind="a > b"><!-- <skip> --><name>Item</name><value>{}</value></item>"#,
+                i, LOREM_IPSUM_TEXT
+            ));
+        }
+        xml.push_str("</skip><keep/>");
+    }
+    xml.push_str("</root>");
+
+    let mut group = c.benchmark_group("skip_element");
+    group.bench_function("read_to_end", |b| {
+        b.iter(|| {
+            let mut r = Reader::from_str(&xml);
+            r.config_mut().check_end_names = false;
+            let mut count = criterion::black_box(0);
+            loop {
+                match r.read_event() {
+                    Ok(Event::Start(e)) if e.name() == QName(b"skip") => {
+                        r.read_to_end(e.name()).unwrap();
+                    }
+                    Ok(Event::Empty(_)) => count += 1,
+                    Ok(Event::Eof) => break,
+                    _ => (),
+                }
+            }
+            assert_eq!(count, 100);
+        })
+    });
+
+    group.bench_function("skip_element", |b| {
+        b.iter(|| {
+            let mut r = Reader::from_str(&xml);
+            r.config_mut().check_end_names = false;
+            let mut count = criterion::black_box(0);
+            loop {
+                match r.read_event() {
+                    Ok(Event::Start(e)) if e.name() == QName(b"skip") => {
+                        r.skip_element(e.name()).unwrap();
+                    }
+                    Ok(Event::Empty(_)) => count += 1,
+                    Ok(Event::Eof) => break,
+                    _ => (),
+                }
+            }
+            assert_eq!(count, 100);
+        })
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     read_event,
@@ -356,5 +544,7 @@ criterion_group!(
     attributes,
     escaping,
     unescaping,
+    transform,
+    skip_element,
 );
 criterion_main!(benches);
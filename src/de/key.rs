@@ -73,7 +73,7 @@ fn decode_name<'n>(name: QName<'n>, decoder: Decoder) -> Result<Cow<'n, str>, De
 /// [`local_name()`]: QName::local_name
 /// [`Deserialize`]: serde::Deserialize
 pub struct QNameDeserializer<'i, 'd> {
-    name: CowRef<'i, 'd, str>,
+    pub(super) name: CowRef<'i, 'd, str>,
 }
 
 impl<'i, 'd> QNameDeserializer<'i, 'd> {
@@ -5,6 +5,7 @@ use crate::{
     de::resolver::EntityResolver,
     de::simple_type::SimpleTypeDeserializer,
     de::text::TextDeserializer,
+    de::var,
     de::{DeEvent, Deserializer, XmlRead, TEXT_KEY, VALUE_KEY},
     encoding::Decoder,
     errors::serialize::DeError,
@@ -12,7 +13,7 @@ use crate::{
     events::attributes::IterState,
     events::BytesStart,
     name::QName,
-    utils::CowRef,
+    utils::{is_whitespace, CowRef},
 };
 use serde::de::value::BorrowedStrDeserializer;
 use serde::de::{self, DeserializeSeed, Deserializer as _, MapAccess, SeqAccess, Visitor};
@@ -149,6 +150,25 @@ enum ValueSource {
     Nested,
 }
 
+/// Returns `true` if the tag has any attributes, including namespace declarations.
+fn has_attributes(start: &BytesStart) -> bool {
+    !start.attributes_raw().iter().all(|b| is_whitespace(*b))
+}
+
+/// Returns an error if a sequence of elements with the specified name was
+/// already ended by an element with another name.
+fn check_seq_continued(
+    ended_seqs: &[Vec<u8>],
+    name: &[u8],
+    decoder: Decoder,
+) -> Result<(), DeError> {
+    if ended_seqs.iter().any(|n| n == name) {
+        let name = decoder.decode(name)?;
+        return Err(DeError::NonContiguousSequence(name.into_owned()));
+    }
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// A deserializer that extracts map-like structures from an XML. This deserializer
@@ -193,6 +213,18 @@ where
     /// <tag>value for VALUE_KEY field<tag>
     /// ```
     has_value_field: bool,
+    /// Names of elements which sequences was ended by an element with another name.
+    /// The sequence for the [`VALUE_KEY`] field is stored as the `$value` name,
+    /// which cannot be a name of an XML element.
+    ///
+    /// When feature [`overlapped-lists`] is not activated, encountering an element
+    /// from that list means that the sequence is not contiguous. Because that sequence
+    /// cannot be continued, an error [`DeError::NonContiguousSequence`] is returned.
+    ///
+    /// Always empty, when feature [`overlapped-lists`] is activated.
+    ///
+    /// [`overlapped-lists`]: ../../index.html#overlapped-lists
+    ended_seqs: Vec<Vec<u8>>,
 }
 
 impl<'de, 'd, R, E> ElementMapAccess<'de, 'd, R, E>
@@ -213,6 +245,7 @@ where
             source: ValueSource::Unknown,
             fields,
             has_value_field: fields.contains(&VALUE_KEY),
+            ended_seqs: Vec::new(),
         })
     }
 }
@@ -233,6 +266,8 @@ where
         // FIXME: There error positions counted from the start of tag name - need global position
         let slice = &self.start.buf;
         let decoder = self.de.reader.decoder();
+        let fields = self.fields;
+        let rename = self.de.rename_fields;
 
         if let Some(a) = self.iter.next(slice).transpose()? {
             // try getting map from attributes (key= "value")
@@ -241,7 +276,7 @@ where
 
             let de =
                 QNameDeserializer::from_attr(QName(&slice[key]), decoder, &mut self.de.key_buf)?;
-            seed.deserialize(de).map(Some)
+            deserialize_field_name(seed, de, fields, rename).map(Some)
         } else {
             // try getting from events (<key>value</key>)
             match self.de.peek()? {
@@ -249,6 +284,7 @@ where
                 // struct, so if we have `$value` field, the we should deserialize
                 // text content to `$value`
                 DeEvent::Text(_) if self.has_value_field => {
+                    check_seq_continued(&self.ended_seqs, VALUE_KEY.as_bytes(), decoder)?;
                     self.source = ValueSource::Content;
                     // Deserialize `key` from special attribute name which means
                     // that value should be taken from the text content of the
@@ -280,17 +316,21 @@ where
                 // }
                 // TODO: This should be handled by #[serde(flatten)]
                 // See https://github.com/serde-rs/serde/issues/1905
-                DeEvent::Start(e) if self.has_value_field && not_in(self.fields, e, decoder)? => {
+                DeEvent::Start(e)
+                    if self.has_value_field && not_in(fields, rename, e, decoder)? =>
+                {
+                    check_seq_continued(&self.ended_seqs, VALUE_KEY.as_bytes(), decoder)?;
                     self.source = ValueSource::Content;
 
                     let de = BorrowedStrDeserializer::<DeError>::new(VALUE_KEY);
                     seed.deserialize(de).map(Some)
                 }
                 DeEvent::Start(e) => {
+                    check_seq_continued(&self.ended_seqs, e.name().as_ref(), decoder)?;
                     self.source = ValueSource::Nested;
 
                     let de = QNameDeserializer::from_elem(e.raw_name(), decoder)?;
-                    seed.deserialize(de).map(Some)
+                    deserialize_field_name(seed, de, fields, rename).map(Some)
                 }
                 // Stop iteration after reaching a closing tag
                 // The matching tag name is guaranteed by the reader if our
@@ -540,6 +580,11 @@ where
     where
         V: Visitor<'de>,
     {
+        // Only elements mapped to fields can be skipped. `$value` fields should
+        // be deserialized from the element itself
+        if self.fixed_name && self.map.de.skip_empty_element()? {
+            return visitor.visit_none();
+        }
         match self.map.de.peek()? {
             DeEvent::Text(t) if t.is_empty() => visitor.visit_none(),
             _ => visitor.visit_some(self),
@@ -583,7 +628,7 @@ where
                 _ => unreachable!(),
             }
         } else {
-            TagFilter::Exclude(self.map.fields)
+            TagFilter::Exclude(self.map.fields, self.map.de.rename_fields)
         };
         visitor.visit_seq(MapValueSeqAccess {
             #[cfg(feature = "overlapped-lists")]
@@ -594,6 +639,27 @@ where
         })
     }
 
+    /// Representation of tuples the same as [sequences](#method.deserialize_seq),
+    /// unless [`Deserializer::positional_tuples`] is enabled and the value is
+    /// deserialized from an element. In the last case the tuple is deserialized
+    /// from the children of that element.
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.fixed_name && self.map.de.positional_tuples {
+            match self.map.de.next()? {
+                DeEvent::Start(e) => self.map.de.deserialize_children(e, visitor),
+                // SAFETY: we use that deserializer with `fixed_name == true`
+                // only from the `ElementMapAccess::next_value_seed` and only when we
+                // peeked `Start` event
+                _ => unreachable!(),
+            }
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
     #[inline]
     fn deserialize_struct<V>(
         self,
@@ -617,6 +683,17 @@ where
         V: Visitor<'de>,
     {
         if self.fixed_name {
+            // Handles <field xsi:type="Variant">...</field>
+            if self.map.de.xsi_type {
+                let decoder = self.map.de.reader.decoder();
+                let has_type = match self.map.de.peek()? {
+                    DeEvent::Start(e) => var::xsi_type(e, decoder)?.is_some(),
+                    _ => false,
+                };
+                if has_type {
+                    return visitor.visit_enum(var::EnumAccess::new(self.map.de));
+                }
+            }
             match self.map.de.next()? {
                 // Handles <field>UnitEnumVariant</field>
                 DeEvent::Start(e) => {
@@ -639,12 +716,47 @@ where
         }
     }
 
+    /// Deserializes text as a string and elements as maps. Elements without
+    /// attributes that contain only text, like
+    ///
+    /// ```xml
+    /// <any-tag>
+    ///   <key>value</key>
+    /// </any-tag>
+    /// ```
+    ///
+    /// are deserialized as strings, so they can be captured, for example, by
+    /// a `#[serde(flatten)]` map of strings. Empty elements are still deserialized
+    /// as empty maps, because this is how empty structs are represented.
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
         match self.map.de.peek()? {
             DeEvent::Text(_) => self.deserialize_str(visitor),
+            DeEvent::Start(e) if self.fixed_name && !has_attributes(e) => {
+                let start = match self.map.de.next()? {
+                    DeEvent::Start(e) => e,
+                    // SAFETY: we just peeked `Start` event
+                    _ => unreachable!(),
+                };
+                let is_text = matches!(self.map.de.peek()?, DeEvent::Text(_));
+                if is_text && self.map.de.is_end_after_peeked() {
+                    let text = match self.map.de.next()? {
+                        DeEvent::Text(e) => e.text,
+                        // SAFETY: we just peeked `Text` event
+                        _ => unreachable!(),
+                    };
+                    // Consume End
+                    self.map.de.next()?;
+                    match text {
+                        Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+                        Cow::Owned(s) => visitor.visit_string(s),
+                    }
+                } else {
+                    visitor.visit_map(ElementMapAccess::new(self.map.de, start, &[])?)
+                }
+            }
             _ => self.deserialize_map(visitor),
         }
     }
@@ -782,17 +894,57 @@ where
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Check if tag `start` is included in the `fields` list. `decoder` is used to
-/// get a string representation of a tag.
+/// get a string representation of a tag. If `rename` is set, field names are
+/// converted by it before comparison.
 ///
 /// Returns `true`, if `start` is not in the `fields` list and `false` otherwise.
 fn not_in(
     fields: &'static [&'static str],
+    rename: Option<fn(&str) -> String>,
     start: &BytesStart,
     decoder: Decoder,
 ) -> Result<bool, DeError> {
     let tag = decoder.decode(start.local_name().into_inner())?;
 
-    Ok(fields.iter().all(|&field| field != tag.as_ref()))
+    Ok(match rename {
+        Some(rename) => fields.iter().all(|&field| match field {
+            TEXT_KEY | VALUE_KEY => true,
+            _ => rename(field) != tag.as_ref(),
+        }),
+        None => fields.iter().all(|&field| field != tag.as_ref()),
+    })
+}
+
+/// Finds a struct field which name converted by `rename` is equal to `name`.
+/// Attribute names (with `@` prefix) are matched only with fields which names
+/// starts with `@` and the prefix itself is not passed to `rename`.
+fn find_renamed_field(
+    fields: &'static [&'static str],
+    rename: fn(&str) -> String,
+    name: &str,
+) -> Option<&'static str> {
+    fields.iter().copied().find(
+        |&field| match (field.strip_prefix('@'), name.strip_prefix('@')) {
+            (Some(field), Some(name)) => rename(field) == name,
+            (None, None) if field != TEXT_KEY && field != VALUE_KEY => rename(field) == name,
+            _ => false,
+        },
+    )
+}
+
+/// Deserializes a key of a map or a struct from a name of an attribute or
+/// an element. If names of fields should be converted, the name of a matched
+/// struct field is passed to `seed` instead of an XML name.
+fn deserialize_field_name<'de, K: DeserializeSeed<'de>>(
+    seed: K,
+    de: QNameDeserializer<'de, '_>,
+    fields: &'static [&'static str],
+    rename: Option<fn(&str) -> String>,
+) -> Result<K::Value, DeError> {
+    match rename.and_then(|rename| find_renamed_field(fields, rename, &de.name)) {
+        Some(field) => seed.deserialize(BorrowedStrDeserializer::<DeError>::new(field)),
+        None => seed.deserialize(de),
+    }
 }
 
 /// A filter that determines, what tags should form a sequence.
@@ -832,15 +984,26 @@ enum TagFilter<'de> {
     Include(BytesStart<'de>), //TODO: Need to store only name instead of a whole tag
     /// A `SeqAccess` interested in tags with any name, except explicitly listed.
     /// Excluded tags are used as struct field names and therefore should not
-    /// fall into a `$value` category
-    Exclude(&'static [&'static str]),
+    /// fall into a `$value` category. Field names are converted to tag names
+    /// using the function set by [`Deserializer::rename_fields`], if any
+    Exclude(&'static [&'static str], Option<fn(&str) -> String>),
 }
 
 impl<'de> TagFilter<'de> {
+    /// Returns the name of elements which this filter includes, or [`VALUE_KEY`]
+    /// if this filter accepts elements with any names
+    #[cfg(not(feature = "overlapped-lists"))]
+    fn name(&self) -> Vec<u8> {
+        match self {
+            Self::Include(n) => n.name().as_ref().to_vec(),
+            Self::Exclude(..) => VALUE_KEY.as_bytes().to_vec(),
+        }
+    }
+
     fn is_suitable(&self, start: &BytesStart, decoder: Decoder) -> Result<bool, DeError> {
         match self {
             Self::Include(n) => Ok(n.name() == start.name()),
-            Self::Exclude(fields) => not_in(fields, start, decoder),
+            Self::Exclude(fields, rename) => not_in(fields, *rename, start, decoder),
         }
     }
 }
@@ -925,9 +1088,14 @@ where
                     self.map.de.skip()?;
                     continue;
                 }
-                // Stop iteration when list elements ends
+                // Stop iteration when list elements ends and remember that
+                // list, so we could report an error if it will be continued later
                 #[cfg(not(feature = "overlapped-lists"))]
-                DeEvent::Start(e) if !self.filter.is_suitable(e, decoder)? => Ok(None),
+                DeEvent::Start(e) if !self.filter.is_suitable(e, decoder)? => {
+                    let name = self.filter.name();
+                    self.map.ended_seqs.push(name);
+                    Ok(None)
+                }
 
                 // Stop iteration after reaching a closing tag
                 // The matching tag name is guaranteed by the reader
@@ -961,6 +1129,64 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// An accessor to the children of an element, that deserializes each child
+/// element or text as a sequence item regardless of its name. Used to deserialize
+/// tuples when [`Deserializer::positional_tuples`] is enabled.
+///
+/// Iteration stops at the closing tag of the parent element, but that tag is
+/// not consumed.
+pub(crate) struct ChildrenSeqAccess<'de, 'd, R, E>
+where
+    R: XmlRead<'de>,
+    E: EntityResolver,
+{
+    /// Opening tag of the element whose children are iterated
+    pub start: BytesStart<'de>,
+    pub de: &'d mut Deserializer<'de, R, E>,
+}
+
+impl<'de, 'd, R, E> SeqAccess<'de> for ChildrenSeqAccess<'de, 'd, R, E>
+where
+    R: XmlRead<'de>,
+    E: EntityResolver,
+{
+    type Error = DeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DeError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.de.peek()? {
+            // Stop iteration after reaching a closing tag
+            // The matching tag name is guaranteed by the reader
+            DeEvent::End(e) => {
+                debug_assert_eq!(self.start.name(), e.name());
+                Ok(None)
+            }
+            // We cannot get `Eof` legally, because we always inside of the
+            // opened tag `self.start`
+            DeEvent::Eof => {
+                Err(Error::missed_end(self.start.name(), self.de.reader.decoder()).into())
+            }
+
+            DeEvent::Text(_) => match self.de.next()? {
+                DeEvent::Text(e) => seed.deserialize(TextDeserializer(e)).map(Some),
+                // SAFETY: we just checked that the next event is Text
+                _ => unreachable!(),
+            },
+            DeEvent::Start(_) => match self.de.next()? {
+                DeEvent::Start(start) => seed
+                    .deserialize(ElementDeserializer { start, de: self.de })
+                    .map(Some),
+                // SAFETY: we just checked that the next event is Start
+                _ => unreachable!(),
+            },
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
 /// A deserializer for a single tag item of a mixed sequence of tags and text.
 ///
 /// This deserializer are very similar to a [`MapValueDeserializer`] (when it
@@ -1085,6 +1311,20 @@ where
         SimpleTypeDeserializer::from_text(text).deserialize_seq(visitor)
     }
 
+    /// Representation of tuples the same as [sequences](#method.deserialize_seq),
+    /// unless [`Deserializer::positional_tuples`] is enabled. In the last case
+    /// the tuple is deserialized from the children of this element.
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.de.positional_tuples {
+            self.de.deserialize_children(self.start, visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
@@ -1130,9 +1370,18 @@ where
     where
         V: DeserializeSeed<'de>,
     {
+        let decoder = self.de.reader.decoder();
+        let seed = if self.de.xsi_type {
+            match var::xsi_type_variant(&self.start, decoder, seed)? {
+                Ok(name) => return Ok((name, self)),
+                Err(seed) => seed,
+            }
+        } else {
+            seed
+        };
         let name = seed.deserialize(QNameDeserializer::from_elem(
             self.start.raw_name(),
-            self.de.reader.decoder(),
+            decoder,
         )?)?;
         Ok((name, self))
     }
@@ -1186,27 +1435,54 @@ fn test_not_in() {
 
     let tag = BytesStart::new("tag");
 
-    assert_eq!(not_in(&[], &tag, Decoder::utf8()).unwrap(), true);
+    assert_eq!(not_in(&[], None, &tag, Decoder::utf8()).unwrap(), true);
     assert_eq!(
-        not_in(&["no", "such", "tags"], &tag, Decoder::utf8()).unwrap(),
+        not_in(&["no", "such", "tags"], None, &tag, Decoder::utf8()).unwrap(),
         true
     );
     assert_eq!(
-        not_in(&["some", "tag", "included"], &tag, Decoder::utf8()).unwrap(),
+        not_in(&["some", "tag", "included"], None, &tag, Decoder::utf8()).unwrap(),
         false
     );
 
     let tag_ns = BytesStart::new("ns1:tag");
     assert_eq!(
-        not_in(&["no", "such", "tags"], &tag_ns, Decoder::utf8()).unwrap(),
+        not_in(&["no", "such", "tags"], None, &tag_ns, Decoder::utf8()).unwrap(),
+        true
+    );
+    assert_eq!(
+        not_in(&["some", "tag", "included"], None, &tag_ns, Decoder::utf8()).unwrap(),
+        false
+    );
+    assert_eq!(
+        not_in(
+            &["some", "namespace", "ns1:tag"],
+            None,
+            &tag_ns,
+            Decoder::utf8()
+        )
+        .unwrap(),
+        true
+    );
+
+    let tag = BytesStart::new("tag-name");
+    let rename: fn(&str) -> String = |name| name.replace('_', "-");
+    assert_eq!(
+        not_in(&["tag_name"], None, &tag, Decoder::utf8()).unwrap(),
         true
     );
     assert_eq!(
-        not_in(&["some", "tag", "included"], &tag_ns, Decoder::utf8()).unwrap(),
+        not_in(&["tag_name"], Some(rename), &tag, Decoder::utf8()).unwrap(),
         false
     );
     assert_eq!(
-        not_in(&["some", "namespace", "ns1:tag"], &tag_ns, Decoder::utf8()).unwrap(),
+        not_in(
+            &["$value", "other_tag"],
+            Some(rename),
+            &tag,
+            Decoder::utf8()
+        )
+        .unwrap(),
         true
     );
 }
@@ -1842,7 +1842,7 @@ macro_rules! deserialize_num {
         {
             // No need to unescape because valid integer representations cannot be escaped
             let text = self.read_string()?;
-            match text.parse() {
+            match crate::utils::trim_xml_spaces(&text).parse() {
                 Ok(number) => visitor.$visit(number),
                 Err(_) => match text {
                     Cow::Borrowed(t) => visitor.visit_str(t),
@@ -1946,15 +1946,6 @@ macro_rules! deserialize_primitives {
             self.deserialize_unit(visitor)
         }
 
-        /// Representation of tuples the same as [sequences](#method.deserialize_seq).
-        #[inline]
-        fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, DeError>
-        where
-            V: Visitor<'de>,
-        {
-            self.deserialize_seq(visitor)
-        }
-
         /// Representation of named tuples the same as [unnamed tuples](#method.deserialize_tuple).
         #[inline]
         fn deserialize_tuple_struct<V>(
@@ -2001,6 +1992,7 @@ macro_rules! deserialize_primitives {
 
 mod key;
 mod map;
+mod path;
 mod resolver;
 mod simple_type;
 mod text;
@@ -2011,13 +2003,14 @@ pub use self::simple_type::SimpleTypeDeserializer;
 pub use crate::errors::serialize::DeError;
 
 use crate::{
-    de::map::ElementMapAccess,
+    de::map::{ChildrenSeqAccess, ElementMapAccess},
+    de::path::ElementPath,
     encoding::Decoder,
     errors::Error,
     events::{BytesCData, BytesEnd, BytesStart, BytesText, Event},
     name::QName,
     reader::Reader,
-    utils::CowRef,
+    utils::{is_whitespace, CowRef},
 };
 use serde::de::{
     self, Deserialize, DeserializeOwned, DeserializeSeed, IntoDeserializer, SeqAccess, Visitor,
@@ -2159,10 +2152,12 @@ struct XmlReader<'i, R: XmlRead<'i>, E: EntityResolver = PredefinedEntityResolve
     /// A source of low-level XML events
     reader: R,
     /// Intermediate event, that could be returned by the next call to `next()`.
-    /// If that is the `Text` event then leading spaces already trimmed, but
-    /// trailing spaces is not. Before the event will be returned, trimming of
-    /// the spaces could be necessary
+    /// If that is the `Text` event then it is not trimmed. Before the event will
+    /// be returned, trimming of the spaces could be necessary
     lookahead: Result<PayloadEvent<'i>, DeError>,
+    /// If `true`, leading spaces of a text after markup and trailing spaces of
+    /// a text before markup are trimmed
+    trim_text: bool,
 
     /// Used to resolve unknown entities that would otherwise cause the parser
     /// to return an [`EscapeError::UnrecognizedEntity`] error.
@@ -2180,6 +2175,7 @@ impl<'i, R: XmlRead<'i>, E: EntityResolver> XmlReader<'i, R, E> {
         Self {
             reader,
             lookahead,
+            trim_text: true,
             entity_resolver,
         }
     }
@@ -2189,6 +2185,11 @@ impl<'i, R: XmlRead<'i>, E: EntityResolver> XmlReader<'i, R, E> {
         matches!(self.lookahead, Ok(PayloadEvent::Eof))
     }
 
+    /// Returns `true` if the next event is an end tag
+    const fn next_is_end(&self) -> bool {
+        matches!(self.lookahead, Ok(PayloadEvent::End(_)))
+    }
+
     /// Read next event and put it in lookahead, return the current lookahead
     #[inline(always)]
     fn next_impl(&mut self) -> Result<PayloadEvent<'i>, DeError> {
@@ -2219,7 +2220,7 @@ impl<'i, R: XmlRead<'i>, E: EntityResolver> XmlReader<'i, R, E> {
 
             match self.next_impl()? {
                 PayloadEvent::Text(mut e) => {
-                    if self.current_event_is_last_text() {
+                    if self.trim_text && self.current_event_is_last_text() {
                         // FIXME: Actually, we should trim after decoding text, but now we trim before
                         e.inplace_trim_end();
                     }
@@ -2243,9 +2244,14 @@ impl<'i, R: XmlRead<'i>, E: EntityResolver> XmlReader<'i, R, E> {
                 PayloadEvent::Start(e) => Ok(DeEvent::Start(e)),
                 PayloadEvent::End(e) => Ok(DeEvent::End(e)),
                 PayloadEvent::Text(mut e) => {
-                    if self.current_event_is_last_text() && e.inplace_trim_end() {
+                    if self.trim_text {
+                        // Text always follows markup here, because consequent
+                        // texts are consumed by `drain_text`.
                         // FIXME: Actually, we should trim after decoding text, but now we trim before
-                        continue;
+                        e.inplace_trim_start();
+                        if self.current_event_is_last_text() && e.inplace_trim_end() {
+                            continue;
+                        }
                     }
                     self.drain_text(e.unescape_with(|entity| self.entity_resolver.resolve(entity))?)
                 }
@@ -2317,6 +2323,12 @@ where
 /// Deserialize from a reader. This method will do internal copies of data
 /// read from `reader`. If you want have a `&str` input and want to borrow
 /// as much as possible, use [`from_str`].
+///
+/// This also applies to in-memory readers, such as `&[u8]` or `Cursor<Vec<u8>>`:
+/// data is copied from them even if the whole document is already in memory.
+/// If such data is known to be UTF-8, convert it with [`std::str::from_utf8`]
+/// and use [`from_str`] which avoids copying and allows to deserialize types
+/// that borrow from the input.
 pub fn from_reader<R, T>(reader: R) -> Result<T, DeError>
 where
     R: BufRead,
@@ -2365,6 +2377,26 @@ where
 
     /// Buffer to store attribute name as a field name exposed to serde consumers
     key_buf: String,
+
+    /// Path to the currently deserialized element. `None` if path tracking is
+    /// disabled.
+    path: Option<ElementPath>,
+
+    /// If `true`, elements without attributes and content are deserialized
+    /// as `None` when `Option` is expected.
+    empty_element_as_none: bool,
+
+    /// If `true`, tuples are deserialized from the child elements of an element
+    /// instead of from a sequence of elements with the same name.
+    positional_tuples: bool,
+
+    /// If `true`, variants of enums are selected by the `xsi:type` attribute
+    /// of an element, if it is present.
+    xsi_type: bool,
+
+    /// Function that converts names of struct fields to names of XML elements
+    /// and attributes. `None` if names are used as is.
+    rename_fields: Option<fn(&str) -> String>,
 }
 
 impl<'de, R, E> Deserializer<'de, R, E>
@@ -2393,6 +2425,16 @@ where
             peek: None,
 
             key_buf: String::new(),
+
+            path: None,
+
+            empty_element_as_none: false,
+
+            positional_tuples: false,
+
+            xsi_type: false,
+
+            rename_fields: None,
         }
     }
 
@@ -2506,6 +2548,356 @@ where
         self
     }
 
+    /// Enables or disables tracking of the path to the currently deserialized
+    /// element. When enabled, errors returned by the deserializer will be wrapped
+    /// into [`DeError::AtPath`] which contains the path to the element where
+    /// the error was occurred.
+    ///
+    /// Path tracking is disabled by default, because it requires additional
+    /// allocations for each element in the document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use serde::Deserialize;
+    /// use quick_xml::de::Deserializer;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Item {
+    ///     price: u32,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Root {
+    ///     item: Vec<Item>,
+    /// }
+    ///
+    /// let mut de = Deserializer::from_str(
+    ///     "<root><item><price>1</price></item><item><price>x</price></item></root>"
+    /// );
+    /// de.track_path(true);
+    ///
+    /// let err = Root::deserialize(&mut de).unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "error at /root/item[2]/price: invalid type: string \"x\", expected u32",
+    /// );
+    /// ```
+    pub fn track_path(&mut self, enable: bool) -> &mut Self {
+        self.path = if enable {
+            Some(ElementPath::default())
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Controls how elements without attributes and content (`<tag/>` or
+    /// `<tag></tag>`) are deserialized when an `Option` is expected.
+    ///
+    /// By default (`false`) such elements are deserialized as `Some` with an
+    /// empty value, for example, `Some("")` for `Option<String>`, so the "present
+    /// but empty" element can be distinguished from the absent one, which is
+    /// always deserialized as `None`. When enabled, empty elements are
+    /// deserialized as `None` as well as absent ones.
+    ///
+    /// | XML                | `Option<String>` (`false`) | `Option<String>` (`true`)
+    /// |--------------------|----------------------------|--------------------------
+    /// | (no element)       | `None`                     | `None`
+    /// | `<tag/>`           | `Some("")`                 | `None`
+    /// | `<tag></tag>`      | `Some("")`                 | `None`
+    /// | `<tag>text</tag>`  | `Some("text")`             | `Some("text")`
+    ///
+    /// Elements with attributes are never considered empty. Note, that the
+    /// `xsi:nil` attribute is not interpreted by the deserializer, so the element
+    /// `<tag xsi:nil="true"/>` is deserialized as `Some` regardless of this option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use serde::Deserialize;
+    /// use quick_xml::de::Deserializer;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Root {
+    ///     tag: Option<String>,
+    /// }
+    ///
+    /// let mut de = Deserializer::from_str("<root><tag/></root>");
+    /// assert_eq!(Root::deserialize(&mut de).unwrap(), Root { tag: Some("".into()) });
+    ///
+    /// let mut de = Deserializer::from_str("<root><tag/></root>");
+    /// de.empty_element_as_none(true);
+    /// assert_eq!(Root::deserialize(&mut de).unwrap(), Root { tag: None });
+    /// ```
+    pub fn empty_element_as_none(&mut self, enable: bool) -> &mut Self {
+        self.empty_element_as_none = enable;
+        self
+    }
+
+    /// Controls how tuples, tuple structs and arrays are deserialized from an element.
+    ///
+    /// By default (`false`) a tuple is deserialized from a sequence of elements
+    /// with the same name, one element per tuple item, like sequences do. When
+    /// enabled, a tuple is deserialized from the child elements of a single element.
+    /// Children are mapped to tuple items in document order, their names are ignored.
+    /// Excess children are skipped.
+    ///
+    /// | XML                                  | `(i32, i32)` (`false`) | `(i32, i32)` (`true`)
+    /// |--------------------------------------|------------------------|----------------------
+    /// | `<point>1</point><point>2</point>`   | `(1, 2)`               | error
+    /// | `<point><x>1</x><y>2</y></point>`    | error                  | `(1, 2)`
+    ///
+    /// Tuples deserialized from a text (for example, from an attribute value or
+    /// from a `$text` field) are not affected by this option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use serde::Deserialize;
+    /// use quick_xml::de::Deserializer;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Root {
+    ///     point: (i32, i32),
+    /// }
+    ///
+    /// let mut de = Deserializer::from_str("<root><point><x>1</x><y>2</y></point></root>");
+    /// de.positional_tuples(true);
+    /// assert_eq!(Root::deserialize(&mut de).unwrap(), Root { point: (1, 2) });
+    /// ```
+    pub fn positional_tuples(&mut self, enable: bool) -> &mut Self {
+        self.positional_tuples = enable;
+        self
+    }
+
+    /// Controls how variants of enums are selected when an enum is deserialized
+    /// from an element.
+    ///
+    /// By default (`false`) a variant is selected by the name of an element.
+    /// When enabled, the value of the [`xsi:type`] attribute of an element, if
+    /// present, is used instead. The namespace prefix of the type is stripped,
+    /// so `xsi:type="ns:Circle"` selects the `Circle` variant. Elements without
+    /// this attribute are still deserialized using their names.
+    ///
+    /// The attribute is recognized by the prefix bound to the `http://www.w3.org/2001/XMLSchema-instance`
+    /// namespace on the element itself or, if the element does not declare such
+    /// binding, by the conventional `xsi` prefix, because that binding is usually
+    /// declared on the root element of a document.
+    ///
+    /// Note, that bindings declared on ancestor elements are not tracked. If a
+    /// document binds that namespace to another prefix on an ancestor element,
+    /// for example, `xmlns:i="http://www.w3.org/2001/XMLSchema-instance"` on
+    /// the root, then the `i:type` attributes of nested elements are not
+    /// recognized and those elements are deserialized using their names.
+    ///
+    /// This option is useful for SOAP and other XML Schema based documents where
+    /// the concrete type of a polymorphic element is given by `xsi:type`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use serde::Deserialize;
+    /// use quick_xml::de::Deserializer;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// enum Shape {
+    ///     Circle { radius: f64 },
+    ///     Square { side: f64 },
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Drawing {
+    ///     shape: Vec<Shape>,
+    /// }
+    ///
+    /// let xml = r#"
+    ///     <drawing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:g="urn:geometry">
+    ///         <shape xsi:type="g:Circle"><radius>1</radius></shape>
+    ///         <shape xsi:type="g:Square"><side>2</side></shape>
+    ///     </drawing>
+    /// "#;
+    /// let mut de = Deserializer::from_str(xml);
+    /// de.xsi_type(true);
+    /// assert_eq!(
+    ///     Drawing::deserialize(&mut de).unwrap(),
+    ///     Drawing {
+    ///         shape: vec![Shape::Circle { radius: 1.0 }, Shape::Square { side: 2.0 }],
+    ///     }
+    /// );
+    /// ```
+    ///
+    /// [`xsi:type`]: https://www.w3.org/TR/xmlschema-1/#xsi_type
+    pub fn xsi_type(&mut self, enable: bool) -> &mut Self {
+        self.xsi_type = enable;
+        self
+    }
+
+    /// Sets a function that converts names of struct fields to names of XML
+    /// elements and attributes, for example, to read `kebab-case` XML names
+    /// into `snake_case` fields without a `#[serde(rename)]` on each field.
+    ///
+    /// The function is applied to all fields of all structs in the document,
+    /// but not to the special `$text` and `$value` fields and not to keys of maps.
+    /// Because serde handles `#[serde(flatten)]` fields as maps, names of fields
+    /// of flattened structs are also not converted.
+    /// For fields which names starts with `@` the function receives the name
+    /// without `@`. Fields renamed by serde are also passed to the function.
+    ///
+    /// The same function should be passed to [`Serializer::rename_fields`] to
+    /// get a document that could be read back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use serde::Deserialize;
+    /// use quick_xml::de::Deserializer;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Book {
+    ///     #[serde(rename = "@page_count")]
+    ///     page_count: u32,
+    ///     first_author: String,
+    /// }
+    ///
+    /// let mut de = Deserializer::from_str(
+    ///     "<book page-count=\"42\"><first-author>Douglas Adams</first-author></book>"
+    /// );
+    /// de.rename_fields(|name| name.replace('_', "-"));
+    /// assert_eq!(
+    ///     Book::deserialize(&mut de).unwrap(),
+    ///     Book {
+    ///         page_count: 42,
+    ///         first_author: "Douglas Adams".into(),
+    ///     }
+    /// );
+    /// ```
+    ///
+    /// [`Serializer::rename_fields`]: crate::se::Serializer::rename_fields
+    pub fn rename_fields(&mut self, rename: fn(&str) -> String) -> &mut Self {
+        self.rename_fields = Some(rename);
+        self
+    }
+
+    /// Controls trimming of spaces around texts. Defaults to `true`.
+    ///
+    /// By default, leading spaces of a text after a tag and trailing spaces
+    /// of a text before a tag are trimmed, so indentation of a document does
+    /// not appear in deserialized strings. That, however, also removes the
+    /// significant spaces of [mixed content], where text is interleaved with
+    /// elements: `<p>Some <b>bold</b> text</p>` would be deserialized into
+    /// texts `"Some"` and `"text"`.
+    ///
+    /// When disabled, texts are deserialized as is. Texts between tags that
+    /// consist only of spaces are still skipped in both modes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use serde::Deserialize;
+    /// use quick_xml::de::Deserializer;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// enum Inline {
+    ///     #[serde(rename = "$text")]
+    ///     Text(String),
+    ///     #[serde(rename = "b")]
+    ///     Bold(String),
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Paragraph {
+    ///     #[serde(rename = "$value")]
+    ///     content: Vec<Inline>,
+    /// }
+    ///
+    /// let xml = "<p>Some <b>bold</b> text</p>";
+    ///
+    /// let mut de = Deserializer::from_str(xml);
+    /// assert_eq!(Paragraph::deserialize(&mut de).unwrap(), Paragraph {
+    ///     content: vec![
+    ///         Inline::Text("Some".into()),
+    ///         Inline::Bold("bold".into()),
+    ///         Inline::Text("text".into()),
+    ///     ],
+    /// });
+    ///
+    /// let mut de = Deserializer::from_str(xml);
+    /// de.trim_text(false);
+    /// assert_eq!(Paragraph::deserialize(&mut de).unwrap(), Paragraph {
+    ///     content: vec![
+    ///         Inline::Text("Some ".into()),
+    ///         Inline::Bold("bold".into()),
+    ///         Inline::Text(" text".into()),
+    ///     ],
+    /// });
+    /// ```
+    ///
+    /// [mixed content]: https://www.w3.org/TR/xml11/#sec-mixed-content
+    pub fn trim_text(&mut self, trim: bool) -> &mut Self {
+        self.reader.trim_text = trim;
+        self
+    }
+
+    /// If [`empty_element_as_none`] is enabled and the next element does not
+    /// have attributes and content, consumes it and returns `true`.
+    ///
+    /// [`empty_element_as_none`]: Self::empty_element_as_none
+    fn skip_empty_element(&mut self) -> Result<bool, DeError> {
+        if !self.empty_element_as_none {
+            return Ok(false);
+        }
+        let is_empty = match self.peek()? {
+            DeEvent::Start(e) => e.attributes_raw().iter().all(|b| is_whitespace(*b)),
+            _ => false,
+        };
+        if is_empty && self.next_is_end() {
+            self.next()?;
+            self.next()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Returns `true` if an event after the peeked one is an [`End`] event.
+    ///
+    /// [`End`]: DeEvent::End
+    #[cfg(feature = "overlapped-lists")]
+    fn next_is_end(&self) -> bool {
+        match self.read.get(1) {
+            Some(event) => matches!(event, DeEvent::End(_)),
+            None => self.reader.next_is_end(),
+        }
+    }
+    /// Returns `true` if an event after the peeked one is an [`End`] event.
+    ///
+    /// [`End`]: DeEvent::End
+    #[cfg(not(feature = "overlapped-lists"))]
+    fn next_is_end(&self) -> bool {
+        self.reader.next_is_end()
+    }
+
+    /// Calls `f` and wraps its error into [`DeError::AtPath`] if path tracking
+    /// is enabled and an error is not wrapped yet.
+    #[inline]
+    fn located<T, F>(&mut self, f: F) -> Result<T, DeError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, DeError>,
+    {
+        match (f(self), &self.path) {
+            (Err(e), Some(path)) if !matches!(e, DeError::AtPath(..)) => {
+                Err(DeError::at_path(path.to_string(), e))
+            }
+            (result, _) => result,
+        }
+    }
+
     #[cfg(feature = "overlapped-lists")]
     fn peek(&mut self) -> Result<&DeEvent<'de>, DeError> {
         if self.read.is_empty() {
@@ -2535,6 +2927,34 @@ where
     }
 
     fn next(&mut self) -> Result<DeEvent<'de>, DeError> {
+        let event = self.next_untracked()?;
+        if let Some(ref mut path) = self.path {
+            match event {
+                DeEvent::Start(ref e) => {
+                    let name = self.reader.decoder().decode(e.name().into_inner())?;
+                    path.push(name.into_owned());
+                }
+                DeEvent::End(_) => path.pop(),
+                _ => {}
+            }
+        }
+        Ok(event)
+    }
+
+    /// Returns `true` if the event that follows the [peeked] one is a [`DeEvent::End`].
+    /// Should be called only after a call to [`Self::peek()`].
+    ///
+    /// [peeked]: Self::peek
+    fn is_end_after_peeked(&self) -> bool {
+        #[cfg(feature = "overlapped-lists")]
+        if let Some(event) = self.read.get(1) {
+            return matches!(event, DeEvent::End(_));
+        }
+        matches!(self.reader.lookahead, Ok(PayloadEvent::End(_)))
+    }
+
+    /// Returns the next event without updating the path to the current element.
+    fn next_untracked(&mut self) -> Result<DeEvent<'de>, DeError> {
         // Replay skipped or peeked events
         #[cfg(feature = "overlapped-lists")]
         if let Some(event) = self.read.pop_front() {
@@ -2562,7 +2982,7 @@ where
     /// events and release internal buffers.
     #[cfg(feature = "overlapped-lists")]
     fn skip(&mut self) -> Result<(), DeError> {
-        let event = self.next()?;
+        let event = self.next_untracked()?;
         self.skip_event(event)?;
         match self.write.back() {
             // Skip all subtree, if we skip a start event
@@ -2570,7 +2990,7 @@ where
                 let end = e.name().as_ref().to_owned();
                 let mut depth = 0;
                 loop {
-                    let event = self.next()?;
+                    let event = self.next_untracked()?;
                     match event {
                         DeEvent::Start(ref e) if e.name().as_ref() == end => {
                             self.skip_event(event)?;
@@ -2708,6 +3128,26 @@ where
         }
     }
 
+    /// Deserializes a sequence from the children of the element `start`, which
+    /// opening tag was already consumed, and then consumes the rest of that element,
+    /// including its closing tag. Used when [positional tuples] are enabled.
+    ///
+    /// [positional tuples]: Self::positional_tuples
+    fn deserialize_children<V>(
+        &mut self,
+        start: BytesStart<'de>,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        let mut seq = ChildrenSeqAccess { start, de: self };
+        let value = visitor.visit_seq(&mut seq)?;
+        // Skip excess children and the closing tag
+        seq.de.read_to_end(seq.start.name())?;
+        Ok(value)
+    }
+
     /// Drops all events until event with [name](BytesEnd::name()) `name` won't be
     /// dropped. This method should be called after [`Self::next()`]
     #[cfg(feature = "overlapped-lists")]
@@ -2752,17 +3192,32 @@ where
                 }
             }
         }
+        self.close_path();
         Ok(())
     }
     #[cfg(not(feature = "overlapped-lists"))]
     fn read_to_end(&mut self, name: QName) -> Result<(), DeError> {
         // First one might be in self.peek
-        match self.next()? {
+        match self.next_untracked()? {
             DeEvent::Start(e) => self.reader.read_to_end(e.name())?,
-            DeEvent::End(e) if e.name() == name => return Ok(()),
+            DeEvent::End(e) if e.name() == name => {
+                self.close_path();
+                return Ok(());
+            }
             _ => (),
         }
-        self.reader.read_to_end(name)
+        self.reader.read_to_end(name)?;
+        self.close_path();
+        Ok(())
+    }
+
+    /// Registers closing of the current element in the path, if path tracking
+    /// is enabled. Used when events were consumed bypassing [`Self::next()`].
+    #[inline]
+    fn close_path(&mut self) {
+        if let Some(ref mut path) = self.path {
+            path.pop();
+        }
     }
 }
 
@@ -2806,7 +3261,12 @@ where
     ///
     /// If you already have a string use [`Self::from_str`] instead, because it
     /// will borrow instead of copy. If you have `&[u8]` which is known to represent
-    /// UTF-8, you can decode it first before using [`from_str`].
+    /// UTF-8, you can decode it first before using [`from_str`]. The same is true
+    /// for in-memory readers such as `Cursor<Vec<u8>>`: the data is copied even
+    /// though the whole document is already available.
+    ///
+    /// The internal buffer is reused between events, so after the largest event
+    /// was read no more reallocations are required.
     ///
     /// Deserializer created with this method will not resolve custom entities.
     pub fn from_reader(reader: R) -> Self {
@@ -2859,8 +3319,8 @@ where
     where
         V: Visitor<'de>,
     {
-        match self.next()? {
-            DeEvent::Start(e) => visitor.visit_map(ElementMapAccess::new(self, e, fields)?),
+        self.located(|de| match de.next()? {
+            DeEvent::Start(e) => visitor.visit_map(ElementMapAccess::new(de, e, fields)?),
             // SAFETY: The reader is guaranteed that we don't have unmatched tags
             // If we here, then out deserializer has a bug
             DeEvent::End(e) => unreachable!("{:?}", e),
@@ -2872,7 +3332,7 @@ where
                 Cow::Owned(s) => visitor.visit_string(s),
             },
             DeEvent::Eof => Err(DeError::UnexpectedEof),
-        }
+        })
     }
 
     /// Unit represented in XML as a `xs:element` or text/CDATA content.
@@ -2896,9 +3356,9 @@ where
     where
         V: Visitor<'de>,
     {
-        match self.next()? {
+        self.located(|de| match de.next()? {
             DeEvent::Start(s) => {
-                self.read_to_end(s.name())?;
+                de.read_to_end(s.name())?;
                 visitor.visit_unit()
             }
             DeEvent::Text(_) => visitor.visit_unit(),
@@ -2906,7 +3366,7 @@ where
             // If we here, then out deserializer has a bug
             DeEvent::End(e) => unreachable!("{:?}", e),
             DeEvent::Eof => Err(DeError::UnexpectedEof),
-        }
+        })
     }
 
     /// Forwards deserialization of the inner type. Always calls [`Visitor::visit_newtype_struct`]
@@ -2931,35 +3391,61 @@ where
     where
         V: Visitor<'de>,
     {
-        visitor.visit_enum(var::EnumAccess::new(self))
+        self.located(|de| visitor.visit_enum(var::EnumAccess::new(de)))
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, DeError>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_seq(self)
+        self.located(|de| visitor.visit_seq(de))
     }
 
-    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeError>
+    /// Representation of tuples the same as [sequences](#method.deserialize_seq),
+    /// unless [positional tuples] are enabled. In the last case the tuple is
+    /// deserialized from the children of the root element.
+    ///
+    /// [positional tuples]: Deserializer::positional_tuples
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, DeError>
     where
         V: Visitor<'de>,
     {
-        match self.peek()? {
-            DeEvent::Text(t) if t.is_empty() => visitor.visit_none(),
-            DeEvent::Eof => visitor.visit_none(),
-            _ => visitor.visit_some(self),
+        if self.positional_tuples {
+            if let DeEvent::Start(_) = self.peek()? {
+                return self.located(|de| match de.next()? {
+                    DeEvent::Start(e) => de.deserialize_children(e, visitor),
+                    // SAFETY: we just peeked `Start` event
+                    _ => unreachable!(),
+                });
+            }
         }
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.located(|de| {
+            if de.skip_empty_element()? {
+                return visitor.visit_none();
+            }
+            match de.peek()? {
+                DeEvent::Text(t) if t.is_empty() => visitor.visit_none(),
+                DeEvent::Eof => visitor.visit_none(),
+                _ => visitor.visit_some(de),
+            }
+        })
     }
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeError>
     where
         V: Visitor<'de>,
     {
-        match self.peek()? {
-            DeEvent::Text(_) => self.deserialize_str(visitor),
-            _ => self.deserialize_map(visitor),
-        }
+        self.located(|de| match de.peek()? {
+            DeEvent::Text(_) => de.deserialize_str(visitor),
+            _ => de.deserialize_map(visitor),
+        })
     }
 }
 
@@ -3008,13 +3494,14 @@ where
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Helper struct that contains a state for an algorithm of converting events
-/// from raw events to semi-trimmed events that is independent from a way of
-/// events reading.
+/// from raw events to payload events that is independent from a way of
+/// events reading. Skips texts consisting only of spaces between markup,
+/// trimming of other texts is performed by the [`XmlReader`].
 struct StartTrimmer {
-    /// If `true`, then leading whitespace will be removed from next returned
-    /// [`Event::Text`]. This field is set to `true` after reading each event
-    /// except [`Event::Text`] and [`Event::CData`], so [`Event::Text`] events
-    /// read right after them does not trimmed.
+    /// If `true`, then next returned [`Event::Text`] will be skipped if it
+    /// contains only whitespaces. This field is set to `true` after reading
+    /// each event except [`Event::Text`] and [`Event::CData`], so [`Event::Text`]
+    /// events read right after them are not skipped.
     trim_start: bool,
 }
 
@@ -3031,9 +3518,9 @@ impl StartTrimmer {
 
             // Do not trim next text event after Text or CDATA event
             Event::CData(e) => (PayloadEvent::CData(e), false),
-            Event::Text(mut e) => {
-                // If event is empty after trimming, skip it
-                if self.trim_start && e.inplace_trim_start() {
+            Event::Text(e) => {
+                // If event would be empty after trimming, skip it
+                if self.trim_start && e.iter().all(|b| is_whitespace(*b)) {
                     return None;
                 }
                 (PayloadEvent::Text(e), false)
@@ -3121,6 +3608,8 @@ impl<R: BufRead> IoReader<R> {
 impl<'i, R: BufRead> XmlRead<'i> for IoReader<R> {
     fn next(&mut self) -> Result<PayloadEvent<'static>, DeError> {
         loop {
+            // `clear` keeps the capacity, so the buffer grows only up to the size
+            // of the largest event and then is reused without reallocations
             self.buf.clear();
 
             let event = self.reader.read_event_into(&mut self.buf)?;
@@ -3873,6 +4362,8 @@ mod tests {
                 IllFormedError::MismatchedEndTag {
                     expected: "root".into(),
                     found: "other".into(),
+                    expected_position: 0,
+                    found_position: 6,
                 }
             ),
             x => panic!("Expected `Err(InvalidXml(IllFormed(_))`, but got `{:?}`", x),
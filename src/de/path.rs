@@ -0,0 +1,120 @@
+//! Contains a tracker of the path to the currently deserialized element, which
+//! is used to report location of deserialization errors.
+
+use std::fmt;
+
+/// Path from the document root to the element that is currently deserialized.
+///
+/// Each element in a path is represented by its name and its 1-based position
+/// among siblings with the same name. The path is formatted like an [XPath]
+/// expression, where position is shown only if the element is not the first
+/// sibling with the same name: `/root/items/item[2]/price`.
+///
+/// [XPath]: https://www.w3.org/TR/xpath-31/#abbrev
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ElementPath {
+    /// Names of the opened elements and their positions among siblings
+    opened: Vec<(String, usize)>,
+    /// Counters of already opened children of each opened element. The first
+    /// item contains counters for top-level elements of the document.
+    siblings: Vec<Vec<(String, usize)>>,
+    /// If `true`, the last element of `opened` was closed, but still reported
+    /// as the current element. Element is removed from the path only when the
+    /// next element is opened or closed, because errors related to the element
+    /// content can be detected only after its end tag was consumed.
+    closed: bool,
+}
+
+impl ElementPath {
+    /// Registers opening of a new element with the specified name.
+    pub fn push(&mut self, name: String) {
+        self.remove_closed();
+
+        let depth = self.opened.len();
+        if self.siblings.len() <= depth {
+            self.siblings.push(Vec::new());
+        }
+        let siblings = &mut self.siblings[depth];
+        let position = match siblings.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => {
+                *count += 1;
+                *count
+            }
+            None => {
+                siblings.push((name.clone(), 1));
+                1
+            }
+        };
+        self.opened.push((name, position));
+    }
+
+    /// Registers closing of the current element.
+    pub fn pop(&mut self) {
+        self.remove_closed();
+        self.closed = true;
+    }
+
+    fn remove_closed(&mut self) {
+        if self.closed {
+            self.closed = false;
+            self.opened.pop();
+            // Forget about children of the closed element
+            self.siblings.truncate(self.opened.len() + 1);
+        }
+    }
+}
+
+impl fmt::Display for ElementPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.opened.is_empty() {
+            return f.write_str("/");
+        }
+        for (name, position) in &self.opened {
+            write!(f, "/{}", name)?;
+            if *position > 1 {
+                write!(f, "[{}]", position)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn empty() {
+        let path = ElementPath::default();
+        assert_eq!(path.to_string(), "/");
+    }
+
+    #[test]
+    fn nested() {
+        let mut path = ElementPath::default();
+        path.push("root".into());
+        path.push("item".into());
+        assert_eq!(path.to_string(), "/root/item");
+
+        // Closed element still reported until next element will be opened
+        path.pop();
+        assert_eq!(path.to_string(), "/root/item");
+
+        path.push("item".into());
+        assert_eq!(path.to_string(), "/root/item[2]");
+
+        path.push("item".into());
+        assert_eq!(path.to_string(), "/root/item[2]/item");
+
+        path.pop();
+        path.pop();
+        path.push("other".into());
+        assert_eq!(path.to_string(), "/root/other");
+
+        path.pop();
+        path.pop();
+        path.push("root".into());
+        assert_eq!(path.to_string(), "/root[2]");
+    }
+}
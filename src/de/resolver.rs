@@ -75,6 +75,11 @@ pub trait EntityResolver {
     /// Called on contents of [`Event::DocType`] to capture declared entities.
     /// Can be called multiple times, for each parsed `<!DOCTYPE >` declaration.
     ///
+    /// External DTDs referenced by the document are never loaded automatically.
+    /// If you need entities declared in them, read the DTD yourself and pass its
+    /// content to this method before creating a deserializer, for example, as
+    /// `resolver.capture(BytesText::from_escaped(dtd))`.
+    ///
     /// [`Event::DocType`]: crate::events::Event::DocType
     fn capture(&mut self, doctype: BytesText) -> Result<(), Self::Error>;
 
@@ -7,11 +7,11 @@ use crate::de::Text;
 use crate::encoding::Decoder;
 use crate::errors::serialize::DeError;
 use crate::escape::unescape;
-use crate::utils::CowRef;
-use memchr::memchr;
+use crate::utils::{is_whitespace, trim_xml_spaces, CowRef};
 use serde::de::value::UnitDeserializer;
 use serde::de::{
-    DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, SeqAccess, VariantAccess, Visitor,
+    DeserializeSeed, Deserializer, EnumAccess, Error as _, IntoDeserializer, SeqAccess,
+    VariantAccess, Visitor,
 };
 use serde::serde_if_integer128;
 use std::borrow::Cow;
@@ -24,7 +24,8 @@ macro_rules! deserialize_num {
         where
             V: Visitor<'de>,
         {
-            let text: &str = self.content.as_ref();
+            // Leading and trailing whitespaces are allowed for numbers
+            let text = trim_xml_spaces(self.content.as_ref());
             match text.parse() {
                 Ok(number) => visitor.$visit(number),
                 Err(_) => self.content.deserialize_str(visitor),
@@ -344,7 +345,7 @@ impl<'de> VariantAccess<'de> for UnitOnly {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
-/// Iterator over string sub-slices delimited by one or several spaces.
+/// Iterator over string sub-slices delimited by one or several XML whitespaces.
 /// Contains decoded value of the `simpleType`.
 /// Iteration ends when list contains `None`.
 struct ListIter<'de, 'a> {
@@ -353,6 +354,18 @@ struct ListIter<'de, 'a> {
     /// If `true`, `content` in escaped form and should be unescaped before use
     escaped: bool,
 }
+impl<'de, 'a> ListIter<'de, 'a> {
+    /// Returns the number of items that was not consumed yet
+    fn remaining(&self) -> usize {
+        self.content.as_ref().map_or(0, |content| {
+            content
+                .as_str()
+                .split(|ch: char| ch.is_ascii() && is_whitespace(ch as u8))
+                .filter(|item| !item.is_empty())
+                .count()
+        })
+    }
+}
 impl<'de, 'a> SeqAccess<'de> for ListIter<'de, 'a> {
     type Error = DeError;
 
@@ -361,14 +374,12 @@ impl<'de, 'a> SeqAccess<'de> for ListIter<'de, 'a> {
         T: DeserializeSeed<'de>,
     {
         if let Some(mut content) = self.content.take() {
-            const DELIMITER: u8 = b' ';
-
             loop {
                 let string = content.as_str();
                 if string.is_empty() {
                     return Ok(None);
                 }
-                return match memchr(DELIMITER, string.as_bytes()) {
+                return match string.bytes().position(is_whitespace) {
                     // No delimiters in the `content`, deserialize it as a whole atomic
                     None => match content {
                         Content::Input(s) => seed.deserialize(AtomicDeserializer {
@@ -391,7 +402,7 @@ impl<'de, 'a> SeqAccess<'de> for ListIter<'de, 'a> {
                     // `content` started with a space, skip them all
                     Some(0) => {
                         // Skip all spaces
-                        let start = string.as_bytes().iter().position(|ch| *ch != DELIMITER);
+                        let start = string.bytes().position(|ch| !is_whitespace(ch));
                         content = match (start, content) {
                             // We cannot find any non-space character, so string contains only spaces
                             (None, _) => return Ok(None),
@@ -486,7 +497,8 @@ impl<'de, 'a> SeqAccess<'de> for ListIter<'de, 'a> {
 ///   deserializer;
 /// - sequences, tuples and tuple structs are deserialized as `xs:list`s. Only
 ///   sequences of primitive types is possible to deserialize this way and they
-///   should be delimited by a space (` `, `\t`, `\r`, or `\n`);
+///   should be delimited by a space (` `, `\t`, `\r`, or `\n`). Tuples, tuple
+///   structs and arrays require the list to have exactly the same number of items;
 /// - structs and maps delegates to [`Self::deserialize_str`] which calls
 ///   [`Visitor::visit_borrowed_str`] or [`Visitor::visit_string`]; it is responsibility
 ///   of the type to return an error if it does not able to process passed data;
@@ -704,12 +716,31 @@ impl<'de, 'a> Deserializer<'de> for SimpleTypeDeserializer<'de, 'a> {
     }
 
     /// Representation of tuples the same as [sequences][Self::deserialize_seq].
-    #[inline]
-    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    ///
+    /// Unlike sequences, tuples require that the list contains exactly `len`
+    /// items, otherwise [`DeError::Custom`] with an invalid length error is
+    /// returned.
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.deserialize_seq(visitor)
+        let content = match self.decode()? {
+            CowRef::Input(s) => Content::Input(s),
+            CowRef::Slice(s) => Content::Slice(s),
+            CowRef::Owned(s) => Content::Owned(s, 0),
+        };
+        let mut iter = ListIter {
+            content: Some(content),
+            escaped: self.escaped,
+        };
+        let value = visitor.visit_seq(&mut iter)?;
+        match iter.remaining() {
+            0 => Ok(value),
+            rest => Err(DeError::invalid_length(
+                len + rest,
+                &format!("a tuple of size {}", len).as_str(),
+            )),
+        }
     }
 
     /// Representation of named tuples the same as [unnamed tuples][Self::deserialize_tuple].
@@ -994,6 +1025,14 @@ mod tests {
         deserialized_to!(f32_: f32 = "1.23" => 1.23);
         deserialized_to!(f64_: f64 = "1.23" => 1.23);
 
+        // Leading and trailing whitespaces are allowed for numbers and booleans
+        deserialized_to_only!(bool_spaces: bool = " true\n" => true);
+        deserialized_to_only!(i32_spaces: i32 = " 42 " => 42);
+        deserialized_to_only!(i64_spaces: i64 = "\n-3\t" => -3);
+        deserialized_to_only!(u8_spaces: u8 = "\r\n3 " => 3);
+        deserialized_to_only!(f64_spaces: f64 = " 1.23\t" => 1.23);
+        deserialized_to_only!(string_spaces: String = " 42 " => " 42 ");
+
         deserialized_to!(char_unescaped: char = "h" => 'h');
         deserialized_to!(char_escaped: char = "&lt;" => '<');
 
@@ -1151,6 +1190,23 @@ mod tests {
             assert_eq!(seq.next_element::<&str>().unwrap(), None);
         }
 
+        /// Items can be delimited by any XML whitespace
+        #[test]
+        fn whitespaces() {
+            let mut seq = ListIter {
+                content: Some(Content::Input("\tabc\r\n def\tghi\n")),
+                escaped: true,
+            };
+
+            assert_eq!(seq.remaining(), 3);
+            assert_eq!(seq.next_element::<&str>().unwrap(), Some("abc"));
+            assert_eq!(seq.next_element::<&str>().unwrap(), Some("def"));
+            assert_eq!(seq.remaining(), 1);
+            assert_eq!(seq.next_element::<&str>().unwrap(), Some("ghi"));
+            assert_eq!(seq.next_element::<&str>().unwrap(), None);
+            assert_eq!(seq.remaining(), 0);
+        }
+
         #[test]
         fn mixed_types() {
             let mut seq = ListIter {
@@ -1237,6 +1293,22 @@ mod tests {
 
         simple_only!(utf8, identifier: Id = "Field" => Id::Field);
         simple_only!(utf8, ignored_any: Any = "any data" => Any(IgnoredAny));
+
+        // Tuples and arrays are deserialized from lists with exactly the same
+        // number of items
+        simple_only!(utf8, tuple_numbers: (f32, f32, u32, i8) = "0 0.5\t100\n -1"
+            => (0.0, 0.5, 100, -1));
+        simple_only!(utf8, tuple_strings: (String, &str) = " &lt;escaped  non-escaped "
+            => ("<escaped".to_string(), "non-escaped"));
+        simple_only!(utf8, array: [u32; 3] = "1 2 3" => [1, 2, 3]);
+        err!(utf8, tuple_too_short: (u32, u32, u32) = "1 2"
+             => Custom("invalid length 2, expected a tuple of size 3"));
+        err!(utf8, tuple_too_long: (u32, u32) = "1 2 3 4"
+             => Custom("invalid length 4, expected a tuple of size 2"));
+        err!(utf8, array_too_long: [&str; 2] = "a b c"
+             => Custom("invalid length 3, expected a tuple of size 2"));
+        err!(utf8, tuple_wrong_type: (u32, u32) = "1 two"
+             => Custom("invalid type: string \"two\", expected u32"));
     }
 
     #[cfg(feature = "encoding")]
@@ -121,6 +121,15 @@ impl<'de> Deserializer<'de> for TextDeserializer<'de> {
         SimpleTypeDeserializer::from_text_content(self.0).deserialize_seq(visitor)
     }
 
+    /// Representation of tuples the same as [sequences](#method.deserialize_seq).
+    #[inline]
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
     #[inline]
     fn deserialize_struct<V>(
         self,
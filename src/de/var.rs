@@ -4,11 +4,76 @@ use crate::{
     de::resolver::EntityResolver,
     de::simple_type::SimpleTypeDeserializer,
     de::{DeEvent, Deserializer, XmlRead, TEXT_KEY},
+    encoding::Decoder,
     errors::serialize::DeError,
+    events::BytesStart,
+    name::{PrefixDeclaration, QName},
 };
-use serde::de::value::BorrowedStrDeserializer;
+use serde::de::value::{BorrowedStrDeserializer, StringDeserializer};
 use serde::de::{self, DeserializeSeed, Deserializer as _, Visitor};
 
+/// The namespace of the [XML Schema instance] attributes, such as `xsi:type`.
+///
+/// [XML Schema instance]: https://www.w3.org/TR/xmlschema-1/#Instance_Document_Constructions
+const XSI_NAMESPACE: &[u8] = b"http://www.w3.org/2001/XMLSchema-instance";
+
+/// Returns the local part of the value of the `xsi:type` attribute of an element,
+/// for example, `Type` for `xsi:type="ns:Type"`.
+///
+/// The attribute is recognized by the prefix bound to the [`XSI_NAMESPACE`] on
+/// the element itself, or, if the element does not bind that namespace, by
+/// the conventional `xsi` prefix (because the binding usually is declared on
+/// the root element). Bindings of other prefixes on ancestor elements are not seen.
+pub(super) fn xsi_type(start: &BytesStart, decoder: Decoder) -> Result<Option<String>, DeError> {
+    let mut bound = None;
+    let mut xsi_rebound = false;
+    let mut types = Vec::new();
+    for attr in start.attributes() {
+        let attr = attr?;
+        if let Some(PrefixDeclaration::Named(prefix)) = attr.key.as_namespace_binding() {
+            if attr.value.as_ref() == XSI_NAMESPACE {
+                bound = Some(prefix);
+            } else if prefix == b"xsi" {
+                xsi_rebound = true;
+            }
+        } else if attr.key.local_name().as_ref() == b"type" {
+            if let Some(prefix) = attr.key.prefix() {
+                types.push((prefix.into_inner(), attr));
+            }
+        }
+    }
+    let prefix: &[u8] = match bound {
+        Some(prefix) => prefix,
+        None if !xsi_rebound => b"xsi",
+        None => return Ok(None),
+    };
+    match types.into_iter().find(|(p, _)| *p == prefix) {
+        Some((_, attr)) => {
+            let value = attr.decode_and_unescape_value(decoder)?;
+            let local = QName(value.trim().as_bytes()).local_name();
+            // The local name is a part of a valid UTF-8 string split by ASCII `:`
+            Ok(Some(String::from_utf8_lossy(local.as_ref()).into_owned()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Deserializes a variant name of an enum from the `xsi:type` attribute of
+/// the element, if it is present.
+pub(super) fn xsi_type_variant<'de, V>(
+    start: &BytesStart,
+    decoder: Decoder,
+    seed: V,
+) -> Result<Result<V::Value, V>, DeError>
+where
+    V: DeserializeSeed<'de>,
+{
+    match xsi_type(start, decoder)? {
+        Some(ty) => Ok(Ok(seed.deserialize(StringDeserializer::<DeError>::new(ty))?)),
+        None => Ok(Err(seed)),
+    }
+}
+
 /// An enum access
 pub struct EnumAccess<'de, 'd, R, E>
 where
@@ -41,7 +106,15 @@ where
         V: DeserializeSeed<'de>,
     {
         let decoder = self.de.reader.decoder();
+        let xsi_type = self.de.xsi_type;
         let (name, is_text) = match self.de.peek()? {
+            DeEvent::Start(e) if xsi_type => match xsi_type_variant(e, decoder, seed)? {
+                Ok(name) => (name, false),
+                Err(seed) => (
+                    seed.deserialize(QNameDeserializer::from_elem(e.raw_name(), decoder)?)?,
+                    false,
+                ),
+            },
             DeEvent::Start(e) => (
                 seed.deserialize(QNameDeserializer::from_elem(e.raw_name(), decoder)?)?,
                 false,
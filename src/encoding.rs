@@ -31,6 +31,12 @@ pub enum EncodingError {
     /// Input did not adhere to the given encoding
     #[cfg(feature = "encoding")]
     Other(&'static Encoding),
+    /// Input did not start with a byte order mark (BOM), but it was required
+    /// by the [`Config::require_bom`] option
+    ///
+    /// [`Config::require_bom`]: crate::reader::Config::require_bom
+    #[cfg(feature = "encoding")]
+    MissingBom,
 }
 
 impl From<Utf8Error> for EncodingError {
@@ -45,7 +51,7 @@ impl std::error::Error for EncodingError {
         match self {
             Self::Utf8(e) => Some(e),
             #[cfg(feature = "encoding")]
-            Self::Other(_) => None,
+            Self::Other(_) | Self::MissingBom => None,
         }
     }
 }
@@ -56,6 +62,8 @@ impl std::fmt::Display for EncodingError {
             Self::Utf8(e) => write!(f, "cannot decode input using UTF-8: {}", e),
             #[cfg(feature = "encoding")]
             Self::Other(encoding) => write!(f, "cannot decode input using {}", encoding.name()),
+            #[cfg(feature = "encoding")]
+            Self::MissingBom => f.write_str("input does not start with a byte order mark"),
         }
     }
 }
@@ -81,7 +89,12 @@ pub struct Decoder {
 }
 
 impl Decoder {
-    pub(crate) fn utf8() -> Self {
+    /// Creates a decoder that decodes bytes in UTF-8. Use it to decode events
+    /// returned by [`Reader::read_event_into_decoded()`], which content is
+    /// always in UTF-8.
+    ///
+    /// [`Reader::read_event_into_decoded()`]: crate::reader::Reader::read_event_into_decoded
+    pub fn utf8() -> Self {
         Decoder {
             #[cfg(feature = "encoding")]
             encoding: UTF_8,
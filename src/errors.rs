@@ -102,6 +102,10 @@ pub enum IllFormedError {
         expected: String,
         /// Name of actually closed tag
         found: String,
+        /// Position of the open tag (of its `<` character) in the input
+        expected_position: u64,
+        /// Position of the close tag (of its `<` character) in the input
+        found_position: u64,
     },
     /// A comment contains forbidden double-hyphen (`--`) sequence inside.
     ///
@@ -114,6 +118,33 @@ pub enum IllFormedError {
     /// [specification]: https://www.w3.org/TR/xml11/#sec-comments
     /// [configuration]: crate::reader::Config::check_comments
     DoubleHyphenInComment,
+    /// A text between markup is longer than allowed by the [configuration].
+    /// Contains the configured limit in bytes.
+    ///
+    /// [configuration]: crate::reader::Config::max_text_len
+    TextTooLong(usize),
+    /// A text contains forbidden `]]>` sequence.
+    ///
+    /// According to the [specification], the `]]>` sequence MUST NOT appear in
+    /// a text content, because it marks the end of a CDATA section.
+    ///
+    /// The quick-xml by default does not check that, but you can enable it in
+    /// the [configuration].
+    ///
+    /// [specification]: https://www.w3.org/TR/xml11/#NT-CharData
+    /// [configuration]: crate::reader::Config::check_characters
+    CDataEndInText,
+    /// An XML declaration (`<?xml ...?>`) is not at the beginning of the document.
+    ///
+    /// According to the [specification], the XML declaration MUST be the first
+    /// thing in the document, only a byte order mark is allowed before it.
+    ///
+    /// The quick-xml by default does not check that, but you can enable it in
+    /// the [configuration].
+    ///
+    /// [specification]: https://www.w3.org/TR/xml11/#sec-prolog-dtd
+    /// [configuration]: crate::reader::Config::check_decl_position
+    MisplacedDeclaration,
 }
 
 impl fmt::Display for IllFormedError {
@@ -136,14 +167,26 @@ impl fmt::Display for IllFormedError {
             Self::UnmatchedEndTag(tag) => {
                 write!(f, "close tag `</{}>` does not match any open tag", tag)
             }
-            Self::MismatchedEndTag { expected, found } => write!(
+            Self::MismatchedEndTag {
+                expected,
+                found,
+                expected_position,
+                found_position,
+            } => write!(
                 f,
-                "expected `</{}>`, but `</{}>` was found",
-                expected, found,
+                "expected `</{}>` for the tag opened at position {}, but `</{}>` was found at position {}",
+                expected, expected_position, found, found_position,
             ),
             Self::DoubleHyphenInComment => {
                 f.write_str("forbidden string `--` was found in a comment")
             }
+            Self::TextTooLong(max) => {
+                write!(f, "text is longer than the allowed {} bytes", max)
+            }
+            Self::CDataEndInText => f.write_str("forbidden string `]]>` was found in a text"),
+            Self::MisplacedDeclaration => {
+                f.write_str("an XML declaration is allowed only at the beginning of the document")
+            }
         }
     }
 }
@@ -152,6 +195,45 @@ impl std::error::Error for IllFormedError {}
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// A non-fatal oddity found in a document. Unlike errors, warnings do not
+/// interrupt reading, they are collected by the reader and can be retrieved
+/// with [`Reader::warnings()`].
+///
+/// Each kind of warnings should be enabled in the reader [configuration].
+///
+/// [`Reader::warnings()`]: crate::reader::Reader::warnings
+/// [configuration]: crate::reader::Config
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A byte order mark (BOM, `U+FEFF`) was found in a text not at the start
+    /// of a document. Contains the position of the first byte of the BOM in
+    /// the input.
+    ///
+    /// Such character is interpreted as a ZERO WIDTH NO-BREAK SPACE, which is
+    /// allowed in the text content, but usually appears as a result of
+    /// concatenation of several documents.
+    ///
+    /// This warning is reported only if it is [enabled].
+    ///
+    /// [enabled]: crate::reader::Config::warn_mid_document_bom
+    MidDocumentBom(u64),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MidDocumentBom(position) => write!(
+                f,
+                "byte order mark (U+FEFF) found in the middle of the document at position {}",
+                position
+            ),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
 /// The error type used by this crate.
 #[derive(Clone, Debug)]
 pub enum Error {
@@ -161,6 +243,15 @@ pub enum Error {
     Io(Arc<IoError>),
     /// The document does not corresponds to the XML grammar.
     Syntax(SyntaxError),
+    /// The document does not corresponds to the XML grammar. Contains the
+    /// beginning of the markup in which the error occurred (including `<`),
+    /// truncated to a few dozens of bytes.
+    ///
+    /// This error is returned instead of [`Error::Syntax`] only if capturing
+    /// of the context is [enabled] and the reader reads from a byte slice.
+    ///
+    /// [enabled]: crate::reader::Config::syntax_error_context
+    SyntaxWithContext(SyntaxError, String),
     /// The document is not [well-formed](https://www.w3.org/TR/xml11/#dt-wellformed).
     IllFormed(IllFormedError),
     /// Attribute parsing error
@@ -174,6 +265,27 @@ pub enum Error {
 }
 
 impl Error {
+    /// Returns the underlying I/O error if this is an [`Error::Io`], otherwise
+    /// returns `None`. Allows to distinguish failures of the underlying source
+    /// from problems in the XML document itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use std::io::{Error as IoError, ErrorKind};
+    /// use quick_xml::Error;
+    ///
+    /// let error = Error::from(IoError::new(ErrorKind::UnexpectedEof, "eof"));
+    /// assert_eq!(error.as_io_error().map(|e| e.kind()), Some(ErrorKind::UnexpectedEof));
+    /// ```
+    pub fn as_io_error(&self) -> Option<&IoError> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+
     pub(crate) fn missed_end(name: QName, decoder: Decoder) -> Self {
         match decoder.decode(name.as_ref()) {
             Ok(name) => IllFormedError::MissingEndTag(name.into()).into(),
@@ -236,6 +348,29 @@ impl From<NamespaceError> for Error {
     }
 }
 
+impl From<Error> for IoError {
+    /// Converts the error to the [`std::io::Error`], so it can be used with `?`
+    /// in functions that return [`std::io::Result`].
+    ///
+    /// The [`Error::Io`] is returned as is. If the I/O error is shared with
+    /// other clones of the error, a new error of the same kind that wraps
+    /// the shared error is created. All other errors are wrapped into the error
+    /// of the [`InvalidData`] kind and can be retrieved from it using
+    /// [`std::io::Error::into_inner`]. The message of the error is preserved
+    /// in both cases.
+    ///
+    /// [`InvalidData`]: std::io::ErrorKind::InvalidData
+    fn from(error: Error) -> IoError {
+        match error {
+            Error::Io(e) => match Arc::try_unwrap(e) {
+                Ok(e) => e,
+                Err(e) => IoError::new(e.kind(), e),
+            },
+            _ => IoError::new(std::io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
 /// A specialized `Result` type where the error is hard-wired to [`Error`].
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -244,6 +379,9 @@ impl fmt::Display for Error {
         match self {
             Self::Io(e) => write!(f, "I/O error: {}", e),
             Self::Syntax(e) => write!(f, "syntax error: {}", e),
+            Self::SyntaxWithContext(e, context) => {
+                write!(f, "syntax error: {} (near `{}`)", e, context)
+            }
             Self::IllFormed(e) => write!(f, "ill-formed document: {}", e),
             Self::InvalidAttr(e) => write!(f, "error while parsing attribute: {}", e),
             Self::Encoding(e) => e.fmt(f),
@@ -256,8 +394,10 @@ impl fmt::Display for Error {
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Io(e) => Some(e),
+            // Return the error itself instead of `Arc` to make it downcastable to `IoError`
+            Self::Io(e) => Some(e.as_ref()),
             Self::Syntax(e) => Some(e),
+            Self::SyntaxWithContext(e, _) => Some(e),
             Self::IllFormed(e) => Some(e),
             Self::InvalidAttr(e) => Some(e),
             Self::Encoding(e) => Some(e),
@@ -308,6 +448,30 @@ pub mod serialize {
         /// exceeded. The limit was provided as an argument
         #[cfg(feature = "overlapped-lists")]
         TooManyEvents(NonZeroUsize),
+        /// An error occurred while deserializing an element at the specified path.
+        /// The path is formatted like `/root/items/item[2]/price`.
+        ///
+        /// This error is returned only if tracking of paths is [enabled].
+        ///
+        /// [enabled]: crate::de::Deserializer::track_path
+        AtPath(String, Box<DeError>),
+        /// Deserialized struct does not have a field with the specified name.
+        /// Contains the name of an unexpected element or attribute (attribute
+        /// names are prefixed with `@`) and the list of fields that the struct
+        /// expects.
+        ///
+        /// This error is returned when the struct is marked with
+        /// `#[serde(deny_unknown_fields)]`.
+        UnknownField(String, &'static [&'static str]),
+        /// Elements of a sequence are interleaved with other elements, but
+        /// feature [`overlapped-lists`] is not activated. Contains the name of
+        /// the element that continues the already ended sequence (or `$value`
+        /// for a sequence of any elements).
+        ///
+        /// This error is never returned when feature [`overlapped-lists`] is activated.
+        ///
+        /// [`overlapped-lists`]: ../index.html#overlapped-lists
+        NonContiguousSequence(String),
     }
 
     impl fmt::Display for DeError {
@@ -324,6 +488,30 @@ pub mod serialize {
                 Self::UnexpectedEof => f.write_str("unexpected `Event::Eof`"),
                 #[cfg(feature = "overlapped-lists")]
                 Self::TooManyEvents(s) => write!(f, "deserializer buffered {} events, limit exceeded", s),
+                Self::AtPath(path, e) => write!(f, "error at {}: {}", path, e),
+                Self::UnknownField(field, expected) => {
+                    match field.strip_prefix('@') {
+                        Some(name) => write!(f, "unknown attribute `{}`, ", name)?,
+                        None => write!(f, "unknown field `{}`, ", field)?,
+                    }
+                    match expected {
+                        [] => f.write_str("there are no fields"),
+                        [name] => write!(f, "expected `{}`", name),
+                        [names @ .., last] => {
+                            f.write_str("expected one of ")?;
+                            for name in names {
+                                write!(f, "`{}`, ", name)?;
+                            }
+                            write!(f, "`{}`", last)
+                        }
+                    }
+                }
+                Self::NonContiguousSequence(name) => write!(
+                    f,
+                    "elements of sequence `{}` are interleaved with other elements; \
+                     activate the `overlapped-lists` feature to deserialize such sequences",
+                    name
+                ),
             }
         }
     }
@@ -332,15 +520,29 @@ pub mod serialize {
         fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
             match self {
                 Self::InvalidXml(e) => Some(e),
+                Self::AtPath(_, e) => Some(e.as_ref()),
                 _ => None,
             }
         }
     }
 
+    impl DeError {
+        /// Creates an error that wraps `inner` error and remembers the path to
+        /// the element where it was occurred.
+        #[inline]
+        pub fn at_path<P: Into<String>>(path: P, inner: DeError) -> Self {
+            Self::AtPath(path.into(), Box::new(inner))
+        }
+    }
+
     impl serde::de::Error for DeError {
         fn custom<T: fmt::Display>(msg: T) -> Self {
             Self::Custom(msg.to_string())
         }
+
+        fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+            Self::UnknownField(field.to_string(), expected)
+        }
     }
 
     impl From<Error> for DeError {
@@ -410,7 +612,7 @@ pub mod serialize {
     impl ::std::error::Error for SeError {
         fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
             match self {
-                Self::Io(e) => Some(e),
+                Self::Io(e) => Some(e.as_ref()),
                 _ => None,
             }
         }
@@ -1,7 +1,14 @@
 //! Manage xml character escapes
+//!
+//! Escaping and unescaping functions accept and return UTF-8 strings (`&str`
+//! and `Cow<str>`), so no conversion from bytes is required. The input is
+//! borrowed when nothing is replaced. The [`needs_escaping`] and
+//! [`needs_unescaping`] checks accept raw bytes, so they can be applied to
+//! the content of events before decoding it.
 
-use memchr::memchr2_iter;
+use memchr::{memchr, memchr2, memchr2_iter, memchr3, memchr3_iter, memchr_iter};
 use std::borrow::Cow;
+use std::iter::Peekable;
 use std::num::ParseIntError;
 use std::ops::Range;
 
@@ -50,6 +57,10 @@ pub enum EscapeError {
     /// Attempt to parse character reference (`&#<dec-number>;` or `&#x<hex-number>;`)
     /// was unsuccessful, not all characters are decimal or hexadecimal numbers.
     InvalidCharRef(ParseCharRefError),
+    /// The character at the specified position cannot appear in an XML 1.0
+    /// document even in escaped form, for example, `\u{0}` or other control
+    /// characters except tab, line feed and carriage return.
+    IllegalCharacter(usize, char),
 }
 
 impl std::fmt::Display for EscapeError {
@@ -66,6 +77,11 @@ impl std::fmt::Display for EscapeError {
             Self::InvalidCharRef(e) => {
                 write!(f, "invalid character reference: {}", e)
             }
+            Self::IllegalCharacter(pos, ch) => write!(
+                f,
+                "at {}: character U+{:04X} is not permitted in XML",
+                pos, *ch as u32
+            ),
         }
     }
 }
@@ -79,18 +95,48 @@ impl std::error::Error for EscapeError {
     }
 }
 
-/// Escapes an `&str` and replaces all xml special characters (`<`, `>`, `&`, `'`, `"`)
-/// with their corresponding xml escaped value.
+/// Checks whether the raw data contains any of xml special characters (`<`, `>`,
+/// `&`, `'`, `"`), that is, whether [`escape`] would change it.
 ///
-/// This function performs following replacements:
+/// This check is much cheaper than the escaping itself and does not allocate,
+/// so it can be used to decide whether text should be escaped at all.
 ///
-/// | Character | Replacement
-/// |-----------|------------
-/// | `<`       | `&lt;`
-/// | `>`       | `&gt;`
-/// | `&`       | `&amp;`
-/// | `'`       | `&apos;`
-/// | `"`       | `&quot;`
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use quick_xml::escape::needs_escaping;
+///
+/// assert_eq!(needs_escaping(b"just a text"), false);
+/// assert_eq!(needs_escaping(b"Tom & Jerry"), true);
+/// assert_eq!(needs_escaping(b"it's"), true);
+/// ```
+pub fn needs_escaping(raw: &[u8]) -> bool {
+    memchr3(b'<', b'>', b'&', raw).is_some() || memchr2(b'\'', b'"', raw).is_some()
+}
+
+/// Checks whether the raw data contains any references (`&...;`) that can be
+/// unescaped, that is, whether [`unescape`] can change it.
+///
+/// Only the presence of the `&` character is checked, so the function returns
+/// `true` also for text with malformed references, for which [`unescape`]
+/// returns an error.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use quick_xml::escape::needs_unescaping;
+///
+/// assert_eq!(needs_unescaping(b"just a text"), false);
+/// assert_eq!(needs_unescaping(b"Tom &amp; Jerry"), true);
+/// ```
+pub fn needs_unescaping(raw: &[u8]) -> bool {
+    memchr(b'&', raw).is_some()
+}
+
+/// Escapes an `&str` and replaces all xml special characters (`<`, `>`, `&`, `'`, `"`)
+/// with their corresponding xml escaped value.
 ///
 /// This function performs following replacements:
 ///
@@ -102,7 +148,16 @@ impl std::error::Error for EscapeError {
 /// | `'`       | `&apos;`
 /// | `"`       | `&quot;`
 pub fn escape<'a>(raw: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
-    _escape(raw, |ch| matches!(ch, b'<' | b'>' | b'&' | b'\'' | b'\"'))
+    let raw = raw.into();
+    let bytes = raw.as_bytes();
+    let positions = Merge::new(
+        memchr3_iter(b'<', b'>', b'&', bytes),
+        memchr2_iter(b'\'', b'"', bytes),
+    );
+    match escape_at(bytes, positions) {
+        Some(escaped) => Cow::Owned(escaped),
+        None => raw,
+    }
 }
 
 /// Escapes an `&str` and replaces xml special characters (`<`, `>`, `&`)
@@ -118,16 +173,13 @@ pub fn escape<'a>(raw: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
 /// | `<`       | `&lt;`
 /// | `>`       | `&gt;`
 /// | `&`       | `&amp;`
-///
-/// This function performs following replacements:
-///
-/// | Character | Replacement
-/// |-----------|------------
-/// | `<`       | `&lt;`
-/// | `>`       | `&gt;`
-/// | `&`       | `&amp;`
 pub fn partial_escape<'a>(raw: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
-    _escape(raw, |ch| matches!(ch, b'<' | b'>' | b'&'))
+    let raw = raw.into();
+    let bytes = raw.as_bytes();
+    match escape_at(bytes, memchr3_iter(b'<', b'>', b'&', bytes)) {
+        Some(escaped) => Cow::Owned(escaped),
+        None => raw,
+    }
 }
 
 /// XML standard [requires] that only `<` and `&` was escaped in text content or
@@ -135,35 +187,88 @@ pub fn partial_escape<'a>(raw: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
 /// for compatibility with SGML they also should be escaped. Practically, escaping
 /// only those characters is enough.
 ///
+/// The only exception is the `]]>` sequence, which is [not allowed] in text content,
+/// so `>` is escaped when it follows `]]`.
+///
 /// This function performs following replacements:
 ///
-/// | Character | Replacement
-/// |-----------|------------
-/// | `<`       | `&lt;`
-/// | `&`       | `&amp;`
+/// | Character       | Replacement
+/// |-----------------|------------
+/// | `<`             | `&lt;`
+/// | `&`             | `&amp;`
+/// | `>` after `]]`  | `&gt;`
 ///
 /// [requires]: https://www.w3.org/TR/xml11/#syntax
+/// [not allowed]: https://www.w3.org/TR/xml11/#NT-CharData
 pub fn minimal_escape<'a>(raw: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
-    _escape(raw, |ch| matches!(ch, b'<' | b'&'))
+    let raw = raw.into();
+    let bytes = raw.as_bytes();
+    let positions = Merge::new(
+        memchr2_iter(b'<', b'&', bytes),
+        memchr_iter(b'>', bytes).filter(|&i| i >= 2 && &bytes[i - 2..i] == b"]]"),
+    );
+    match escape_at(bytes, positions) {
+        Some(escaped) => Cow::Owned(escaped),
+        None => raw,
+    }
 }
 
-/// Escapes an `&str` and replaces a subset of xml special characters (`<`, `>`,
-/// `&`, `'`, `"`) with their corresponding xml escaped value.
-pub(crate) fn _escape<'a, F: Fn(u8) -> bool>(
+/// Escapes an `&str` and replaces each ASCII character for which `should_escape`
+/// returns `true` with its escaped value. Use this function when you need to
+/// escape a set of characters other than the sets used by [`escape`],
+/// [`partial_escape`] and [`minimal_escape`].
+///
+/// The `should_escape` is called only for ASCII characters, because escaping of
+/// one byte of a multi-byte UTF-8 sequence would produce an invalid string.
+///
+/// This function performs following replacements:
+///
+/// | Character | Replacement
+/// |-----------|------------
+/// | `<`       | `&lt;`
+/// | `>`       | `&gt;`
+/// | `&`       | `&amp;`
+/// | `'`       | `&apos;`
+/// | `"`       | `&quot;`
+/// | other     | `&#N;`, where `N` is a decimal code of a character
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use quick_xml::escape::escape_with;
+///
+/// // Escape only `&`
+/// assert_eq!(escape_with("<a&b>", |ch| ch == b'&'), "<a&amp;b>");
+/// // Escape also spaces
+/// assert_eq!(escape_with("a & b", |ch| ch == b'&' || ch == b' '), "a&#32;&amp;&#32;b");
+/// ```
+pub fn escape_with<'a, F: Fn(u8) -> bool>(
     raw: impl Into<Cow<'a, str>>,
-    escape_chars: F,
+    should_escape: F,
 ) -> Cow<'a, str> {
     let raw = raw.into();
     let bytes = raw.as_bytes();
+    let positions = bytes
+        .iter()
+        .enumerate()
+        .filter(|(_, &b)| b.is_ascii() && should_escape(b))
+        .map(|(i, _)| i);
+    match escape_at(bytes, positions) {
+        Some(escaped) => Cow::Owned(escaped),
+        None => raw,
+    }
+}
+
+/// Replaces characters at the specified positions with their escaped values.
+/// Positions should be increasing. Parts between them are copied in bulk.
+///
+/// Returns `None` if there are no positions, i.e. nothing to escape.
+fn escape_at(bytes: &[u8], positions: impl Iterator<Item = usize>) -> Option<String> {
     let mut escaped = None;
-    let mut iter = bytes.iter();
     let mut pos = 0;
-    while let Some(i) = iter.position(|&b| escape_chars(b)) {
-        if escaped.is_none() {
-            escaped = Some(Vec::with_capacity(raw.len()));
-        }
-        let escaped = escaped.as_mut().expect("initialized");
-        let new_pos = pos + i;
+    for new_pos in positions {
+        let escaped = escaped.get_or_insert_with(|| Vec::with_capacity(bytes.len()));
         escaped.extend_from_slice(&bytes[pos..new_pos]);
         match bytes[new_pos] {
             b'<' => escaped.extend_from_slice(b"&lt;"),
@@ -179,14 +284,13 @@ pub(crate) fn _escape<'a, F: Fn(u8) -> bool>(
             b'\n' => escaped.extend_from_slice(b"&#10;"),
             b'\r' => escaped.extend_from_slice(b"&#13;"),
             b' ' => escaped.extend_from_slice(b"&#32;"),
-            _ => unreachable!(
-                "Only '<', '>','\', '&', '\"', '\\t', '\\r', '\\n', and ' ' are escaped"
-            ),
+            // Other characters could be escaped only by `escape_with`
+            b => escaped.extend_from_slice(format!("&#{};", b).as_bytes()),
         }
         pos = new_pos + 1;
     }
 
-    if let Some(mut escaped) = escaped {
+    escaped.map(|mut escaped| {
         if let Some(raw) = bytes.get(pos..) {
             escaped.extend_from_slice(raw);
         }
@@ -194,9 +298,37 @@ pub(crate) fn _escape<'a, F: Fn(u8) -> bool>(
         // so all slices that was put to the `escaped` is a valid UTF-8 encoded strings
         // TODO: Can be replaced with `unsafe { String::from_utf8_unchecked() }`
         // if unsafe code will be allowed
-        Cow::Owned(String::from_utf8(escaped).unwrap())
-    } else {
-        raw
+        String::from_utf8(escaped).unwrap()
+    })
+}
+
+/// Merges two increasing sequences of positions into one increasing sequence.
+/// Used to combine results of several `memchr` searches, because each of them
+/// can look only for up to three different bytes.
+struct Merge<A: Iterator<Item = usize>, B: Iterator<Item = usize>> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A: Iterator<Item = usize>, B: Iterator<Item = usize>> Merge<A, B> {
+    fn new(a: A, b: B) -> Self {
+        Self {
+            a: a.peekable(),
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<A: Iterator<Item = usize>, B: Iterator<Item = usize>> Iterator for Merge<A, B> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) if a <= b => self.a.next(),
+            (Some(_), Some(_)) => self.b.next(),
+            (Some(_), None) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
     }
 }
 
@@ -251,8 +383,66 @@ pub fn unescape(raw: &str) -> Result<Cow<str>, EscapeError> {
 /// [HTML5 escapes]: https://dev.w3.org/html5/html-author/charref
 /// [requirements]: https://www.w3.org/TR/xml11/#intern-replacement
 pub fn unescape_with<'input, 'entity, F>(
+    raw: &'input str,
+    resolve_entity: F,
+) -> Result<Cow<'input, str>, EscapeError>
+where
+    // the lifetime of the output comes from a capture or is `'static`
+    F: FnMut(&str) -> Option<&'entity str>,
+{
+    unescape_impl(raw, resolve_entity, false)
+}
+
+/// Unescape an `&str` and replaces all character references and predefined
+/// entities (`&...;`) into their corresponding value, but keeps references to
+/// unknown entities as is instead of returning [`EscapeError::UnrecognizedEntity`].
+///
+/// This is useful when the text should be passed to a downstream stage (for
+/// example, a template processor) which resolves custom entities by itself.
+/// Note, that after unescaping it is impossible to distinguish a preserved
+/// entity reference from the escaped one, so `&amp;foo;` and `&foo;` both
+/// become `&foo;`.
+///
+/// If feature [`escape-html`] is enabled, then recognizes all [HTML5 escapes].
+///
+/// # Example
+///
+/// ```
+/// # use quick_xml::escape::partial_unescape;
+/// # use pretty_assertions::assert_eq;
+/// assert_eq!(
+///     partial_unescape("&lt;&custom;&#x3E;").unwrap(),
+///     "<&custom;>"
+/// );
+/// ```
+///
+/// [`escape-html`]: ../index.html#escape-html
+/// [HTML5 escapes]: https://dev.w3.org/html5/html-author/charref
+pub fn partial_unescape(raw: &str) -> Result<Cow<str>, EscapeError> {
+    partial_unescape_with(raw, resolve_predefined_entity)
+}
+
+/// Unescape an `&str` as [`unescape_with`] does, but keeps references to entities
+/// that cannot be resolved by `resolve_entity` as is instead of returning
+/// [`EscapeError::UnrecognizedEntity`]. See [`partial_unescape`] for details.
+pub fn partial_unescape_with<'input, 'entity, F>(
+    raw: &'input str,
+    resolve_entity: F,
+) -> Result<Cow<'input, str>, EscapeError>
+where
+    // the lifetime of the output comes from a capture or is `'static`
+    F: FnMut(&str) -> Option<&'entity str>,
+{
+    unescape_impl(raw, resolve_entity, true)
+}
+
+/// Common implementation of [`unescape_with`] and [`partial_unescape_with`].
+/// If `keep_unknown` is `true`, unrecognized entities are copied to the result
+/// as is, otherwise an error is returned for them.
+fn unescape_impl<'input, 'entity, F>(
     raw: &'input str,
     mut resolve_entity: F,
+    keep_unknown: bool,
 ) -> Result<Cow<'input, str>, EscapeError>
 where
     // the lifetime of the output comes from a capture or is `'static`
@@ -279,6 +469,8 @@ where
                     unescaped.push_str(codepoint.encode_utf8(&mut [0u8; 4]));
                 } else if let Some(value) = resolve_entity(pat) {
                     unescaped.push_str(value);
+                } else if keep_unknown {
+                    unescaped.push_str(&raw[start..=end]);
                 } else {
                     return Err(EscapeError::UnrecognizedEntity(
                         start + 1..end,
@@ -1820,6 +2012,15 @@ pub const fn resolve_html5_entity(entity: &str) -> Option<&'static str> {
     Some(s)
 }
 
+/// Checks whether the character matches the [`Char`] production of XML 1.0,
+/// i.e. can be represented in a document in the literal or escaped form.
+///
+/// [`Char`]: https://www.w3.org/TR/xml/#NT-Char
+#[inline]
+pub(crate) const fn is_xml10_char(ch: char) -> bool {
+    matches!(ch, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..='\u{10FFFF}')
+}
+
 fn parse_number(num: &str) -> Result<char, ParseCharRefError> {
     let code = if let Some(hex) = num.strip_prefix('x') {
         from_str_radix(hex, 16)?
@@ -2,7 +2,7 @@
 //!
 //! Provides an iterator over attributes key/value pairs
 
-use crate::encoding::Decoder;
+use crate::encoding::{Decoder, EncodingError};
 use crate::errors::Result as XmlResult;
 use crate::escape::{escape, resolve_predefined_entity, unescape_with};
 use crate::name::QName;
@@ -96,6 +96,16 @@ impl<'a> Attribute<'a> {
             Cow::Owned(s) => Ok(s.into()),
         }
     }
+
+    /// Decodes the key of the attribute. This is a shortcut for
+    /// [`self.key.decode(decoder)`](QName::decode).
+    ///
+    /// Does not allocate if the key is in UTF-8 encoding or contains only ASCII
+    /// characters.
+    #[inline]
+    pub fn decode_key(&self, decoder: Decoder) -> Result<Cow<'a, str>, EncodingError> {
+        self.key.decode(decoder)
+    }
 }
 
 impl<'a> Debug for Attribute<'a> {
@@ -249,6 +259,37 @@ impl<'a> Iterator for Attributes<'a> {
     }
 }
 
+/// Iterates attributes from the last to the first by scanning the raw buffer
+/// backward. Forward and backward iteration can be mixed and meet in the middle.
+///
+/// For well-formed attribute lists (separated by whitespace, as required by the
+/// XML specification) the reversed iterator yields exactly the same items as the
+/// forward one in the opposite order. When duplicates are checked, the attribute
+/// that was met first _by this iterator_ is considered original, so in reverse
+/// the error is reported for the earlier attribute in the document.
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use quick_xml::events::BytesStart;
+///
+/// let tag = BytesStart::from_content(r#"tag a="1" b='2' c="3""#, 3);
+/// let keys: Vec<_> = tag
+///     .attributes()
+///     .rev()
+///     .map(|a| a.unwrap().key.into_inner())
+///     .collect();
+/// assert_eq!(keys, [&b"c"[..], b"b", b"a"]);
+/// ```
+impl<'a> DoubleEndedIterator for Attributes<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.state.next_back(self.bytes) {
+            None => None,
+            Some(Ok(a)) => Some(Ok(a.map(|range| &self.bytes[range]).into())),
+            Some(Err(e)) => Some(Err(e)),
+        }
+    }
+}
+
 impl<'a> FusedIterator for Attributes<'a> {}
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -527,6 +568,9 @@ pub(crate) struct IterState {
     /// names. We store a ranges instead of slices to able to report a previous
     /// attribute position
     keys: Vec<Range<usize>>,
+    /// Position after the last attribute not yet consumed by [`Self::next_back`].
+    /// Parsing never looks past this offset
+    end: usize,
 }
 
 impl IterState {
@@ -536,9 +580,28 @@ impl IterState {
             html,
             check_duplicates: true,
             keys: Vec::new(),
+            end: usize::MAX,
         }
     }
 
+    /// Creates a state that does not check attributes for duplicates, so
+    /// iteration does not allocate
+    pub const fn unchecked(offset: usize, html: bool) -> Self {
+        Self {
+            state: State::Next(offset),
+            html,
+            check_duplicates: false,
+            keys: Vec::new(),
+            end: usize::MAX,
+        }
+    }
+
+    /// Returns part of `bytes` that was not yet consumed from the back
+    #[inline]
+    fn bounded<'s>(&self, bytes: &'s [u8]) -> &'s [u8] {
+        &bytes[..self.end.min(bytes.len())]
+    }
+
     /// Recover from an error that could have been made on a previous step.
     /// Returns an offset from which parsing should continue.
     /// If there no input left, returns `None`.
@@ -658,7 +721,121 @@ impl IterState {
         Some(Ok(Attr::SingleQ(key, value)))
     }
 
-    pub fn next(&mut self, slice: &[u8]) -> Option<AttrResult> {
+    /// Returns the last not yet consumed attribute. When no attributes are left,
+    /// returns `None` and forward iteration is finished too.
+    pub fn next_back(&mut self, bytes: &[u8]) -> Option<AttrResult> {
+        match self.parse_back(bytes) {
+            Some((result, start)) => {
+                self.end = start;
+                Some(result)
+            }
+            None => {
+                self.end = 0;
+                None
+            }
+        }
+    }
+
+    /// Parses the last attribute in the not yet consumed part of `bytes`.
+    /// Returns the result and the offset at which that attribute starts, which
+    /// becomes the end of the not yet consumed part.
+    fn parse_back(&mut self, bytes: &[u8]) -> Option<(AttrResult, usize)> {
+        let slice = self.bounded(bytes);
+        let start = self.recover(slice)?;
+        // Index right after the last attribute
+        let end = match rfind(slice, start, slice.len(), |b| !is_whitespace(b)) {
+            // Input: `    key="value"   `
+            //                       ^
+            Some(e) => e + 1,
+            // Input: `    `
+            None => {
+                self.state = State::Done;
+                return None;
+            }
+        };
+
+        let (key, value) = match slice[end - 1] {
+            quote @ (b'"' | b'\'') => {
+                let open = match rfind(slice, start, end - 1, |b| b == quote) {
+                    // Input: `    key  =  "value"`
+                    //                     ^
+                    Some(open) => open,
+                    // Input: `    value"`
+                    //                  ^
+                    // Opening quote not found, so we cannot make sense of the
+                    // rest of input
+                    None => {
+                        self.state = State::Done;
+                        return Some((Err(AttrError::ExpectedQuote(start, quote)), start));
+                    }
+                };
+                let value = open + 1..end - 1;
+                let key = match rfind(slice, start, open, |b| !is_whitespace(b)) {
+                    // Input: `    key  =  "value"`
+                    //                  ^
+                    Some(eq) if slice[eq] == b'=' => key_before(slice, start, eq),
+                    // Input: `    key  "value"`
+                    //                ^
+                    // Input: `"value"`
+                    //         ^
+                    _ => return Some((Err(AttrError::ExpectedEq(open)), open)),
+                };
+                let attr_start = key.start;
+                let key = match self.check_for_duplicates(bytes, key) {
+                    Ok(key) => key,
+                    Err(e) => return Some((Err(e), attr_start)),
+                };
+                return Some(if quote == b'"' {
+                    (Ok(Attr::DoubleQ(key, value)), attr_start)
+                } else {
+                    (Ok(Attr::SingleQ(key, value)), attr_start)
+                });
+            }
+            _ => {
+                // Start of the last whitespace-delimited token
+                let token = key_start(slice, start, end);
+                match slice[token..end].iter().position(|&b| b == b'=') {
+                    // Input: `    key=value`
+                    //             |  ^
+                    //         token  eq
+                    Some(eq) if eq > 0 => (token..token + eq, token + eq + 1..end),
+                    // Input: `    key  =value`
+                    //                  ^
+                    //                  token
+                    Some(_) => (key_before(slice, start, token), token + 1..end),
+                    None => match rfind(slice, start, token, |b| !is_whitespace(b)) {
+                        // Input: `    key  =  value`
+                        //                  ^  |
+                        //                 eq  token
+                        Some(eq) if slice[eq] == b'=' => (key_before(slice, start, eq), token..end),
+                        // Input: `    key`
+                        //             ^
+                        //             token
+                        _ => {
+                            let result = self.key_only(bytes, token..end, end);
+                            return result.map(|r| (r, token));
+                        }
+                    },
+                }
+            }
+        };
+
+        // Here we have unquoted value
+        let attr_start = key.start;
+        if value.is_empty() {
+            return Some((Err(AttrError::ExpectedValue(end)), attr_start));
+        }
+        if !self.html {
+            return Some((Err(AttrError::UnquotedValue(value.start)), attr_start));
+        }
+        Some(match self.check_for_duplicates(bytes, key) {
+            Ok(key) => (Ok(Attr::Unquoted(key, value)), attr_start),
+            Err(e) => (Err(e), attr_start),
+        })
+    }
+
+    pub fn next(&mut self, bytes: &[u8]) -> Option<AttrResult> {
+        let slice = self.bounded(bytes);
         let mut iter = match self.recover(slice) {
             Some(offset) => (offset..).zip(slice[offset..].iter()),
             None => return None,
@@ -698,7 +875,7 @@ impl IterState {
                 Some((offset, _)) => {
                     // In any case, recovering is not required
                     self.state = State::Next(offset);
-                    return self.key_only(slice, start_key..e, offset);
+                    return self.key_only(bytes, start_key..e, offset);
                 }
                 // Input: `    key  `
                 //             |  | ^
@@ -707,7 +884,7 @@ impl IterState {
                 None => {
                     // Because we reach end-of-input, stop iteration on next call
                     self.state = State::Done;
-                    return self.key_only(slice, start_key..e, slice.len());
+                    return self.key_only(bytes, start_key..e, slice.len());
                 }
             },
 
@@ -719,11 +896,11 @@ impl IterState {
                 // Because we reach end-of-input, stop iteration on next call
                 self.state = State::Done;
                 let e = slice.len();
-                return self.key_only(slice, start_key..e, e);
+                return self.key_only(bytes, start_key..e, e);
             }
         };
 
-        let key = match self.check_for_duplicates(slice, key) {
+        let key = match self.check_for_duplicates(bytes, key) {
             Err(e) => {
                 self.state = State::SkipEqValue(offset);
                 return Some(Err(e));
@@ -799,6 +976,33 @@ impl IterState {
     }
 }
 
+/// Returns the index of the last byte in `slice[start..end]` that satisfies
+/// the predicate
+#[inline]
+fn rfind(slice: &[u8], start: usize, end: usize, f: impl Fn(u8) -> bool) -> Option<usize> {
+    slice[start..end]
+        .iter()
+        .rposition(|&b| f(b))
+        .map(|p| start + p)
+}
+
+/// Returns the start of the whitespace-delimited token that ends at `end`
+#[inline]
+fn key_start(slice: &[u8], start: usize, end: usize) -> usize {
+    rfind(slice, start, end, is_whitespace).map_or(start, |p| p + 1)
+}
+
+/// Returns the span of the key that precedes the equal (`=`) sign at `eq`.
+/// If no key is present, returns an empty span at the `eq` position, like
+/// the forward parsing does
+#[inline]
+fn key_before(slice: &[u8], start: usize, eq: usize) -> Range<usize> {
+    match rfind(slice, start, eq, |b| !is_whitespace(b)) {
+        Some(p) => key_start(slice, start, p + 1)..p + 1,
+        None => eq..eq,
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Checks, how parsing of XML-style attributes works. Each attribute should
@@ -2257,3 +2461,90 @@ mod html {
         assert_eq!(iter.next(), None);
     }
 }
+
+/// Checks that iteration from the back yields the same attributes as forward
+/// iteration, but in the opposite order
+#[cfg(test)]
+mod reverse {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn check(mut iter: Attributes) {
+        let mut forward: Vec<_> = iter.clone().collect();
+        forward.reverse();
+        let backward: Vec<_> = iter.by_ref().rev().collect();
+        assert_eq!(backward, forward);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn xml() {
+        check(Attributes::new(r#"tag"#, 3));
+        check(Attributes::new(r#"tag  "#, 3));
+        check(Attributes::new(r#"tag key='value'"#, 3));
+        check(Attributes::new(
+            r#"tag a="1" b = 'x"y' c=  "a'b"  d='' e="with space" "#,
+            3,
+        ));
+    }
+
+    #[test]
+    fn html() {
+        check(Attributes::html(r#"tag key"#, 3));
+        check(Attributes::html(
+            r#"tag a=1 b = 2 c d="3" e =4 f= 5 g 'h'='6'"#,
+            3,
+        ));
+    }
+
+    #[test]
+    fn duplicated() {
+        let mut iter = Attributes::new(r#"tag a='1' b='2' a='3'"#, 3);
+        assert_eq!(
+            iter.next_back(),
+            Some(Ok(Attribute {
+                key: QName(b"a"),
+                value: Cow::Borrowed(b"3"),
+            }))
+        );
+        assert_eq!(
+            iter.next_back(),
+            Some(Ok(Attribute {
+                key: QName(b"b"),
+                value: Cow::Borrowed(b"2"),
+            }))
+        );
+        assert_eq!(iter.next_back(), Some(Err(AttrError::Duplicated(4, 16))));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    /// Forward and backward iteration meet in the middle
+    #[test]
+    fn mixed() {
+        let mut iter = Attributes::new(r#"tag a='1' b="2" c='3'"#, 3);
+        assert_eq!(
+            iter.next(),
+            Some(Ok(Attribute {
+                key: QName(b"a"),
+                value: Cow::Borrowed(b"1"),
+            }))
+        );
+        assert_eq!(
+            iter.next_back(),
+            Some(Ok(Attribute {
+                key: QName(b"c"),
+                value: Cow::Borrowed(b"3"),
+            }))
+        );
+        assert_eq!(
+            iter.next_back(),
+            Some(Ok(Attribute {
+                key: QName(b"b"),
+                value: Cow::Borrowed(b"2"),
+            }))
+        );
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
@@ -45,17 +45,22 @@ use std::iter::FusedIterator;
 use std::mem::replace;
 use std::ops::Deref;
 use std::str::from_utf8;
+use std::sync::Arc;
 
 use crate::encoding::{Decoder, EncodingError};
 use crate::errors::{Error, IllFormedError};
 use crate::escape::{
-    escape, minimal_escape, partial_escape, resolve_predefined_entity, unescape_with,
+    escape, is_xml10_char, minimal_escape, partial_escape, partial_unescape_with,
+    resolve_predefined_entity, unescape_with, EscapeError,
 };
-use crate::name::{LocalName, QName};
+use crate::name::{LocalName, Namespace, QName};
 #[cfg(feature = "serialize")]
 use crate::utils::CowRef;
-use crate::utils::{name_len, trim_xml_end, trim_xml_start, write_cow_string, Bytes};
-use attributes::{AttrError, Attribute, Attributes};
+use crate::utils::{
+    is_whitespace, name_len, trim_xml_end, trim_xml_start, write_byte_string, write_cow_string,
+    Bytes,
+};
+use attributes::{Attr, AttrError, Attribute, Attributes, IterState};
 
 /// Opening tag data (`Event::Start`), with optional attributes: `<name attr="value">`.
 ///
@@ -89,12 +94,17 @@ use attributes::{AttrError, Attribute, Attributes};
 /// [`name`]: Self::name
 /// [`local_name`]: Self::local_name
 /// [`attributes`]: Self::attributes
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct BytesStart<'a> {
     /// content of the element, before any utf8 conversion
     pub(crate) buf: Cow<'a, [u8]>,
     /// end of the element name, the name starts at that the start of `buf`
     pub(crate) name_len: usize,
+    /// Namespace URI of the element name, attached by the [`NsReader`]. See
+    /// [`Self::namespace()`]
+    ///
+    /// [`NsReader`]: crate::reader::NsReader
+    pub(crate) namespace: Option<Arc<[u8]>>,
 }
 
 impl<'a> BytesStart<'a> {
@@ -104,6 +114,7 @@ impl<'a> BytesStart<'a> {
         BytesStart {
             buf: Cow::Borrowed(content),
             name_len,
+            namespace: None,
         }
     }
 
@@ -118,6 +129,7 @@ impl<'a> BytesStart<'a> {
         BytesStart {
             name_len: buf.len(),
             buf,
+            namespace: None,
         }
     }
 
@@ -133,6 +145,7 @@ impl<'a> BytesStart<'a> {
         BytesStart {
             buf: str_cow_to_bytes(content),
             name_len,
+            namespace: None,
         }
     }
 
@@ -141,6 +154,7 @@ impl<'a> BytesStart<'a> {
         BytesStart {
             buf: Cow::Owned(self.buf.into_owned()),
             name_len: self.name_len,
+            namespace: self.namespace,
         }
     }
 
@@ -149,6 +163,7 @@ impl<'a> BytesStart<'a> {
         BytesStart {
             buf: Cow::Owned(self.buf.clone().into_owned()),
             name_len: self.name_len,
+            namespace: self.namespace.clone(),
         }
     }
 
@@ -181,16 +196,27 @@ impl<'a> BytesStart<'a> {
         BytesStart {
             buf: Cow::Borrowed(&self.buf),
             name_len: self.name_len,
+            namespace: self.namespace.clone(),
         }
     }
 
-    /// Creates new paired close tag
+    /// Creates new paired close tag. The [namespace] of this element, if any,
+    /// is copied to the close tag.
+    ///
+    /// [namespace]: Self::namespace
     #[inline]
     pub fn to_end(&self) -> BytesEnd {
-        BytesEnd::from(self.name())
+        let mut end = BytesEnd::from(self.name());
+        end.namespace = self.namespace.clone();
+        end
     }
 
     /// Gets the undecoded raw tag name, as present in the input stream.
+    ///
+    /// The name is in the document encoding. Because only ASCII-compatible
+    /// encodings are supported, ASCII names can be compared with raw bytes
+    /// directly, for example, `e.name() == QName(b"tag")`. Use [`QName::decode()`]
+    /// to get the name as a string.
     #[inline]
     pub fn name(&self) -> QName {
         QName(&self.buf[..self.name_len])
@@ -205,6 +231,61 @@ impl<'a> BytesStart<'a> {
         self.name().into()
     }
 
+    /// Returns the namespace URI to which the name of this element is bound.
+    ///
+    /// The namespace is attached to events by the [`NsReader`] only if the
+    /// [`attach_namespaces`] option is enabled. `None` is returned if the
+    /// option is disabled, if the event was not produced by the `NsReader`,
+    /// or if the name is not bound to any namespace (including names with
+    /// undeclared prefixes). Use [`NsReader::resolve_element()`] if you need
+    /// to distinguish those cases.
+    ///
+    /// # Ownership
+    ///
+    /// The URI is copied from the reader into a reference-counted storage
+    /// owned by the event, so it does not borrow the reader and lives as long
+    /// as the event. [`borrow()`], [`into_owned()`] and [`to_end()`] share
+    /// the same storage without copying. Changing the name of the element
+    /// with [`set_name()`] or [`reset()`] removes the namespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::Event;
+    /// use quick_xml::name::Namespace;
+    /// use quick_xml::reader::NsReader;
+    ///
+    /// let mut reader = NsReader::from_str(r#"<x:tag xmlns:x="urn:x"><inner/></x:tag>"#);
+    /// reader.config_mut().attach_namespaces = true;
+    ///
+    /// match reader.read_event().unwrap() {
+    ///     Event::Start(e) => assert_eq!(e.namespace(), Some(Namespace(b"urn:x"))),
+    ///     e => panic!("unexpected event {:?}", e),
+    /// }
+    /// match reader.read_event().unwrap() {
+    ///     Event::Empty(e) => assert_eq!(e.namespace(), None),
+    ///     e => panic!("unexpected event {:?}", e),
+    /// }
+    /// match reader.read_event().unwrap() {
+    ///     Event::End(e) => assert_eq!(e.namespace(), Some(Namespace(b"urn:x"))),
+    ///     e => panic!("unexpected event {:?}", e),
+    /// }
+    /// ```
+    ///
+    /// [`NsReader`]: crate::reader::NsReader
+    /// [`attach_namespaces`]: crate::reader::Config::attach_namespaces
+    /// [`NsReader::resolve_element()`]: crate::reader::NsReader::resolve_element
+    /// [`borrow()`]: Self::borrow
+    /// [`into_owned()`]: Self::into_owned
+    /// [`to_end()`]: Self::to_end
+    /// [`set_name()`]: Self::set_name
+    /// [`reset()`]: Self::reset
+    #[inline]
+    pub fn namespace(&self) -> Option<Namespace<'_>> {
+        self.namespace.as_deref().map(Namespace)
+    }
+
     /// Edit the name of the BytesStart in-place
     ///
     /// # Warning
@@ -214,6 +295,7 @@ impl<'a> BytesStart<'a> {
         let bytes = self.buf.to_mut();
         bytes.splice(..self.name_len, name.iter().cloned());
         self.name_len = name.len();
+        self.namespace = None;
         self
     }
 
@@ -262,6 +344,46 @@ impl<'a> BytesStart<'a> {
         self
     }
 
+    /// Add additional attributes to this tag from an iterator over fallible
+    /// attributes, stopping at the first error.
+    ///
+    /// This is useful to copy attributes of one element to another, because
+    /// [`attributes()`] yields `Result`s. Attributes yielded before the error
+    /// are added to the tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    ///
+    /// let source = BytesStart::from_content(r#"source a="1" b='2'"#, 6);
+    /// let mut copy = BytesStart::new("copy");
+    /// copy.try_extend_attributes(source.attributes()).unwrap();
+    /// // Values are always written in double quotes
+    /// assert_eq!(copy, BytesStart::from_content(r#"copy a="1" b="2""#, 4));
+    ///
+    /// let broken = BytesStart::from_content(r#"broken a="1" b"#, 6);
+    /// let mut copy = BytesStart::new("copy");
+    /// assert!(copy.try_extend_attributes(broken.attributes()).is_err());
+    /// // Correct attributes before the broken one are still copied
+    /// assert_eq!(copy, BytesStart::from_content(r#"copy a="1""#, 4));
+    /// ```
+    ///
+    /// [`attributes()`]: Self::attributes
+    pub fn try_extend_attributes<'b, I, E>(
+        &mut self,
+        attributes: I,
+    ) -> Result<&mut BytesStart<'a>, E>
+    where
+        I: IntoIterator<Item = Result<Attribute<'b>, E>>,
+    {
+        for attr in attributes {
+            self.push_attribute(attr?);
+        }
+        Ok(self)
+    }
+
     /// Adds an attribute to this element.
     pub fn push_attribute<'b, A>(&mut self, attr: A)
     where
@@ -271,12 +393,226 @@ impl<'a> BytesStart<'a> {
         self.push_attr(attr.into());
     }
 
+    /// Adds an attribute without a value to this element, like `disabled` in
+    /// `<input disabled>`. Such attributes are [not allowed] in XML, but are
+    /// used in HTML documents, where they are called _boolean attributes_.
+    ///
+    /// Elements with such attributes can be read back only using [`html_attributes()`],
+    /// the [`attributes()`] iterator will return an error for them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    ///
+    /// let mut start = BytesStart::new("input");
+    /// start.push_attribute(("type", "checkbox"));
+    /// start.push_attribute_flag("disabled");
+    ///
+    /// assert_eq!(&*start, br#"input type="checkbox" disabled"#);
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// `name` must be a valid name.
+    ///
+    /// [not allowed]: https://www.w3.org/TR/xml11/#NT-Attribute
+    /// [`html_attributes()`]: Self::html_attributes
+    /// [`attributes()`]: Self::attributes
+    pub fn push_attribute_flag(&mut self, name: &str) {
+        let bytes = self.buf.to_mut();
+        bytes.push(b' ');
+        bytes.extend_from_slice(name.as_bytes());
+    }
+
+    /// Replaces the value of the existing attribute with the key of `attr` by
+    /// the value of `attr`. Returns `false` and does not change the element if
+    /// there is no attribute with such key.
+    ///
+    /// Only the value is replaced in place, so the position of the attribute
+    /// and the quote character around its value are preserved. As in
+    /// [`push_attribute()`], the value is escaped if `attr` is created from
+    /// strings. If the new value contains the quote character used around
+    /// the old value, that character is replaced with a character reference.
+    ///
+    /// Use [`upsert_attribute()`] to add an attribute if it is missing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    ///
+    /// let mut start = BytesStart::from_content(r#"tag a='1' b="2""#, 3);
+    ///
+    /// assert_eq!(start.set_attribute_value(("a", "it's <long>")), true);
+    /// assert_eq!(start.set_attribute_value(("c", "3")), false);
+    ///
+    /// assert_eq!(&*start, br#"tag a='it&apos;s &lt;long&gt;' b="2""#);
+    /// ```
+    ///
+    /// [`push_attribute()`]: Self::push_attribute
+    /// [`upsert_attribute()`]: Self::upsert_attribute
+    pub fn set_attribute_value<'b, A>(&mut self, attr: A) -> bool
+    where
+        A: Into<Attribute<'b>>,
+    {
+        let attr = attr.into();
+        let mut state = IterState::new(self.name_len, false);
+        let (range, quote) = loop {
+            let (key, value, quote) = match state.next(&self.buf) {
+                None => return false,
+                Some(Ok(Attr::DoubleQ(key, value))) => (key, value, b'"'),
+                Some(Ok(Attr::SingleQ(key, value))) => (key, value, b'\''),
+                // Malformed attributes cannot be changed
+                Some(_) => continue,
+            };
+            if self.buf[key] == *attr.key.as_ref() {
+                break (value, quote);
+            }
+        };
+        let value = attr.value.as_ref();
+        let bytes = self.buf.to_mut();
+        if value.contains(&quote) {
+            let reference: &[u8] = if quote == b'"' { b"&quot;" } else { b"&apos;" };
+            let mut escaped = Vec::with_capacity(value.len() + reference.len());
+            for &b in value {
+                if b == quote {
+                    escaped.extend_from_slice(reference);
+                } else {
+                    escaped.push(b);
+                }
+            }
+            bytes.splice(range, escaped);
+        } else {
+            bytes.splice(range, value.iter().cloned());
+        }
+        true
+    }
+
+    /// Replaces the value of the existing attribute with the key of `attr` as
+    /// [`set_attribute_value()`] does, or adds `attr` to the end of the element
+    /// as [`push_attribute()`] does if there is no such attribute.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    ///
+    /// let mut start = BytesStart::from_content(r#"tag a="1""#, 3);
+    ///
+    /// start.upsert_attribute(("a", "one"));
+    /// start.upsert_attribute(("b", "two"));
+    ///
+    /// assert_eq!(&*start, br#"tag a="one" b="two""#);
+    /// ```
+    ///
+    /// [`set_attribute_value()`]: Self::set_attribute_value
+    /// [`push_attribute()`]: Self::push_attribute
+    pub fn upsert_attribute<'b, A>(&mut self, attr: A)
+    where
+        A: Into<Attribute<'b>>,
+    {
+        let attr = attr.into();
+        if !self.set_attribute_value(attr.clone()) {
+            self.push_attribute(attr);
+        }
+    }
+
+    /// Copies all attributes of the `other` element to this element. Values of
+    /// attributes which keys are already present in this element are replaced
+    /// as [`upsert_attribute()`] does, so this method can be used to merge
+    /// a set of default attributes with overrides. Other attributes are added
+    /// to the end of the element.
+    ///
+    /// Values are copied as is, without unescaping and escaping them again.
+    ///
+    /// If an attribute of `other` cannot be parsed, returns the error and stops
+    /// copying. Attributes before the broken one are still copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    ///
+    /// let mut link = BytesStart::from_content(r#"a href="/" class="nav""#, 1);
+    /// let overrides = BytesStart::from_content(r#"a class='active' title="Home &amp; more""#, 1);
+    ///
+    /// link.append_attributes_from(&overrides).unwrap();
+    ///
+    /// assert_eq!(&*link, br#"a href="/" class="active" title="Home &amp; more""#);
+    /// ```
+    ///
+    /// [`upsert_attribute()`]: Self::upsert_attribute
+    pub fn append_attributes_from(
+        &mut self,
+        other: &BytesStart,
+    ) -> Result<&mut BytesStart<'a>, AttrError> {
+        for attr in other.attributes() {
+            let attr = attr?;
+            if !self.set_attribute_value(attr.clone()) {
+                // Raw value can contain a double quote if it was in single quotes
+                let quote = if attr.value.contains(&b'"') {
+                    b'\''
+                } else {
+                    b'"'
+                };
+                self.push_quoted_attribute(attr, quote);
+            }
+        }
+        Ok(self)
+    }
+
     /// Remove all attributes from the ByteStart
+    ///
+    /// The capacity of the underlying buffer is retained. Use [`reset()`] to also
+    /// change the name, or [`into_owned()`] followed by creating a new event if
+    /// you want to release the memory.
+    ///
+    /// [`reset()`]: Self::reset
+    /// [`into_owned()`]: Self::into_owned
     pub fn clear_attributes(&mut self) -> &mut BytesStart<'a> {
         self.buf.to_mut().truncate(self.name_len);
         self
     }
 
+    /// Turns this event into a fresh element with the specified name and without
+    /// attributes.
+    ///
+    /// If the event owns its buffer, that buffer is reused, so no reallocation
+    /// occurs when its capacity is large enough for the new name. That allows
+    /// to reuse the same event for writing many elements:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    ///
+    /// let mut start = BytesStart::new(String::from("first"));
+    /// start.push_attribute(("attribute", "value"));
+    ///
+    /// start.reset(b"second");
+    /// assert_eq!(&*start, b"second");
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// `name` must be a valid name.
+    pub fn reset(&mut self, name: &[u8]) -> &mut BytesStart<'a> {
+        match self.buf {
+            Cow::Owned(ref mut bytes) => {
+                bytes.clear();
+                bytes.extend_from_slice(name);
+            }
+            Cow::Borrowed(_) => self.buf = Cow::Owned(name.to_vec()),
+        }
+        self.name_len = name.len();
+        self.namespace = None;
+        self
+    }
+
     /// Returns an iterator over the attributes of this tag.
     pub fn attributes(&self) -> Attributes {
         Attributes::wrap(&self.buf, self.name_len, false)
@@ -294,6 +630,72 @@ impl<'a> BytesStart<'a> {
         &self.buf[self.name_len..]
     }
 
+    /// Returns the number of attributes of this tag.
+    ///
+    /// This is cheaper than `attributes().count()` because no [`Attribute`]s
+    /// are created. Attributes are checked in the same way as [`attributes()`]
+    /// does, so the first malformed or duplicated attribute is returned as
+    /// an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use quick_xml::events::BytesStart;
+    /// use quick_xml::events::attributes::AttrError;
+    ///
+    /// let tag = BytesStart::from_content(r#"tag a="1" b='2' c="3""#, 3);
+    /// assert_eq!(tag.attribute_count(), Ok(3));
+    ///
+    /// let tag = BytesStart::from_content(r#"tag a="1" a="2""#, 3);
+    /// assert_eq!(tag.attribute_count(), Err(AttrError::Duplicated(10, 4)));
+    /// ```
+    ///
+    /// [`attributes()`]: Self::attributes
+    pub fn attribute_count(&self) -> Result<usize, AttrError> {
+        let mut state = IterState::new(self.name_len, false);
+        let mut count = 0;
+        while let Some(attr) = state.next(&self.buf) {
+            attr?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Parses all attributes of this tag in one pass and returns their keys and
+    /// values. Values are returned as is, without unescaping.
+    ///
+    /// This is more efficient than calling [`try_get_attribute()`] for each
+    /// attribute of interest, because each such call scans attributes from
+    /// the beginning. Attributes are checked in the same way as [`attributes()`]
+    /// does, so the first malformed or duplicated attribute is returned as
+    /// an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    /// use std::borrow::Cow;
+    ///
+    /// let tag = BytesStart::from_content(r#"tag a="1" b='&lt;'"#, 3);
+    /// assert_eq!(
+    ///     tag.parse_attributes().unwrap(),
+    ///     vec![
+    ///         (Cow::Borrowed(&b"a"[..]), Cow::Borrowed(&b"1"[..])),
+    ///         (Cow::Borrowed(&b"b"[..]), Cow::Borrowed(&b"&lt;"[..])),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// [`try_get_attribute()`]: Self::try_get_attribute
+    /// [`attributes()`]: Self::attributes
+    #[allow(clippy::type_complexity)]
+    pub fn parse_attributes(&self) -> Result<Vec<(Cow<'_, [u8]>, Cow<'_, [u8]>)>, AttrError> {
+        self.attributes()
+            .map(|a| a.map(|a| (Cow::Borrowed(a.key.into_inner()), a.value)))
+            .collect()
+    }
+
     /// Try to get an attribute
     pub fn try_get_attribute<N: AsRef<[u8]> + Sized>(
         &'a self,
@@ -308,19 +710,65 @@ impl<'a> BytesStart<'a> {
         Ok(None)
     }
 
+    /// Returns the raw value of the first attribute with the specified name,
+    /// or `None` if there is no such attribute. The value is borrowed from
+    /// this tag and returned as is, without unescaping.
+    ///
+    /// This is a cheaper alternative to [`try_get_attribute()`] when the value
+    /// does not need to be unescaped. Like [`try_get_attribute()`], this method
+    /// does not check attributes for duplicates, but returns an error if some
+    /// attribute before the searched one is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesStart;
+    ///
+    /// let tag = BytesStart::from_content(r#"tag a="1" b='&lt;'"#, 3);
+    /// assert_eq!(tag.attribute_value("b").unwrap(), Some(&b"&lt;"[..]));
+    /// assert_eq!(tag.attribute_value("c").unwrap(), None);
+    /// ```
+    ///
+    /// [`try_get_attribute()`]: Self::try_get_attribute
+    pub fn attribute_value<N: AsRef<[u8]>>(&self, name: N) -> Result<Option<&[u8]>, AttrError> {
+        let mut state = IterState::unchecked(self.name_len, false);
+        while let Some(attr) = state.next(&self.buf) {
+            let (key, value) = attr?.into();
+            if self.buf[key] == *name.as_ref() {
+                return Ok(Some(&self.buf[value.unwrap_or_default()]));
+            }
+        }
+        Ok(None)
+    }
+
     /// Adds an attribute to this element.
     pub(crate) fn push_attr<'b>(&mut self, attr: Attribute<'b>) {
+        self.push_quoted_attr(attr, b'"');
+    }
+
+    /// Adds an attribute to this element, enclosing its value in the specified
+    /// quote character. The value should not contain that character.
+    pub(crate) fn push_quoted_attribute<'b>(&mut self, attr: Attribute<'b>, quote: u8) {
+        self.buf.to_mut().push(b' ');
+        self.push_quoted_attr(attr, quote);
+    }
+
+    /// Adds an attribute to this element without a leading space, enclosing
+    /// its value in the specified quote character.
+    pub(crate) fn push_quoted_attr<'b>(&mut self, attr: Attribute<'b>, quote: u8) {
         let bytes = self.buf.to_mut();
         bytes.extend_from_slice(attr.key.as_ref());
-        bytes.extend_from_slice(b"=\"");
+        bytes.push(b'=');
+        bytes.push(quote);
         // FIXME: need to escape attribute content
         bytes.extend_from_slice(attr.value.as_ref());
-        bytes.push(b'"');
+        bytes.push(quote);
     }
 
     /// Adds new line in existing element
-    pub(crate) fn push_newline(&mut self) {
-        self.buf.to_mut().push(b'\n');
+    pub(crate) fn push_newline(&mut self, newline: &str) {
+        self.buf.to_mut().extend_from_slice(newline.as_bytes());
     }
 
     /// Adds indentation bytes in existing element
@@ -333,10 +781,27 @@ impl<'a> Debug for BytesStart<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "BytesStart {{ buf: ")?;
         write_cow_string(f, &self.buf)?;
-        write!(f, ", name_len: {} }}", self.name_len)
+        write!(f, ", name_len: {}", self.name_len)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, ", namespace: ")?;
+            write_byte_string(f, namespace)?;
+        }
+        write!(f, " }}")
     }
 }
 
+/// Compares the raw content of the tags. The namespace attached by the
+/// [`NsReader`] does not participate in the comparison.
+///
+/// [`NsReader`]: crate::reader::NsReader
+impl<'a> PartialEq for BytesStart<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf == other.buf && self.name_len == other.name_len
+    }
+}
+
+impl<'a> Eq for BytesStart<'a> {}
+
 impl<'a> Deref for BytesStart<'a> {
     type Target = [u8];
 
@@ -403,16 +868,24 @@ impl<'a> arbitrary::Arbitrary<'a> for BytesStart<'a> {
 ///
 /// [`name`]: Self::name
 /// [`local_name`]: Self::local_name
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct BytesEnd<'a> {
     name: Cow<'a, [u8]>,
+    /// Namespace URI of the element name, attached by the [`NsReader`]. See
+    /// [`Self::namespace()`]
+    ///
+    /// [`NsReader`]: crate::reader::NsReader
+    pub(crate) namespace: Option<Arc<[u8]>>,
 }
 
 impl<'a> BytesEnd<'a> {
     /// Internal constructor, used by `Reader`. Supplies data in reader's encoding
     #[inline]
     pub(crate) const fn wrap(name: Cow<'a, [u8]>) -> Self {
-        BytesEnd { name }
+        BytesEnd {
+            name,
+            namespace: None,
+        }
     }
 
     /// Creates a new `BytesEnd` borrowing a slice.
@@ -429,6 +902,7 @@ impl<'a> BytesEnd<'a> {
     pub fn into_owned(self) -> BytesEnd<'static> {
         BytesEnd {
             name: Cow::Owned(self.name.into_owned()),
+            namespace: self.namespace,
         }
     }
 
@@ -437,6 +911,7 @@ impl<'a> BytesEnd<'a> {
     pub fn borrow(&self) -> BytesEnd {
         BytesEnd {
             name: Cow::Borrowed(&self.name),
+            namespace: self.namespace.clone(),
         }
     }
 
@@ -454,16 +929,39 @@ impl<'a> BytesEnd<'a> {
     pub fn local_name(&self) -> LocalName {
         self.name().into()
     }
+
+    /// Returns the namespace URI to which the name of this element is bound.
+    /// See [`BytesStart::namespace()`] for details.
+    #[inline]
+    pub fn namespace(&self) -> Option<Namespace<'_>> {
+        self.namespace.as_deref().map(Namespace)
+    }
 }
 
 impl<'a> Debug for BytesEnd<'a> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "BytesEnd {{ name: ")?;
         write_cow_string(f, &self.name)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, ", namespace: ")?;
+            write_byte_string(f, namespace)?;
+        }
         write!(f, " }}")
     }
 }
 
+/// Compares the names of the tags. The namespace attached by the
+/// [`NsReader`] does not participate in the comparison.
+///
+/// [`NsReader`]: crate::reader::NsReader
+impl<'a> PartialEq for BytesEnd<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<'a> Eq for BytesEnd<'a> {}
+
 impl<'a> Deref for BytesEnd<'a> {
     type Target = [u8];
 
@@ -543,18 +1041,62 @@ impl<'a> BytesText<'a> {
     }
 
     /// Creates a new `BytesText` from an escaped string.
+    ///
+    /// The content is stored as is, so it must already be escaped, for example,
+    /// be obtained from a text event of a reader. Use [`new()`] to create an
+    /// event from a plain string, otherwise markup characters will be written
+    /// unescaped. Passing escaped string to [`new()`] escapes it twice:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesText;
+    ///
+    /// assert_eq!(&*BytesText::from_escaped("Tom &amp; Jerry"), b"Tom &amp; Jerry");
+    /// assert_eq!(&*BytesText::new("Tom & Jerry"), b"Tom &amp; Jerry");
+    /// // Double escaping
+    /// assert_eq!(&*BytesText::new("Tom &amp; Jerry"), b"Tom &amp;amp; Jerry");
+    /// ```
+    ///
+    /// [`new()`]: Self::new
     #[inline]
     pub fn from_escaped<C: Into<Cow<'a, str>>>(content: C) -> Self {
         Self::wrap(str_cow_to_bytes(content), Decoder::utf8())
     }
 
     /// Creates a new `BytesText` from a string. The string is expected not to
-    /// be escaped.
+    /// be escaped, use [`from_escaped()`] for already escaped strings.
+    ///
+    /// [`from_escaped()`]: Self::from_escaped
     #[inline]
     pub fn new(content: &'a str) -> Self {
         Self::from_escaped(escape(content))
     }
 
+    /// Creates a new `BytesText` from a string like [`new()`] does, but checks
+    /// that the string contains only characters permitted in an XML 1.0 document.
+    ///
+    /// Returns [`EscapeError::IllegalCharacter`] with the byte position of the
+    /// first illegal character if the check failed. Such characters, for example
+    /// `\u{0}`, cannot be written even in escaped form.
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::escape::EscapeError;
+    /// use quick_xml::events::BytesText;
+    ///
+    /// assert_eq!(BytesText::try_new("a < b").unwrap(), BytesText::from_escaped("a &lt; b"));
+    /// assert_eq!(BytesText::try_new("a\u{0}b"), Err(EscapeError::IllegalCharacter(1, '\u{0}')));
+    /// ```
+    ///
+    /// [`new()`]: Self::new
+    #[doc(alias = "try_from_plain")]
+    pub fn try_new(content: &'a str) -> Result<Self, EscapeError> {
+        match content.char_indices().find(|(_, ch)| !is_xml10_char(*ch)) {
+            Some((pos, ch)) => Err(EscapeError::IllegalCharacter(pos, ch)),
+            None => Ok(Self::new(content)),
+        }
+    }
+
     /// Ensures that all data is owned to extend the object's lifetime if
     /// necessary.
     #[inline]
@@ -580,6 +1122,32 @@ impl<'a> BytesText<'a> {
         }
     }
 
+    /// Returns the raw (escaped and encoded) content of the event.
+    #[inline]
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.content
+    }
+
+    /// Returns `true` if the event has no content.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// Returns `true` if the event contains only XML whitespace characters
+    /// (spaces, tabs, carriage returns and line feeds) or is empty.
+    ///
+    /// ```
+    /// # use quick_xml::events::BytesText;
+    /// assert!(BytesText::new(" \t\r\n").is_whitespace());
+    /// assert!(BytesText::new("").is_whitespace());
+    /// assert!(!BytesText::new(" text ").is_whitespace());
+    /// ```
+    #[inline]
+    pub fn is_whitespace(&self) -> bool {
+        self.content.iter().all(|&b| is_whitespace(b))
+    }
+
     /// Decodes then unescapes the content of the event.
     ///
     /// This will allocate if the value contains any escape sequences or in
@@ -588,6 +1156,33 @@ impl<'a> BytesText<'a> {
         self.unescape_with(resolve_predefined_entity)
     }
 
+    /// Decodes then unescapes the content of the event, but keeps references to
+    /// unknown entities as is. See [`escape::partial_unescape()`] for details.
+    ///
+    /// This will allocate if the value contains any escape sequences or in
+    /// non-UTF-8 encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesText;
+    ///
+    /// let text = BytesText::from_escaped("Tom &amp; &name;");
+    /// assert_eq!(text.partial_unescape().unwrap(), "Tom & &name;");
+    /// ```
+    ///
+    /// [`escape::partial_unescape()`]: crate::escape::partial_unescape
+    pub fn partial_unescape(&self) -> Result<Cow<'a, str>, Error> {
+        let decoded = self.decoder.decode_cow(&self.content)?;
+
+        match partial_unescape_with(&decoded, resolve_predefined_entity)? {
+            // Because result is borrowed, no replacements was done and we can use original string
+            Cow::Borrowed(_) => Ok(decoded),
+            Cow::Owned(s) => Ok(s.into()),
+        }
+    }
+
     /// Decodes then unescapes the content of the event with custom entities.
     ///
     /// This will allocate if the value contains any escape sequences or in
@@ -773,6 +1368,25 @@ impl<'a> BytesCData<'a> {
         }
     }
 
+    /// Returns the raw (encoded) content of the CDATA section.
+    #[inline]
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.content
+    }
+
+    /// Returns `true` if the CDATA section has no content.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// Returns `true` if the CDATA section contains only XML whitespace characters
+    /// (spaces, tabs, carriage returns and line feeds) or is empty.
+    #[inline]
+    pub fn is_whitespace(&self) -> bool {
+        self.content.iter().all(|&b| is_whitespace(b))
+    }
+
     /// Converts this CDATA content to an escaped version, that can be written
     /// as an usual text in XML.
     ///
@@ -967,7 +1581,11 @@ impl<'a> BytesPI<'a> {
         let buf = str_cow_to_bytes(content);
         let name_len = name_len(&buf);
         Self {
-            content: BytesStart { buf, name_len },
+            content: BytesStart {
+                buf,
+                name_len,
+                namespace: None,
+            },
         }
     }
 
@@ -1438,6 +2056,137 @@ impl<'a> Event<'a> {
             Event::Eof => Event::Eof,
         }
     }
+
+    /// Returns `true` if this event is [`Start`], i.e. a start tag `<tag>`.
+    ///
+    /// [`Start`]: Self::Start
+    #[inline]
+    pub const fn is_start(&self) -> bool {
+        matches!(self, Event::Start(_))
+    }
+
+    /// Returns `true` if this event is [`End`], i.e. an end tag `</tag>`.
+    ///
+    /// [`End`]: Self::End
+    #[inline]
+    pub const fn is_end(&self) -> bool {
+        matches!(self, Event::End(_))
+    }
+
+    /// Returns `true` if this event is [`Empty`], i.e. a self-closed tag `<tag/>`.
+    ///
+    /// [`Empty`]: Self::Empty
+    #[inline]
+    pub const fn is_empty_element(&self) -> bool {
+        matches!(self, Event::Empty(_))
+    }
+
+    /// Returns `true` if this event is [`Text`], i.e. a text between tags.
+    ///
+    /// [`Text`]: Self::Text
+    #[inline]
+    pub const fn is_text(&self) -> bool {
+        matches!(self, Event::Text(_))
+    }
+
+    /// Returns `true` if this event is [`CData`], i.e. a CDATA section `<![CDATA[...]]>`.
+    ///
+    /// [`CData`]: Self::CData
+    #[inline]
+    pub const fn is_cdata(&self) -> bool {
+        matches!(self, Event::CData(_))
+    }
+
+    /// Returns `true` if this event is [`Comment`], i.e. a comment `<!-- ... -->`.
+    ///
+    /// [`Comment`]: Self::Comment
+    #[inline]
+    pub const fn is_comment(&self) -> bool {
+        matches!(self, Event::Comment(_))
+    }
+
+    /// Returns `true` if this event is [`Eof`], i.e. the end of the document.
+    ///
+    /// [`Eof`]: Self::Eof
+    #[inline]
+    pub const fn is_eof(&self) -> bool {
+        matches!(self, Event::Eof)
+    }
+
+    /// Returns the start tag of the [`Start`] and [`Empty`] events and a flag
+    /// indicating that the tag is self-closed, i.e. that no matching [`End`]
+    /// event will follow. Returns `None` for all other events.
+    ///
+    /// This allows to handle both events in a single match arm:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::Event;
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str("<a><b/></a>");
+    /// let mut tags = Vec::new();
+    /// loop {
+    ///     let event = reader.read_event().unwrap();
+    ///     if let Some((start, self_closed)) = event.as_start() {
+    ///         tags.push((start.name().as_ref().to_vec(), self_closed));
+    ///     } else if event.is_eof() {
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(tags, [(b"a".to_vec(), false), (b"b".to_vec(), true)]);
+    /// ```
+    ///
+    /// [`Start`]: Self::Start
+    /// [`Empty`]: Self::Empty
+    /// [`End`]: Self::End
+    #[inline]
+    pub const fn as_start(&self) -> Option<(&BytesStart<'a>, bool)> {
+        match self {
+            Event::Start(e) => Some((e, false)),
+            Event::Empty(e) => Some((e, true)),
+            _ => None,
+        }
+    }
+
+    /// Returns the character data of the [`Text`] and [`CData`] events.
+    /// Returns `None` for all other events.
+    ///
+    /// The content of the `Text` event is decoded and unescaped as in
+    /// [`BytesText::unescape()`], the content of the `CData` event is only
+    /// decoded, because it is not escaped. This allows to get the text
+    /// regardless of whether it is written as an escaped text or as a CDATA
+    /// section:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::Event;
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str("<a>x &amp; y</a><b><![CDATA[x & y]]></b>");
+    /// let mut texts = Vec::new();
+    /// loop {
+    ///     match reader.read_event().unwrap() {
+    ///         Event::Eof => break,
+    ///         e => {
+    ///             if let Some(text) = e.text() {
+    ///                 texts.push(text.unwrap().into_owned());
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!(texts, ["x & y", "x & y"]);
+    /// ```
+    ///
+    /// [`Text`]: Self::Text
+    /// [`CData`]: Self::CData
+    pub fn text(&self) -> Option<Result<Cow<'a, str>, Error>> {
+        match self {
+            Event::Text(e) => Some(e.unescape()),
+            Event::CData(e) => Some(e.decode().map_err(Error::from)),
+            _ => None,
+        }
+    }
 }
 
 impl<'a> Deref for Event<'a> {
@@ -1516,6 +2265,42 @@ mod test {
         assert_eq!(b.name(), QName(b"g"));
     }
 
+    #[test]
+    fn bytestext_is_whitespace() {
+        assert!(BytesText::from_escaped("").is_whitespace());
+        assert!(BytesText::from_escaped(" \t\r\n").is_whitespace());
+        assert!(!BytesText::from_escaped(" &#32; ").is_whitespace());
+        assert!(!BytesText::from_escaped(" text ").is_whitespace());
+
+        assert!(BytesCData::new("").is_whitespace());
+        assert!(BytesCData::new(" \t\r\n").is_whitespace());
+        assert!(!BytesCData::new(" text ").is_whitespace());
+    }
+
+    #[test]
+    fn bytestext_try_new() {
+        assert_eq!(
+            BytesText::try_new("a<b\t\r\n\u{10FFFF}").unwrap(),
+            BytesText::from_escaped("a&lt;b\t\r\n\u{10FFFF}")
+        );
+        assert_eq!(
+            BytesText::try_new("\u{0}"),
+            Err(EscapeError::IllegalCharacter(0, '\u{0}'))
+        );
+        assert_eq!(
+            BytesText::try_new("text\u{0}"),
+            Err(EscapeError::IllegalCharacter(4, '\u{0}'))
+        );
+        assert_eq!(
+            BytesText::try_new("\u{1B}[0m"),
+            Err(EscapeError::IllegalCharacter(0, '\u{1B}'))
+        );
+        assert_eq!(
+            BytesText::try_new("ы\u{FFFF}"),
+            Err(EscapeError::IllegalCharacter(2, '\u{FFFF}'))
+        );
+    }
+
     #[test]
     fn bytestart_clear_attributes() {
         let mut b = BytesStart::new("test");
@@ -1526,4 +2311,198 @@ mod test {
         assert_eq!(b.len(), 4);
         assert_eq!(b.name(), QName(b"test"));
     }
+
+    #[test]
+    fn bytestart_reset() {
+        let mut b = BytesStart::new("test");
+        b.push_attribute(("x", "y"));
+        let capacity = match &b.buf {
+            Cow::Owned(bytes) => bytes.capacity(),
+            Cow::Borrowed(_) => unreachable!("buffer should be owned after push_attribute"),
+        };
+        let ptr = b.buf.as_ptr();
+
+        b.reset(b"new");
+        assert!(b.attributes().next().is_none());
+        assert_eq!(b.name(), QName(b"new"));
+        assert_eq!(&*b, b"new");
+        // The allocation is reused
+        assert_eq!(b.buf.as_ptr(), ptr);
+        match &b.buf {
+            Cow::Owned(bytes) => assert_eq!(bytes.capacity(), capacity),
+            Cow::Borrowed(_) => unreachable!("buffer should stay owned"),
+        }
+
+        let mut b = BytesStart::new("borrowed");
+        b.reset(b"new");
+        assert_eq!(b.name(), QName(b"new"));
+    }
+
+    #[test]
+    fn bytestart_set_attribute_value() {
+        let mut b = BytesStart::from_content(r#"test a="1" b='2' c="3""#, 4);
+
+        // Longer and shorter values
+        assert_eq!(b.set_attribute_value(("b", "two")), true);
+        assert_eq!(&*b, br#"test a="1" b='two' c="3""#);
+        assert_eq!(b.set_attribute_value(("b", "")), true);
+        assert_eq!(&*b, br#"test a="1" b='' c="3""#);
+
+        // Value is escaped if created from strings
+        assert_eq!(b.set_attribute_value(("c", "<&>")), true);
+        assert_eq!(&*b, br#"test a="1" b='' c="&lt;&amp;&gt;""#);
+
+        // Raw values are inserted as is, except for the used quote character
+        let raw: (&[u8], &[u8]) = (b"a", br#"&amp;"'"#);
+        assert_eq!(b.set_attribute_value(raw), true);
+        assert_eq!(&*b, br#"test a="&amp;&quot;'" b='' c="&lt;&amp;&gt;""#);
+        let raw: (&[u8], &[u8]) = (b"b", br#""'"#);
+        assert_eq!(b.set_attribute_value(raw), true);
+        assert_eq!(
+            &*b,
+            br#"test a="&amp;&quot;'" b='"&apos;' c="&lt;&amp;&gt;""#
+        );
+
+        // Missing attribute
+        assert_eq!(b.set_attribute_value(("d", "4")), false);
+        assert_eq!(
+            &*b,
+            br#"test a="&amp;&quot;'" b='"&apos;' c="&lt;&amp;&gt;""#
+        );
+
+        let values: Vec<_> = b.attributes().map(|a| a.unwrap().value).collect();
+        assert_eq!(
+            values,
+            [
+                Cow::Borrowed(&br#"&amp;&quot;'"#[..]),
+                Cow::Borrowed(&br#""&apos;"#[..]),
+                Cow::Borrowed(&b"&lt;&amp;&gt;"[..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn bytestart_upsert_attribute() {
+        let mut b = BytesStart::new("test");
+        b.upsert_attribute(("a", "1"));
+        b.upsert_attribute(("b", "2"));
+        b.upsert_attribute(("a", "one"));
+        assert_eq!(&*b, br#"test a="one" b="2""#);
+    }
+
+    #[test]
+    fn bytestart_append_attributes_from() {
+        let mut b = BytesStart::from_content(r#"test a="1" b='2'"#, 4);
+        let other = BytesStart::from_content(r#"other b="two" c='say "3"' d="4""#, 5);
+        b.append_attributes_from(&other).unwrap();
+        assert_eq!(&*b, br#"test a="1" b='two' c='say "3"' d="4""#);
+
+        // Attributes before the broken one are copied
+        let broken = BytesStart::from_content(r#"broken a="one" e"#, 6);
+        assert_eq!(
+            b.append_attributes_from(&broken).unwrap_err(),
+            AttrError::ExpectedEq(16)
+        );
+        assert_eq!(&*b, br#"test a="one" b='two' c='say "3"' d="4""#);
+    }
+
+    #[test]
+    fn bytestart_attribute_count() {
+        let mut b = BytesStart::new("test");
+        assert_eq!(b.attribute_count(), Ok(0));
+        b.push_attribute(("x", "1"));
+        b.push_attribute(("y", "2"));
+        assert_eq!(b.attribute_count(), Ok(2));
+        b.push_attribute_flag("z");
+        assert_eq!(b.attribute_count(), Err(AttrError::ExpectedEq(18)));
+        b.clear_attributes();
+        assert_eq!(b.attribute_count(), Ok(0));
+    }
+
+    #[test]
+    fn bytestart_parse_attributes() {
+        let mut b = BytesStart::new("test");
+        assert_eq!(b.parse_attributes(), Ok(vec![]));
+        b.push_attribute(("x", "1"));
+        b.push_attribute(("y", "<"));
+        assert_eq!(
+            b.parse_attributes(),
+            Ok(vec![
+                (Cow::Borrowed(&b"x"[..]), Cow::Borrowed(&b"1"[..])),
+                (Cow::Borrowed(&b"y"[..]), Cow::Borrowed(&b"&lt;"[..])),
+            ])
+        );
+        b.push_attribute(("x", "2"));
+        assert_eq!(b.parse_attributes(), Err(AttrError::Duplicated(20, 5)));
+    }
+
+    #[test]
+    fn bytestart_attribute_value() {
+        let b = BytesStart::from_content(r#"test x="1" y='&lt;' x="2""#, 4);
+        // Present
+        assert_eq!(b.attribute_value("y"), Ok(Some(&b"&lt;"[..])));
+        // Absent
+        assert_eq!(b.attribute_value("w"), Ok(None));
+        // Duplicated, the first value is returned
+        assert_eq!(b.attribute_value(b"x"), Ok(Some(&b"1"[..])));
+
+        // Malformed attribute is returned only if it is reached
+        let b = BytesStart::from_content(r#"test x="1" z"#, 4);
+        assert_eq!(b.attribute_value("x"), Ok(Some(&b"1"[..])));
+        assert_eq!(b.attribute_value("w"), Err(AttrError::ExpectedEq(12)));
+    }
+
+    #[test]
+    fn event_predicates() {
+        let events = [
+            Event::Start(BytesStart::new("tag")),
+            Event::End(BytesEnd::new("tag")),
+            Event::Empty(BytesStart::new("tag")),
+            Event::Text(BytesText::new("text")),
+            Event::CData(BytesCData::new("cdata")),
+            Event::Comment(BytesText::new("comment")),
+            Event::Eof,
+        ];
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(event.is_start(), i == 0, "{:?}", event);
+            assert_eq!(event.is_end(), i == 1, "{:?}", event);
+            assert_eq!(event.is_empty_element(), i == 2, "{:?}", event);
+            assert_eq!(event.is_text(), i == 3, "{:?}", event);
+            assert_eq!(event.is_cdata(), i == 4, "{:?}", event);
+            assert_eq!(event.is_comment(), i == 5, "{:?}", event);
+            assert_eq!(event.is_eof(), i == 6, "{:?}", event);
+        }
+    }
+
+    #[test]
+    fn event_as_start() {
+        let start = BytesStart::new("tag");
+        assert_eq!(
+            Event::Start(start.borrow()).as_start(),
+            Some((&start, false))
+        );
+        assert_eq!(
+            Event::Empty(start.borrow()).as_start(),
+            Some((&start, true))
+        );
+        assert_eq!(Event::End(BytesEnd::new("tag")).as_start(), None);
+        assert_eq!(Event::Text(BytesText::new("text")).as_start(), None);
+        assert_eq!(Event::Eof.as_start(), None);
+    }
+
+    #[test]
+    fn event_text() {
+        let text = Event::Text(BytesText::from_escaped("x &amp; y"));
+        assert_eq!(text.text().unwrap().unwrap(), "x & y");
+
+        let cdata = Event::CData(BytesCData::new("x &amp; y"));
+        assert_eq!(cdata.text().unwrap().unwrap(), "x &amp; y");
+
+        let invalid = Event::Text(BytesText::from_escaped("x & y"));
+        assert!(invalid.text().unwrap().is_err());
+
+        assert!(Event::Comment(BytesText::new("comment")).text().is_none());
+        assert!(Event::Start(BytesStart::new("tag")).text().is_none());
+        assert!(Event::Eof.text().is_none());
+    }
 }
@@ -76,4 +76,6 @@ pub use crate::encoding::Decoder;
 pub use crate::errors::serialize::{DeError, SeError};
 pub use crate::errors::{Error, Result};
 pub use crate::reader::{NsReader, Reader};
-pub use crate::writer::{ElementWriter, Writer};
+#[cfg(feature = "serialize")]
+pub use crate::se::{Comment, Pi};
+pub use crate::writer::{reformat, ElementWriter, Writer};
@@ -3,10 +3,12 @@
 //!
 //! [spec]: https://www.w3.org/TR/xml-names11
 
+use crate::encoding::{Decoder, EncodingError};
 use crate::events::attributes::Attribute;
 use crate::events::BytesStart;
 use crate::utils::write_byte_string;
 use memchr::memchr;
+use std::borrow::Cow;
 use std::fmt::{self, Debug, Formatter};
 
 /// Some namespace was invalid
@@ -80,7 +82,14 @@ impl std::error::Error for NamespaceError {}
 /// A [qualified name] of an element or an attribute, including an optional
 /// namespace [prefix](Prefix) and a [local name](LocalName).
 ///
+/// Names are stored as raw bytes in the document encoding. Because all supported
+/// encodings are ASCII-compatible, and names in XML documents usually consist of
+/// ASCII characters, it is valid to compare raw bytes of names read from a
+/// document with names written in UTF-8, for example, `QName(b"tag")`. Use
+/// [`decode()`] to get a name as a string when it can contain non-ASCII characters.
+///
 /// [qualified name]: https://www.w3.org/TR/xml-names11/#dt-qualname
+/// [`decode()`]: Self::decode
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde-types", derive(serde::Deserialize, serde::Serialize))]
 pub struct QName<'a>(pub &'a [u8]);
@@ -105,6 +114,9 @@ impl<'a> QName<'a> {
     ///
     /// let qname = QName(b"namespace:simple-name");
     /// assert_eq!(qname.local_name().as_ref(), b"simple-name");
+    ///
+    /// let qname = QName(b"a:b:c");
+    /// assert_eq!(qname.local_name().as_ref(), b"b:c");
     /// ```
     pub fn local_name(&self) -> LocalName<'a> {
         LocalName(self.index().map_or(self.0, |i| &self.0[i + 1..]))
@@ -113,6 +125,9 @@ impl<'a> QName<'a> {
     /// Returns namespace part of this qualified name or `None` if namespace part
     /// is not defined (symbol `':'` not found).
     ///
+    /// Only the first `:` separates the prefix, so the prefix of `a:b:c` is `a`
+    /// and the prefix of `:x` is an empty prefix.
+    ///
     /// # Examples
     ///
     /// ```
@@ -172,6 +187,28 @@ impl<'a> QName<'a> {
         None
     }
 
+    /// Decodes the name using the specified decoder. Usually you will get the
+    /// decoder from the [`Reader::decoder()`] of the reader which produced the name.
+    ///
+    /// Does not allocate if the name is in UTF-8 encoding or contains only ASCII
+    /// characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::encoding::Decoder;
+    /// use quick_xml::name::QName;
+    ///
+    /// let qname = QName(b"prefix:name");
+    /// assert_eq!(qname.decode(Decoder::utf8()).unwrap(), "prefix:name");
+    /// ```
+    ///
+    /// [`Reader::decoder()`]: crate::reader::Reader::decoder
+    pub fn decode(&self, decoder: Decoder) -> Result<Cow<'a, str>, EncodingError> {
+        decoder.decode(self.0)
+    }
+
     /// Returns the index in the name where prefix ended
     #[inline(always)]
     fn index(&self) -> Option<usize> {
@@ -1191,6 +1228,11 @@ mod namespaces {
     /// Checks how the QName is decomposed to a prefix and a local name
     #[test]
     fn prefix_and_local_name() {
+        let name = QName(b"foo");
+        assert_eq!(name.prefix(), None);
+        assert_eq!(name.local_name(), LocalName(b"foo"));
+        assert_eq!(name.decompose(), (LocalName(b"foo"), None));
+
         let name = QName(b"foo:bus");
         assert_eq!(name.prefix(), Some(Prefix(b"foo")));
         assert_eq!(name.local_name(), LocalName(b"bus"));
@@ -0,0 +1,130 @@
+//! Contains a parser that searches an arbitrary byte sequence.
+
+use crate::errors::SyntaxError;
+use crate::parser::Parser;
+
+/// A parser that search the specified sequence of bytes (a delimiter) in the
+/// slice. Unlike other parsers, it does not know anything about XML grammar.
+///
+/// After successful search the parser will return [`Some`] with position of
+/// the last byte of the delimiter, so the reader consumes the whole delimiter,
+/// but returns the delimiter without its last byte at the end of read data.
+/// If search was unsuccessful, a [`None`] will be returned.
+///
+/// NOTE: after successful match the parser does not returned to the initial
+/// state and should not be used anymore. Create a new parser if you want to perform
+/// new search.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct DelimiterParser<'n> {
+    /// The sequence to search. Should not be empty
+    delimiter: &'n [u8],
+    /// The number of first bytes of the delimiter that was found at the end
+    /// of the bytes fed in the previous attempt to find the delimiter
+    matched: usize,
+}
+
+impl<'n> DelimiterParser<'n> {
+    /// Creates a parser that searches `delimiter`, which should not be empty.
+    #[inline]
+    pub const fn new(delimiter: &'n [u8]) -> Self {
+        Self {
+            delimiter,
+            matched: 0,
+        }
+    }
+}
+
+impl<'n> Parser for DelimiterParser<'n> {
+    /// Determines the position of the last byte of the delimiter in the provided
+    /// slice. The delimiter can start in the previously fed slices.
+    ///
+    /// Returns position of the last byte of the delimiter or `None` if the
+    /// delimiter was not found.
+    #[inline]
+    fn feed(&mut self, bytes: &[u8]) -> Option<usize> {
+        let delimiter = self.delimiter;
+        let len = delimiter.len();
+
+        // Try to continue matches started in the previous slices, the longest first.
+        // Every suffix of the matched part, which is also a prefix of the delimiter,
+        // is a possible start of the delimiter
+        for start in (1..=self.matched).rev() {
+            if delimiter[..start] != delimiter[self.matched - start..self.matched] {
+                continue;
+            }
+            let rest = &delimiter[start..];
+            if bytes.len() >= rest.len() {
+                if bytes.starts_with(rest) {
+                    return Some(rest.len() - 1);
+                }
+            } else if rest.starts_with(bytes) {
+                self.matched = start + bytes.len();
+                return None;
+            }
+        }
+
+        if let Some(i) = memchr::memmem::find(bytes, delimiter) {
+            return Some(i + len - 1);
+        }
+
+        // Remember the longest suffix of `bytes` that is a prefix of the delimiter
+        let max = (len - 1).min(bytes.len());
+        self.matched = (1..=max)
+            .rev()
+            .find(|&n| bytes.ends_with(&delimiter[..n]))
+            .unwrap_or(0);
+        None
+    }
+
+    /// This error is never returned to the user, because the end of input
+    /// without a delimiter is not an error for the users of this parser.
+    #[inline]
+    fn eof_error() -> SyntaxError {
+        SyntaxError::UnclosedTag
+    }
+}
+
+#[test]
+fn delimiter() {
+    use pretty_assertions::assert_eq;
+
+    /// Returns `Ok(pos)` with the position in the buffer where the delimiter
+    /// is ended.
+    ///
+    /// Returns `Err(internal_state)` if parsing is not done yet.
+    fn parse(delimiter: &[u8], bytes: &[u8], matched: usize) -> Result<usize, usize> {
+        let mut parser = DelimiterParser { delimiter, matched };
+        match parser.feed(bytes) {
+            Some(i) => Ok(i),
+            None => Err(parser.matched),
+        }
+    }
+
+    // Comments shows which characters of the delimiter was seen before calling `feed`.
+    // `x` means any character, pipe denotes start of the buffer that passed to `feed`
+
+    assert_eq!(parse(b"-->", b"", 0), Err(0)); // x|
+    assert_eq!(parse(b"-->", b"", 2), Err(2)); // --|
+
+    assert_eq!(parse(b"-->", b"-", 0), Err(1)); // x|-
+    assert_eq!(parse(b"-->", b"-", 1), Err(2)); // -|-
+    assert_eq!(parse(b"-->", b"-", 2), Err(2)); // --|-
+
+    assert_eq!(parse(b"-->", b">", 0), Err(0)); // x|>
+    assert_eq!(parse(b"-->", b">", 1), Err(0)); // -|>
+    assert_eq!(parse(b"-->", b">", 2), Ok(0)); // --|>
+
+    assert_eq!(parse(b"-->", b"->", 0), Err(0)); // x|->
+    assert_eq!(parse(b"-->", b"->", 1), Ok(1)); // -|->
+    assert_eq!(parse(b"-->", b"->", 2), Ok(1)); // --|->
+
+    assert_eq!(parse(b"-->", b"a-->b", 0), Ok(3)); // x|a-->b
+    assert_eq!(parse(b"-->", b"a--", 0), Err(2)); // x|a--
+    assert_eq!(parse(b"-->", b"a-->b", 2), Ok(3)); // --|a-->b
+
+    // Overlapped partial matches
+    assert_eq!(parse(b"abac", b"c", 3), Ok(0)); // aba|c
+    assert_eq!(parse(b"abac", b"bac", 3), Ok(2)); // aba|bac
+    assert_eq!(parse(b"abac", b"ac", 3), Err(0)); // aba|ac
+    assert_eq!(parse(b"abac", b"ba", 1), Err(3)); // a|ba
+}
@@ -2,9 +2,11 @@
 
 use crate::errors::SyntaxError;
 
+mod delimiter;
 mod element;
 mod pi;
 
+pub(crate) use delimiter::DelimiterParser;
 pub use element::ElementParser;
 pub use pi::PiParser;
 
@@ -5,13 +5,52 @@ use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 
+#[cfg(feature = "gzip")]
+use {flate2::read::GzDecoder, std::io::Read};
+
+use crate::encoding::Decoder;
 use crate::errors::{Error, Result};
-use crate::events::Event;
+use crate::events::{BytesCData, BytesDecl, BytesEnd, BytesPI, BytesStart, BytesText, Event};
 use crate::name::QName;
-use crate::parser::Parser;
+use crate::parser::{DelimiterParser, Parser};
 use crate::reader::{BangType, ReadTextResult, Reader, Span, XmlSource};
 use crate::utils::is_whitespace;
 
+/// Returns an error for the case when reading of the incomplete markup is resumed,
+/// but the bytes of that markup, which were read by the previous call, are not
+/// in the buffer anymore. Those bytes were already consumed from the underlying
+/// reader, so the markup cannot be restored.
+///
+/// See [`Config::resume_incomplete_markup`](crate::reader::Config::resume_incomplete_markup)
+pub(super) fn incomplete_markup_lost() -> Error {
+    Error::Io(
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the buffer does not contain the beginning of the incomplete markup; \
+             pass the same buffer without clearing it to continue reading the markup",
+        )
+        .into(),
+    )
+}
+
+/// Replaces the `encoding` attribute of a declaration, decoded to UTF-8, with
+/// `UTF-8`. Declarations without that attribute or with malformed version are
+/// returned as is.
+fn utf8_decl(decl: BytesDecl) -> BytesDecl {
+    if decl.encoding().is_none() {
+        return decl;
+    }
+    let version = match decl.version() {
+        Ok(version) => String::from_utf8_lossy(&version).into_owned(),
+        Err(_) => return decl,
+    };
+    let standalone = match decl.standalone() {
+        Some(Ok(s)) => Some(String::from_utf8_lossy(&s).into_owned()),
+        _ => None,
+    };
+    BytesDecl::new(&version, Some("UTF-8"), standalone.as_deref())
+}
+
 macro_rules! impl_buffered_source {
     ($($lf:lifetime, $reader:tt, $async:ident, $await:ident)?) => {
         #[cfg(not(feature = "encoding"))]
@@ -35,12 +74,17 @@ macro_rules! impl_buffered_source {
 
         #[cfg(feature = "encoding")]
         #[inline]
-        $($async)? fn detect_encoding(&mut self) -> io::Result<Option<&'static encoding_rs::Encoding>> {
+        $($async)? fn detect_encoding(
+            &mut self,
+            consume_bom: bool,
+        ) -> io::Result<Option<(&'static encoding_rs::Encoding, usize)>> {
             loop {
                 break match self $(.$reader)? .fill_buf() $(.$await)? {
                     Ok(n) => if let Some((enc, bom_len)) = crate::encoding::detect_encoding(n) {
-                        self $(.$reader)? .consume(bom_len);
-                        Ok(Some(enc))
+                        if consume_bom {
+                            self $(.$reader)? .consume(bom_len);
+                        }
+                        Ok(Some((enc, bom_len)))
                     } else {
                         Ok(None)
                     },
@@ -54,6 +98,7 @@ macro_rules! impl_buffered_source {
         $($async)? fn read_text $(<$lf>)? (
             &mut self,
             buf: &'b mut Vec<u8>,
+            max_len: usize,
             position: &mut u64,
         ) -> ReadTextResult<'b, &'b mut Vec<u8>> {
             let mut read = 0;
@@ -77,6 +122,10 @@ macro_rules! impl_buffered_source {
                         *position += 1;
                         return ReadTextResult::Markup(buf);
                     }
+                    Some(i) if read as usize + i > max_len => {
+                        *position += read;
+                        return ReadTextResult::TooLong;
+                    }
                     Some(i) => {
                         buf.extend_from_slice(&available[..i]);
 
@@ -93,6 +142,11 @@ macro_rules! impl_buffered_source {
                         let used = available.len();
                         self $(.$reader)? .consume(used);
                         read += used as u64;
+
+                        if read as usize > max_len {
+                            *position += read;
+                            return ReadTextResult::TooLong;
+                        }
                     }
                 }
             }
@@ -106,10 +160,17 @@ macro_rules! impl_buffered_source {
             &mut self,
             mut parser: P,
             buf: &'b mut Vec<u8>,
+            read_before: usize,
             position: &mut u64,
         ) -> Result<&'b [u8]> {
             let mut read = 0;
-            let start = buf.len();
+            let start = match buf.len().checked_sub(read_before) {
+                Some(start) => start,
+                None => return Err(crate::reader::buffered_reader::incomplete_markup_lost()),
+            };
+            // Restore the parser state after the previous interrupted read.
+            // The end was not found in those bytes, so the result is always `None`
+            let _ = parser.feed(&buf[start..]);
             loop {
                 let available = match self $(.$reader)? .fill_buf() $(.$await)? {
                     Ok(n) if n.is_empty() => break,
@@ -148,16 +209,27 @@ macro_rules! impl_buffered_source {
         $($async)? fn read_bang_element $(<$lf>)? (
             &mut self,
             buf: &'b mut Vec<u8>,
+            read_before: usize,
             position: &mut u64,
         ) -> Result<(BangType, &'b [u8])> {
-            // Peeked one bang ('!') before being called, so it's guaranteed to
-            // start with it.
-            let start = buf.len();
-            let mut read = 1;
-            buf.push(b'!');
-            self $(.$reader)? .consume(1);
+            let start = match buf.len().checked_sub(read_before) {
+                Some(start) => start,
+                None => return Err(crate::reader::buffered_reader::incomplete_markup_lost()),
+            };
+            let mut read = 0;
+            if read_before == 0 {
+                // Peeked one bang ('!') before being called, so it's guaranteed to
+                // start with it.
+                read = 1;
+                buf.push(b'!');
+                self $(.$reader)? .consume(1);
+            }
 
-            let mut bang_type = BangType::new(self.peek_one() $(.$await)? ?)?;
+            let next = match buf.get(start + 1) {
+                Some(&byte) => Some(byte),
+                None => self.peek_one() $(.$await)? ?,
+            };
+            let mut bang_type = BangType::new(next)?;
 
             loop {
                 match self $(.$reader)? .fill_buf() $(.$await)? {
@@ -225,6 +297,18 @@ macro_rules! impl_buffered_source {
                 };
             }
         }
+
+        #[inline]
+        fn can_resume(&self) -> bool {
+            true
+        }
+
+        // Async readers do not report syntax error context
+        #[allow(dead_code)]
+        #[inline]
+        fn remaining(&self) -> Option<&[u8]> {
+            None
+        }
     };
 }
 
@@ -260,6 +344,11 @@ impl<R: BufRead> Reader<R> {
     ///   end of your loop).
     /// - Reserve the buffer length if you know the file size (using `Vec::with_capacity`).
     ///
+    /// When the end of input is reached, `Event::Eof` is returned. After that
+    /// all subsequent calls also return `Event::Eof`, so the reader can be safely
+    /// polled again. Reading usually can be continued after [`Error::IllFormed`]
+    /// errors up to the end of input.
+    ///
     /// # Examples
     ///
     /// ```
@@ -294,6 +383,209 @@ impl<R: BufRead> Reader<R> {
         self.read_event_impl(buf)
     }
 
+    /// Reads the next event without consuming it. The next call to
+    /// [`read_event_into()`] (or to any other method that reads events) returns
+    /// the same event.
+    ///
+    /// Because the event should outlive the buffer, it is copied into the owned
+    /// storage inside the reader. Calling this method several times in a row
+    /// returns the same event and does not touch the buffer.
+    ///
+    /// Note, that [`buffer_position()`] already points after the peeked event.
+    /// The span returned by [`read_to_end_into()`] called while an event is
+    /// peeked still starts before that event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_reader("<tag/>".as_bytes());
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(
+    ///     reader.peek_event_into(&mut buf).unwrap(),
+    ///     &Event::Empty(BytesStart::new("tag"))
+    /// );
+    /// assert_eq!(
+    ///     reader.read_event_into(&mut buf).unwrap(),
+    ///     Event::Empty(BytesStart::new("tag"))
+    /// );
+    /// assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+    /// ```
+    ///
+    /// [`read_event_into()`]: Self::read_event_into
+    /// [`buffer_position()`]: Self::buffer_position
+    /// [`read_to_end_into()`]: Self::read_to_end_into
+    pub fn peek_event_into(&mut self, buf: &mut Vec<u8>) -> Result<&Event<'static>> {
+        let peeked = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => {
+                let position = self.buffer_position();
+                (self.read_event_impl(buf)?.into_owned(), position)
+            }
+        };
+        // The peeked event is not returned yet
+        self.state.markup = None;
+        Ok(&self.peeked.insert(peeked).0)
+    }
+
+    /// Reads the next event using a buffer owned by the reader and returns
+    /// an owned copy of that event.
+    ///
+    /// This is a convenience method that frees you from managing a buffer: the
+    /// internal buffer is cleared automatically before each read, and because
+    /// the returned event does not borrow from the reader, you can keep it while
+    /// reading the next events. The buffer is not cleared only when reading of
+    /// an incomplete markup is continued (see [`Config::resume_incomplete_markup`]).
+    ///
+    /// # Performance
+    ///
+    /// The content of each event is copied into a new allocation, so this method
+    /// is slower than [`read_event_into()`] with a reused buffer, which returns
+    /// events borrowed from the buffer without copying. Also the internal buffer
+    /// is never shrunk, so it holds the memory required for the largest event
+    /// read so far until the reader is dropped. Use [`read_event_into()`] when
+    /// you want full control over buffering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_reader("<tag>text</tag>".as_bytes());
+    /// let mut events = Vec::new();
+    /// loop {
+    ///     match reader.read_event_owned().unwrap() {
+    ///         Event::Eof => break,
+    ///         event => events.push(event),
+    ///     }
+    /// }
+    /// assert_eq!(events, [
+    ///     Event::Start(BytesStart::new("tag")),
+    ///     Event::Text(BytesText::new("text")),
+    ///     Event::End(BytesEnd::new("tag")),
+    /// ]);
+    /// ```
+    ///
+    /// [`read_event_into()`]: Self::read_event_into
+    /// [`Config::resume_incomplete_markup`]: crate::reader::Config::resume_incomplete_markup
+    pub fn read_event_owned(&mut self) -> Result<Event<'static>> {
+        let mut buf = std::mem::take(&mut self.buf);
+        // Keep the already read part of the incomplete markup, so its reading
+        // could be continued
+        if self.state.incomplete.is_none() {
+            buf.clear();
+        }
+        let result = self.read_event_impl(&mut buf).map(Event::into_owned);
+        self.buf = buf;
+        result
+    }
+
+    /// Reads the next event and decodes its content into `str_buf`. The returned
+    /// event borrows from `str_buf` and its content is always in UTF-8.
+    ///
+    /// This is a convenience method that trades zero-copy parsing for simplicity:
+    /// content of each event is copied to `str_buf`, which is cleared before reading.
+    /// Use it when the input is not in UTF-8 (for example, in Windows-1251 or Shift_JIS)
+    /// and you would decode every event anyway. `buf` is used as an intermediate
+    /// storage of the raw event content, as in [`read_event_into()`]. Reuse both
+    /// buffers between calls to avoid allocations.
+    ///
+    /// Because the content of the event is already decoded, do not use decoder
+    /// of this reader to decode it. Use [`Decoder::utf8()`] instead, for example,
+    /// when calling [`Attribute::decode_and_unescape_value()`].
+    ///
+    /// The `encoding` attribute of the [`Decl`] event, if present, is replaced
+    /// by `UTF-8`, so the declaration describes the decoded content.
+    ///
+    /// Text events still contain escaped content, use [`BytesText::unescape()`]
+    /// to unescape it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, BytesText, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_reader(b"<tag attr='value'>text</tag>".as_ref());
+    /// let mut buf = Vec::new();
+    /// let mut str_buf = String::new();
+    ///
+    /// assert_eq!(
+    ///     reader.read_event_into_decoded(&mut buf, &mut str_buf).unwrap(),
+    ///     Event::Start(BytesStart::from_content("tag attr='value'", 3))
+    /// );
+    /// buf.clear();
+    /// assert_eq!(
+    ///     reader.read_event_into_decoded(&mut buf, &mut str_buf).unwrap(),
+    ///     Event::Text(BytesText::new("text"))
+    /// );
+    /// ```
+    ///
+    /// [`read_event_into()`]: Self::read_event_into
+    /// [`Decl`]: Event::Decl
+    /// [`Decoder::utf8()`]: crate::encoding::Decoder::utf8
+    /// [`Attribute::decode_and_unescape_value()`]: crate::events::attributes::Attribute::decode_and_unescape_value
+    /// [`BytesText::unescape()`]: crate::events::BytesText::unescape
+    pub fn read_event_into_decoded<'s>(
+        &mut self,
+        buf: &mut Vec<u8>,
+        str_buf: &'s mut String,
+    ) -> Result<Event<'s>> {
+        str_buf.clear();
+        let decoder = self.decoder();
+        let event = self.read_event_into(buf)?;
+        // Length of the name (or PI target) in the decoded content
+        let mut name_len = 0;
+        match &event {
+            Event::Start(e) | Event::Empty(e) => {
+                let len = e.name().as_ref().len();
+                decoder.decode_into(&e[..len], str_buf)?;
+                name_len = str_buf.len();
+                decoder.decode_into(&e[len..], str_buf)?;
+            }
+            Event::PI(e) => {
+                let len = e.target().len();
+                decoder.decode_into(&e[..len], str_buf)?;
+                name_len = str_buf.len();
+                decoder.decode_into(&e[len..], str_buf)?;
+            }
+            Event::End(e) => decoder.decode_into(e, str_buf)?,
+            Event::Text(e) | Event::Comment(e) | Event::DocType(e) => {
+                decoder.decode_into(e, str_buf)?
+            }
+            Event::CData(e) => decoder.decode_into(e, str_buf)?,
+            Event::Decl(e) => decoder.decode_into(e, str_buf)?,
+            Event::Eof => {}
+        }
+
+        let content: &'s String = str_buf;
+        let content = content.as_bytes();
+        let utf8 = Decoder::utf8();
+        Ok(match event {
+            Event::Start(_) => Event::Start(BytesStart::wrap(content, name_len)),
+            Event::Empty(_) => Event::Empty(BytesStart::wrap(content, name_len)),
+            Event::End(_) => Event::End(BytesEnd::wrap(content.into())),
+            Event::Text(_) => Event::Text(BytesText::wrap(content, utf8)),
+            Event::CData(_) => Event::CData(BytesCData::wrap(content, utf8)),
+            Event::Comment(_) => Event::Comment(BytesText::wrap(content, utf8)),
+            Event::Decl(_) => {
+                // Declaration always starts with `xml`
+                let decl = BytesDecl::from_start(BytesStart::wrap(content, 3));
+                Event::Decl(utf8_decl(decl))
+            }
+            Event::PI(_) => Event::PI(BytesPI::wrap(content, name_len)),
+            Event::DocType(_) => Event::DocType(BytesText::wrap(content, utf8)),
+            Event::Eof => Event::Eof,
+        })
+    }
+
     /// Reads until end element is found using provided buffer as intermediate
     /// storage for events content. This function is supposed to be called after
     /// you already read a [`Start`] event.
@@ -387,6 +679,177 @@ impl<R: BufRead> Reader<R> {
             buf.clear();
         }))
     }
+
+    /// Reads the whole element `name` and returns owned copies of all its events:
+    /// the start event, events of all descendants and the end event.
+    ///
+    /// Unlike [`read_to_end_into()`], this method should be called _before_ the
+    /// start event of the element is read. If the next event is not a [`Start`]
+    /// or [`Empty`] event with the name `name`, nothing is consumed and an empty
+    /// vector is returned. For an [`Empty`] event the vector contains only that
+    /// event.
+    ///
+    /// If a corresponding [`End`] event is not found, an error of type
+    /// [`Error::IllFormed`] will be returned. Events read before the error are lost.
+    ///
+    /// The provided `buf` buffer will be filled only by one event content at time.
+    /// Before reading of each event the buffer will be cleared.
+    ///
+    /// # Memory usage
+    ///
+    /// Because the returned events are owned, the content of the whole element
+    /// is copied into memory, and the memory required grows with the size of
+    /// the element. The only limit applied is the [`max_text_len`] option, which
+    /// restricts the size of each text event; neither the nesting depth nor the
+    /// total size of the element are limited. Use this method only for elements
+    /// which size is known to be small, and process big or untrusted elements
+    /// event by event with [`read_event_into()`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+    /// use quick_xml::name::QName;
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_reader("<root><item>1</item><item/></root>".as_bytes());
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(
+    ///     reader.read_event_into(&mut buf).unwrap(),
+    ///     Event::Start(BytesStart::new("root"))
+    /// );
+    /// assert_eq!(
+    ///     reader.read_subtree_events(QName(b"item"), &mut buf).unwrap(),
+    ///     [
+    ///         Event::Start(BytesStart::new("item")),
+    ///         Event::Text(BytesText::new("1")),
+    ///         Event::End(BytesEnd::new("item")),
+    ///     ]
+    /// );
+    /// assert_eq!(
+    ///     reader.read_subtree_events(QName(b"item"), &mut buf).unwrap(),
+    ///     [Event::Empty(BytesStart::new("item"))]
+    /// );
+    /// // The next event is the end of `root`, so it is not consumed
+    /// assert_eq!(reader.read_subtree_events(QName(b"item"), &mut buf).unwrap(), []);
+    /// assert_eq!(
+    ///     reader.read_event_into(&mut buf).unwrap(),
+    ///     Event::End(BytesEnd::new("root"))
+    /// );
+    /// ```
+    ///
+    /// [`read_to_end_into()`]: Self::read_to_end_into
+    /// [`read_event_into()`]: Self::read_event_into
+    /// [`Start`]: Event::Start
+    /// [`Empty`]: Event::Empty
+    /// [`End`]: Event::End
+    /// [`max_text_len`]: crate::reader::Config::max_text_len
+    pub fn read_subtree_events(
+        &mut self,
+        name: QName,
+        buf: &mut Vec<u8>,
+    ) -> Result<Vec<Event<'static>>> {
+        buf.clear();
+        let is_empty = match self.peek_event_into(buf)? {
+            Event::Start(e) if e.name() == name => false,
+            Event::Empty(e) if e.name() == name => true,
+            _ => return Ok(Vec::new()),
+        };
+        let mut events = Vec::new();
+        if is_empty {
+            events.push(self.read_event_impl(&mut *buf)?.into_owned());
+            return Ok(events);
+        }
+        let mut depth = 0;
+        loop {
+            buf.clear();
+            let event = self.read_event_impl(&mut *buf)?;
+            match &event {
+                Event::Start(e) if e.name() == name => depth += 1,
+                Event::End(e) if e.name() == name => depth -= 1,
+                Event::Eof => return Err(Error::missed_end(name, self.decoder())),
+                _ => (),
+            }
+            events.push(event.into_owned());
+            if depth == 0 {
+                return Ok(events);
+            }
+        }
+    }
+
+    /// Reads raw bytes from the underlying reader until the `needle` sequence
+    /// is found or the end of input is reached.
+    ///
+    /// Read bytes are appended to the `buf` and returned without the `needle`,
+    /// which is consumed. The returned flag is `true` if the `needle` was found
+    /// and `false` if the end of input was reached before it. An empty `needle`
+    /// is found immediately without consuming anything.
+    ///
+    /// This method operates at the byte level and does not interpret the XML
+    /// structure: the read bytes are not checked and will not be returned in
+    /// any subsequent events. Like the [`stream()`], it does not affect the state
+    /// of the parser, so it is intended to be used for ad-hoc extraction of data
+    /// which boundaries are known to the caller. [`buffer_position()`] is updated
+    /// accordingly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_reader("<tag>raw <data> -- here</tag>".as_bytes());
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(
+    ///     reader.read_event_into(&mut buf).unwrap(),
+    ///     Event::Start(BytesStart::new("tag"))
+    /// );
+    ///
+    /// buf.clear();
+    /// assert_eq!(
+    ///     reader.read_until(b"</tag>", &mut buf).unwrap(),
+    ///     (b"raw <data> -- here".as_ref(), true)
+    /// );
+    /// assert_eq!(reader.buffer_position(), 29);
+    ///
+    /// buf.clear();
+    /// assert_eq!(reader.read_until(b"</tag>", &mut buf).unwrap(), (b"".as_ref(), false));
+    /// ```
+    ///
+    /// [`stream()`]: Self::stream
+    /// [`buffer_position()`]: Self::buffer_position
+    pub fn read_until<'b>(
+        &mut self,
+        needle: &[u8],
+        buf: &'b mut Vec<u8>,
+    ) -> Result<(&'b [u8], bool)> {
+        let start = buf.len();
+        if needle.is_empty() {
+            return Ok((&buf[start..], true));
+        }
+        let found = match self.reader.read_with(
+            DelimiterParser::new(needle),
+            &mut *buf,
+            0,
+            &mut self.state.offset,
+        ) {
+            Ok(_) => true,
+            // The only syntax error that parser can return is an unexpected end of input
+            Err(Error::Syntax(_)) => false,
+            Err(e) => return Err(e),
+        };
+        // The last byte of the needle is not stored in the buffer
+        let end = if found {
+            buf.len() - (needle.len() - 1)
+        } else {
+            buf.len()
+        };
+        Ok((&buf[start..end], found))
+    }
 }
 
 impl Reader<BufReader<File>> {
@@ -398,6 +861,38 @@ impl Reader<BufReader<File>> {
     }
 }
 
+#[cfg(feature = "gzip")]
+impl<R: Read> Reader<BufReader<GzDecoder<R>>> {
+    /// Creates an XML reader from a reader of gzip-compressed data. The data
+    /// are decompressed on the fly while reading.
+    ///
+    /// This method is only available when the `gzip` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use flate2::{write::GzEncoder, Compression};
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::reader::Reader;
+    /// use std::io::Write;
+    ///
+    /// let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    /// encoder.write_all(b"<root/>").unwrap();
+    /// let compressed = encoder.finish().unwrap();
+    ///
+    /// let mut reader = Reader::from_gzip_reader(compressed.as_slice());
+    /// let mut buf = Vec::new();
+    /// assert_eq!(
+    ///     reader.read_event_into(&mut buf).unwrap(),
+    ///     Event::Empty(BytesStart::new("root"))
+    /// );
+    /// ```
+    pub fn from_gzip_reader(reader: R) -> Self {
+        Self::from_reader(BufReader::new(GzDecoder::new(reader)))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::reader::test::check;
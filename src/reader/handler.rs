@@ -0,0 +1,195 @@
+//! A callback-based (SAX-style) interface on top of the pull [`Reader`].
+
+use std::io::BufRead;
+use std::ops::ControlFlow;
+
+use crate::errors::Result;
+use crate::events::{BytesCData, BytesDecl, BytesEnd, BytesPI, BytesStart, BytesText, Event};
+use crate::reader::Reader;
+
+/// A receiver of events read by [`parse_with()`].
+///
+/// Each method is called for the corresponding [`Event`] and returns
+/// [`ControlFlow::Continue`] to continue parsing or [`ControlFlow::Break`] to
+/// stop it. All methods have default implementations that ignore the event
+/// and continue parsing, so only methods for events of interest should be
+/// implemented.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use std::ops::ControlFlow;
+/// use quick_xml::events::BytesText;
+/// use quick_xml::reader::{parse_with, EventHandler, Reader};
+///
+/// /// Collects all texts in the document
+/// struct Texts(Vec<String>);
+///
+/// impl EventHandler for Texts {
+///     fn text(&mut self, e: &BytesText) -> ControlFlow<()> {
+///         self.0.push(e.unescape().unwrap().into_owned());
+///         ControlFlow::Continue(())
+///     }
+/// }
+///
+/// let mut reader = Reader::from_str("<a>first</a><b>second &amp; last</b>");
+/// let mut handler = Texts(Vec::new());
+/// parse_with(&mut reader, &mut Vec::new(), &mut handler).unwrap();
+///
+/// assert_eq!(handler.0, ["first", "second & last"]);
+/// ```
+pub trait EventHandler {
+    /// Called for a start tag `<tag>` ([`Event::Start`]).
+    fn start_element(&mut self, e: &BytesStart) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for a self-closed tag `<tag/>` ([`Event::Empty`]).
+    ///
+    /// The default implementation calls [`start_element()`] and then
+    /// [`end_element()`] (unless the first call requested a stop), so handlers
+    /// which do not distinguish self-closed elements from empty paired ones do
+    /// not need to implement this method.
+    ///
+    /// [`start_element()`]: Self::start_element
+    /// [`end_element()`]: Self::end_element
+    fn empty_element(&mut self, e: &BytesStart) -> ControlFlow<()> {
+        match self.start_element(e) {
+            ControlFlow::Continue(()) => self.end_element(&e.to_end()),
+            ControlFlow::Break(()) => ControlFlow::Break(()),
+        }
+    }
+
+    /// Called for an end tag `</tag>` ([`Event::End`]).
+    fn end_element(&mut self, e: &BytesEnd) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for a text between markup ([`Event::Text`]).
+    fn text(&mut self, e: &BytesText) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for a CDATA section `<![CDATA[...]]>` ([`Event::CData`]).
+    fn cdata(&mut self, e: &BytesCData) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for a comment `<!-- ... -->` ([`Event::Comment`]).
+    fn comment(&mut self, e: &BytesText) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for an XML declaration `<?xml ...?>` ([`Event::Decl`]).
+    fn declaration(&mut self, e: &BytesDecl) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for a processing instruction `<?...?>` ([`Event::PI`]).
+    fn processing_instruction(&mut self, e: &BytesPI) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for a document type definition `<!DOCTYPE ...>` ([`Event::DocType`]).
+    fn doctype(&mut self, e: &BytesText) -> ControlFlow<()> {
+        let _ = e;
+        ControlFlow::Continue(())
+    }
+
+    /// Called once when the end of the document is reached ([`Event::Eof`]).
+    /// Not called if parsing was stopped by the handler or by an error.
+    fn end_document(&mut self) {}
+}
+
+/// Reads events from the `reader` and passes each of them to the corresponding
+/// method of the `handler` until the end of the document or until a handler
+/// method returns [`ControlFlow::Break`].
+///
+/// This is a thin layer over [`Reader::read_event_into()`], so the reader
+/// is configured as usual, for example, with [`Config::expand_empty_elements`]
+/// the [`EventHandler::empty_element()`] is never called. The first error
+/// returned by the reader stops parsing and is returned from this function.
+///
+/// `buf` is used to store events as in [`Reader::read_event_into()`]. It is
+/// cleared after each event passed to the handler, but not when an error is
+/// returned, so if reading of an incomplete markup can be [resumed], pass
+/// the same buffer to the next call.
+///
+/// Returns `Ok(ControlFlow::Break(()))` if parsing was stopped by the handler.
+/// In that case the reader is positioned right after the event for which
+/// the handler requested the stop, so the rest of the document can be read
+/// later either with `parse_with` or with the `reader` itself.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use std::ops::ControlFlow;
+/// use quick_xml::events::BytesStart;
+/// use quick_xml::reader::{parse_with, EventHandler, Reader};
+///
+/// /// Finds the name of the first element with the `id` attribute
+/// struct FindId(Option<String>);
+///
+/// impl EventHandler for FindId {
+///     fn start_element(&mut self, e: &BytesStart) -> ControlFlow<()> {
+///         if let Ok(Some(_)) = e.try_get_attribute("id") {
+///             self.0 = Some(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+///             return ControlFlow::Break(());
+///         }
+///         ControlFlow::Continue(())
+///     }
+/// }
+///
+/// let mut reader = Reader::from_str(r#"<root><a/><b id="1"/><c id="2"/></root>"#);
+/// let mut handler = FindId(None);
+/// let mut buf = Vec::new();
+///
+/// assert_eq!(
+///     parse_with(&mut reader, &mut buf, &mut handler).unwrap(),
+///     ControlFlow::Break(())
+/// );
+/// assert_eq!(handler.0.as_deref(), Some("b"));
+/// ```
+///
+/// [`Config::expand_empty_elements`]: crate::reader::Config::expand_empty_elements
+/// [resumed]: crate::reader::Config::resume_incomplete_markup
+pub fn parse_with<R, H>(
+    reader: &mut Reader<R>,
+    buf: &mut Vec<u8>,
+    handler: &mut H,
+) -> Result<ControlFlow<()>>
+where
+    R: BufRead,
+    H: EventHandler + ?Sized,
+{
+    loop {
+        let flow = match reader.read_event_into(buf)? {
+            Event::Start(e) => handler.start_element(&e),
+            Event::Empty(e) => handler.empty_element(&e),
+            Event::End(e) => handler.end_element(&e),
+            Event::Text(e) => handler.text(&e),
+            Event::CData(e) => handler.cdata(&e),
+            Event::Comment(e) => handler.comment(&e),
+            Event::Decl(e) => handler.declaration(&e),
+            Event::PI(e) => handler.processing_instruction(&e),
+            Event::DocType(e) => handler.doctype(&e),
+            Event::Eof => {
+                handler.end_document();
+                return Ok(ControlFlow::Continue(()));
+            }
+        };
+        if let ControlFlow::Break(()) = flow {
+            return Ok(flow);
+        }
+        buf.clear();
+    }
+}
@@ -2,11 +2,12 @@
 
 #[cfg(feature = "encoding")]
 use encoding_rs::Encoding;
-use std::io;
+use std::collections::HashMap;
+use std::io::{self, BufRead};
 use std::ops::Range;
 
 use crate::encoding::Decoder;
-use crate::errors::{Error, SyntaxError};
+use crate::errors::{Error, SyntaxError, Warning};
 use crate::events::Event;
 use crate::parser::{ElementParser, Parser, PiParser};
 use crate::reader::state::ReaderState;
@@ -43,6 +44,50 @@ pub struct Config {
     /// [`End`]: crate::events::Event::End
     pub allow_unmatched_ends: bool,
 
+    /// Whether the [`NsReader`] should attach the resolved namespace URI to
+    /// each [`Start`], [`Empty`] and [`End`] event. If enabled, the namespace
+    /// can be retrieved by calling [`BytesStart::namespace()`] or
+    /// [`BytesEnd::namespace()`] on the event itself, without a separate call
+    /// to the resolver.
+    ///
+    /// This option has no effect on the [`Reader`].
+    ///
+    /// Note, that setting this to `true` will lead to additional allocations
+    /// for each element bound to a namespace, because the namespace URI is
+    /// copied to the event so that the event does not borrow the reader.
+    ///
+    /// The attached namespace is not taken into account when events are
+    /// compared, so an event read with this option enabled is still equal
+    /// to the same event constructed by hand (for example, with
+    /// [`BytesStart::new()`]).
+    ///
+    /// Default: `false`
+    ///
+    /// [`BytesStart::new()`]: crate::events::BytesStart::new
+    /// [`NsReader`]: crate::reader::NsReader
+    /// [`Start`]: crate::events::Event::Start
+    /// [`Empty`]: crate::events::Event::Empty
+    /// [`End`]: crate::events::Event::End
+    /// [`BytesStart::namespace()`]: crate::events::BytesStart::namespace
+    /// [`BytesEnd::namespace()`]: crate::events::BytesEnd::namespace
+    pub attach_namespaces: bool,
+
+    /// Whether text content should be validated. If enabled, in case of a text
+    /// containing the `]]>` sequence [`Error::IllFormed(CDataEndInText)`] is
+    /// returned from read methods.
+    ///
+    /// When set to `true`, every [`Text`] event will be checked for not
+    /// containing `]]>`, which [is not allowed] in text content. Such text is
+    /// not emitted. The check requires an additional scan of each text, so the
+    /// default value is `false` to improve performance.
+    ///
+    /// Default: `false`
+    ///
+    /// [`Error::IllFormed(CDataEndInText)`]: crate::errors::IllFormedError::CDataEndInText
+    /// [`Text`]: crate::events::Event::Text
+    /// [is not allowed]: https://www.w3.org/TR/xml11/#NT-CharData
+    pub check_characters: bool,
+
     /// Whether comments should be validated. If enabled, in case of invalid comment
     /// [`Error::IllFormed(DoubleHyphenInComment)`] is returned from read methods.
     ///
@@ -58,6 +103,28 @@ pub struct Config {
     /// [is not allowed]: https://www.w3.org/TR/xml11/#sec-comments
     pub check_comments: bool,
 
+    /// Whether the position of an XML declaration should be validated. If enabled,
+    /// in case of an XML declaration (`<?xml ...?>`) which is not at the very
+    /// beginning of the document, the [`Error::IllFormed(MisplacedDeclaration)`]
+    /// is returned from read methods.
+    ///
+    /// According to the [specification], the XML declaration may appear only once,
+    /// before any other content, including whitespaces, comments and processing
+    /// instructions. The only thing that is allowed before it is a byte order mark,
+    /// which is returned as a [`Text`] event when [`consume_bom`] is `false`.
+    ///
+    /// When set to `false`, a declaration is returned as a [`Decl`] event wherever
+    /// it appears in the document.
+    ///
+    /// Default: `false`
+    ///
+    /// [`Error::IllFormed(MisplacedDeclaration)`]: crate::errors::IllFormedError::MisplacedDeclaration
+    /// [specification]: https://www.w3.org/TR/xml11/#sec-prolog-dtd
+    /// [`Text`]: crate::events::Event::Text
+    /// [`Decl`]: crate::events::Event::Decl
+    /// [`consume_bom`]: Self::consume_bom
+    pub check_decl_position: bool,
+
     /// Whether mismatched closing tag names should be detected. If enabled, in
     /// case of mismatch the [`Error::IllFormed(MismatchedEndTag)`] is returned from
     /// read methods.
@@ -96,6 +163,22 @@ pub struct Config {
     /// [`expand_empty_elements`]: Self::expand_empty_elements
     pub check_end_names: bool,
 
+    /// Whether a byte order mark (BOM) at the beginning of the input should be
+    /// removed.
+    ///
+    /// When set to `false`, the BOM is kept in the input and is returned as the
+    /// beginning of the first [`Text`] event. If `encoding` feature is enabled,
+    /// the BOM is still used to detect the encoding of the document.
+    ///
+    /// Note, that the reader created by [`Reader::from_str`] also removes the
+    /// UTF-8 BOM (`\u{FEFF}` character) at the beginning of the string unless
+    /// this option is set to `false`.
+    ///
+    /// Default: `true`
+    ///
+    /// [`Text`]: crate::events::Event::Text
+    pub consume_bom: bool,
+
     /// Whether empty elements should be split into an `Open` and a `Close` event.
     ///
     /// When set to `true`, all [`Empty`] events produced by a self-closing tag
@@ -116,6 +199,93 @@ pub struct Config {
     /// [`check_end_names`]: Self::check_end_names
     pub expand_empty_elements: bool,
 
+    /// The maximum length in bytes of a text between markup. If a longer text is
+    /// found, the [`Error::IllFormed(TextTooLong)`] is returned from read methods
+    /// and reading stops. `None` means that the length is not limited.
+    ///
+    /// Use this option when reading untrusted input with a buffered reader,
+    /// because otherwise a huge document without markup would be read into
+    /// the memory entirely. The length is checked before trimming and unescaping
+    /// of a text. The buffered reader can read up to the size of its internal
+    /// buffer more before it detects that the text is too long.
+    ///
+    /// Default: `None`
+    ///
+    /// [`Error::IllFormed(TextTooLong)`]: crate::errors::IllFormedError::TextTooLong
+    pub max_text_len: Option<usize>,
+
+    /// Whether line endings are normalized as required by the [XML specification]:
+    /// each `\r\n` sequence and each `\r` which is not followed by `\n` are
+    /// replaced by `\n` in [`Text`] and [`CData`] events and in attribute values
+    /// of [`Start`] and [`Empty`] events.
+    ///
+    /// When this option is `false`, events borrow data from the input as is.
+    /// When it is `true`, event data is copied only when it contains `\r`.
+    ///
+    /// Default: `false`
+    ///
+    /// [XML specification]: https://www.w3.org/TR/xml11/#sec-line-ends
+    /// [`Text`]: crate::events::Event::Text
+    /// [`CData`]: crate::events::Event::CData
+    /// [`Start`]: crate::events::Event::Start
+    /// [`Empty`]: crate::events::Event::Empty
+    pub normalize_line_endings: bool,
+
+    /// Whether input is required to start with a byte order mark (BOM). If
+    /// enabled and the input does not start with the UTF-8, UTF-16 BE or
+    /// UTF-16 LE BOM, the first read returns [`EncodingError::MissingBom`].
+    ///
+    /// Because a reader created by [`Reader::from_str`] reads already decoded
+    /// string, such input usually has no BOM, so you probably do not want to
+    /// enable this option for such readers.
+    ///
+    /// Default: `false`
+    ///
+    /// [`EncodingError::MissingBom`]: crate::encoding::EncodingError::MissingBom
+    #[cfg(feature = "encoding")]
+    pub require_bom: bool,
+
+    /// Whether reading of a markup interrupted by the end of input can be resumed
+    /// when more data becomes available in the underlying reader.
+    ///
+    /// When this option is `false`, a [`Error::Syntax`] error returned because
+    /// of an incomplete tag, comment, CDATA, processing instruction or DOCTYPE
+    /// is fatal and all following reads return [`Event::Eof`].
+    ///
+    /// When this option is `true` and a buffered reader is used, the reader
+    /// stays inside the markup after such error. Bytes of the incomplete markup
+    /// are kept at the end of the buffer passed to the read method, so you should
+    /// pass the same buffer without clearing it to the next call after more data
+    /// is appended to the underlying reader. That call will continue to read
+    /// the markup from the place where it was interrupted. If the buffer is
+    /// shorter than the already read part of the markup (for example, it was
+    /// cleared), an [`Error::Io`] of kind [`InvalidInput`] is returned and
+    /// reading cannot be continued.
+    ///
+    /// This option has no effect for readers which borrow from `&[u8]`, because
+    /// their input cannot grow.
+    ///
+    /// Default: `false`
+    ///
+    /// [`Event::Eof`]: crate::events::Event::Eof
+    /// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+    pub resume_incomplete_markup: bool,
+
+    /// Whether [`Error::Syntax`] errors should include the beginning of the
+    /// markup in which the error occurred.
+    ///
+    /// When this option is `true`, readers which borrow from `&[u8]` return an
+    /// [`Error::SyntaxWithContext`] error instead of [`Error::Syntax`]. That error
+    /// contains up to 32 bytes of the markup starting from `<`, decoded for display.
+    ///
+    /// This option has no effect for buffered readers, because they do not keep
+    /// the already read input.
+    ///
+    /// Default: `false`
+    ///
+    /// [`Error::SyntaxWithContext`]: crate::errors::Error::SyntaxWithContext
+    pub syntax_error_context: bool,
+
     /// Whether trailing whitespace after the markup name are trimmed in closing
     /// tags `</a >`.
     ///
@@ -173,6 +343,36 @@ pub struct Config {
     /// [`BytesText::inplace_trim_start`]: crate::events::BytesText::inplace_trim_start
     /// [`BytesText::inplace_trim_end`]: crate::events::BytesText::inplace_trim_end
     pub trim_text_end: bool,
+
+    /// Whether [`xml:base`] attributes should be tracked by the [`NsReader`].
+    /// If enabled, the base URI in scope of the current element can be retrieved
+    /// by calling [`NsReader::base_uri()`].
+    ///
+    /// This option has no effect on the [`Reader`].
+    ///
+    /// Note, that setting this to `true` will lead to additional allocations
+    /// for each element with the `xml:base` attribute.
+    ///
+    /// Default: `false`
+    ///
+    /// [`xml:base`]: https://www.w3.org/TR/xmlbase/
+    /// [`NsReader`]: crate::reader::NsReader
+    /// [`NsReader::base_uri()`]: crate::reader::NsReader::base_uri
+    pub track_xml_base: bool,
+
+    /// Whether a byte order mark (BOM) in a text not at the start of a document
+    /// should be reported. If enabled, the [`Warning::MidDocumentBom`] is added
+    /// to the [warnings] of the reader for each such BOM. The text event itself
+    /// is returned unchanged.
+    ///
+    /// Only UTF-8 documents are checked. The check requires an additional scan
+    /// of each text, so the default value is `false` to improve performance.
+    ///
+    /// Default: `false`
+    ///
+    /// [`Warning::MidDocumentBom`]: crate::errors::Warning::MidDocumentBom
+    /// [warnings]: crate::reader::Reader::warnings
+    pub warn_mid_document_bom: bool,
 }
 
 impl Config {
@@ -187,23 +387,97 @@ impl Config {
     /// only to necessary events.
     /// </div>
     ///
+    /// Returns `self`, so calls can be chained:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str("  <tag>  </tag>  ");
+    /// reader.config_mut().trim_text(true).enable_all_checks(true);
+    ///
+    /// assert_eq!(reader.read_event().unwrap(), Event::Start(BytesStart::new("tag")));
+    /// assert!(reader.config().check_end_names);
+    /// ```
+    ///
     /// [`trim_text_start`]: Self::trim_text_start
     /// [`trim_text_end`]: Self::trim_text_end
     /// [`BytesText::inplace_trim_start`]: crate::events::BytesText::inplace_trim_start
     /// [`BytesText::inplace_trim_end`]: crate::events::BytesText::inplace_trim_end
     #[inline]
-    pub fn trim_text(&mut self, trim: bool) {
+    pub fn trim_text(&mut self, trim: bool) -> &mut Self {
         self.trim_text_start = trim;
         self.trim_text_end = trim;
+        self
     }
 
     /// Turn on or off all checks for well-formedness. Currently it is that settings:
+    /// - [`check_characters`](Self::check_characters)
     /// - [`check_comments`](Self::check_comments)
+    /// - [`check_decl_position`](Self::check_decl_position)
     /// - [`check_end_names`](Self::check_end_names)
+    ///
+    /// Returns `self`, so calls can be chained.
     #[inline]
-    pub fn enable_all_checks(&mut self, enable: bool) {
+    pub fn enable_all_checks(&mut self, enable: bool) -> &mut Self {
+        self.check_characters = enable;
         self.check_comments = enable;
+        self.check_decl_position = enable;
         self.check_end_names = enable;
+        self
+    }
+
+    /// Creates a configuration with all well-formedness checks enabled. Use it
+    /// when you want to be sure that the input conforms to the specification.
+    ///
+    /// Differences from the [default] configuration:
+    ///
+    /// |Option                                              |Value
+    /// |----------------------------------------------------|-------
+    /// |[`allow_unmatched_ends`](Self::allow_unmatched_ends)|`false`
+    /// |[`check_characters`](Self::check_characters)        |`true`
+    /// |[`check_comments`](Self::check_comments)            |`true`
+    /// |[`check_decl_position`](Self::check_decl_position)  |`true`
+    /// |[`check_end_names`](Self::check_end_names)          |`true`
+    ///
+    /// All other options have their default values.
+    ///
+    /// [default]: Self::default
+    pub fn strict() -> Self {
+        Self {
+            allow_unmatched_ends: false,
+            check_characters: true,
+            check_comments: true,
+            check_decl_position: true,
+            check_end_names: true,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a configuration with all well-formedness checks disabled. Use it
+    /// when you want to parse as fast as possible or to tolerate malformed input.
+    ///
+    /// Differences from the [default] configuration:
+    ///
+    /// |Option                                              |Value
+    /// |----------------------------------------------------|-------
+    /// |[`allow_unmatched_ends`](Self::allow_unmatched_ends)|`true`
+    /// |[`check_characters`](Self::check_characters)        |`false`
+    /// |[`check_comments`](Self::check_comments)            |`false`
+    /// |[`check_end_names`](Self::check_end_names)          |`false`
+    ///
+    /// All other options have their default values.
+    ///
+    /// [default]: Self::default
+    pub fn lenient() -> Self {
+        Self {
+            allow_unmatched_ends: true,
+            check_characters: false,
+            check_comments: false,
+            check_end_names: false,
+            ..Self::default()
+        }
     }
 }
 
@@ -211,12 +485,24 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             allow_unmatched_ends: false,
+            attach_namespaces: false,
+            check_characters: false,
             check_comments: false,
+            check_decl_position: false,
             check_end_names: true,
+            consume_bom: true,
             expand_empty_elements: false,
+            max_text_len: None,
+            normalize_line_endings: false,
+            #[cfg(feature = "encoding")]
+            require_bom: false,
+            resume_incomplete_markup: false,
+            syntax_error_context: false,
             trim_markup_names_in_closing_tags: true,
             trim_text_start: false,
             trim_text_end: false,
+            track_xml_base: false,
+            warn_mid_document_bom: false,
         }
     }
 }
@@ -230,6 +516,15 @@ macro_rules! read_event_impl {
         $read_until_close:ident
         $(, $await:ident)?
     ) => {{
+        if let Some((event, _)) = $self.peeked.take() {
+            $self.state.markup = None;
+            return Ok(event);
+        }
+        // The End event of the expanded empty element shares the markup with
+        // the Start event
+        if !matches!($self.state.state, ParseState::InsideEmpty) {
+            $self.state.markup = None;
+        }
         let event = loop {
             break match $self.state.state {
                 ParseState::Init => { // Go to InsideMarkup state
@@ -238,15 +533,30 @@ macro_rules! read_event_impl {
                     // But we still need to remove BOM for consistency with no encoding
                     // feature enabled path
                     #[cfg(feature = "encoding")]
-                    if let Some(encoding) = $reader.detect_encoding() $(.$await)? ? {
-                        if $self.state.encoding.can_be_refined() {
-                            $self.state.encoding = crate::reader::EncodingRef::BomDetected(encoding);
+                    {
+                        let consume_bom = $self.state.config.consume_bom;
+                        let bom_len = match $reader.detect_encoding(consume_bom) $(.$await)? ? {
+                            Some((encoding, bom_len)) => {
+                                if $self.state.encoding.can_be_refined() {
+                                    $self.state.encoding = crate::reader::EncodingRef::BomDetected(encoding);
+                                }
+                                bom_len
+                            }
+                            None => 0,
+                        };
+                        if bom_len == 0 && $self.state.config.require_bom {
+                            $self.state.state = ParseState::InsideText;
+                            return Err(crate::errors::Error::Encoding(
+                                crate::encoding::EncodingError::MissingBom,
+                            ));
                         }
                     }
 
                     // Removes UTF-8 BOM if it is present
                     #[cfg(not(feature = "encoding"))]
-                    $reader.remove_utf8_bom() $(.$await)? ?;
+                    if $self.state.config.consume_bom {
+                        $reader.remove_utf8_bom() $(.$await)? ?;
+                    }
 
                     $self.state.state = ParseState::InsideText;
                     continue;
@@ -256,7 +566,9 @@ macro_rules! read_event_impl {
                         $reader.skip_whitespace(&mut $self.state.offset) $(.$await)? ?;
                     }
 
-                    match $reader.read_text($buf, &mut $self.state.offset) $(.$await)? {
+                    let start = $self.state.offset;
+                    let max_len = $self.state.config.max_text_len.unwrap_or(usize::MAX);
+                    match $reader.read_text($buf, max_len, &mut $self.state.offset) $(.$await)? {
                         ReadTextResult::Markup(buf) => {
                             $self.state.state = ParseState::InsideMarkup;
                             // Pass `buf` to the next next iteration of parsing loop
@@ -265,6 +577,7 @@ macro_rules! read_event_impl {
                         }
                         ReadTextResult::UpToMarkup(bytes) => {
                             $self.state.state = ParseState::InsideMarkup;
+                            $self.state.check_text(bytes, start)?;
                             // FIXME: Can produce an empty event if:
                             // - event contains only spaces
                             // - trim_text_start = false
@@ -273,6 +586,7 @@ macro_rules! read_event_impl {
                         }
                         ReadTextResult::UpToEof(bytes) => {
                             $self.state.state = ParseState::Done;
+                            $self.state.check_text(bytes, start)?;
                             // Trim bytes from end if required
                             let event = $self.state.emit_text(bytes);
                             if event.is_empty() {
@@ -281,6 +595,13 @@ macro_rules! read_event_impl {
                                 Ok(Event::Text(event))
                             }
                         }
+                        ReadTextResult::TooLong => {
+                            // Reading cannot be continued, because we do not
+                            // know where the rest of the text ends
+                            $self.state.state = ParseState::Done;
+                            $self.state.last_error_offset = start;
+                            Err(Error::IllFormed(crate::errors::IllFormedError::TextTooLong(max_len)))
+                        }
                         ReadTextResult::Err(e) => Err(Error::Io(e.into())),
                     }
                 },
@@ -290,10 +611,22 @@ macro_rules! read_event_impl {
                 ParseState::Done => Ok(Event::Eof),
             };
         };
+        match &event {
+            // The BOM, that was not consumed, is the only content allowed before
+            // an XML declaration
+            Ok(Event::Text(e)) if e.as_ref() == crate::encoding::UTF8_BOM => {
+                $self.state.decl_position = Some(crate::encoding::UTF8_BOM.len() as u64);
+            }
+            // Reading of incomplete markup will be resumed, so nothing was read yet
+            Err(Error::Syntax(_)) if matches!($self.state.state, ParseState::InsideMarkup) => {}
+            _ => $self.state.decl_position = None,
+        }
         match event {
             // #513: In case of ill-formed errors we already consume the wrong data
             // and change the state. We can continue parsing if we wish
             Err(Error::IllFormed(_)) => {}
+            // Reading of incomplete markup can be resumed when more data is available
+            Err(Error::Syntax(_)) if matches!($self.state.state, ParseState::InsideMarkup) => {}
             Err(_) | Ok(Event::Eof) => $self.state.state = ParseState::Done,
             _ => {}
         }
@@ -329,11 +662,19 @@ macro_rules! read_until_close {
     ) => {{
         $self.state.state = ParseState::InsideText;
 
-        let start = $self.state.offset;
-        match $reader.peek_one() $(.$await)? {
+        // If previous reading of markup was interrupted by the end of input,
+        // `read` bytes of it was already consumed from the reader
+        let (peeked, read) = match $self.state.incomplete.take() {
+            Some((byte, read)) => (Ok(Some(byte)), read),
+            None => ($reader.peek_one() $(.$await)?, 0),
+        };
+        let byte = peeked.as_ref().ok().copied().flatten();
+        // Position after the `<`
+        let start = $self.state.offset - read as u64;
+        let event = match peeked {
             // `<!` - comment, CDATA or DOCTYPE declaration
             Ok(Some(b'!')) => match $reader
-                .read_bang_element($buf, &mut $self.state.offset)
+                .read_bang_element($buf, read, &mut $self.state.offset)
                 $(.$await)?
             {
                 Ok((bang_type, bytes)) => $self.state.emit_bang(bang_type, bytes),
@@ -355,7 +696,7 @@ macro_rules! read_until_close {
             //   does. This is malformed XML, however it is tolerated by some parsers
             //   (e.g. the one used by Adobe Flash) and such documents do exist in the wild.
             Ok(Some(b'/')) => match $reader
-                .read_with(ElementParser::Outside, $buf, &mut $self.state.offset)
+                .read_with(ElementParser::Outside, $buf, read, &mut $self.state.offset)
                 $(.$await)?
             {
                 Ok(bytes) => $self.state.emit_end(bytes),
@@ -368,7 +709,7 @@ macro_rules! read_until_close {
             },
             // `<?` - processing instruction
             Ok(Some(b'?')) => match $reader
-                .read_with(PiParser(false), $buf, &mut $self.state.offset)
+                .read_with(PiParser(false), $buf, read, &mut $self.state.offset)
                 $(.$await)?
             {
                 Ok(bytes) => $self.state.emit_question_mark(bytes),
@@ -381,7 +722,7 @@ macro_rules! read_until_close {
             },
             // `<...` - opening or self-closed tag
             Ok(Some(_)) => match $reader
-                .read_with(ElementParser::Outside, $buf, &mut $self.state.offset)
+                .read_with(ElementParser::Outside, $buf, read, &mut $self.state.offset)
                 $(.$await)?
             {
                 Ok(bytes) => Ok($self.state.emit_start(bytes)),
@@ -400,6 +741,32 @@ macro_rules! read_until_close {
                 Err(Error::Syntax(SyntaxError::UnclosedTag))
             }
             Err(e) => Err(Error::Io(e.into())),
+        };
+        if event.is_ok() {
+            $self.state.markup = Some(start - 1..$self.state.offset);
+        }
+        match event {
+            Err(Error::Syntax(e))
+                if $self.state.config.resume_incomplete_markup && $reader.can_resume() =>
+            {
+                if e == SyntaxError::InvalidBangMarkup {
+                    match $reader.peek_one() $(.$await)? {
+                        // Unknown symbol after `<!`, more data will not help
+                        Ok(Some(_)) => return Err(Error::Syntax(e)),
+                        // `!` is already consumed, but not counted
+                        _ if $self.state.offset == start => $self.state.offset += 1,
+                        _ => {}
+                    }
+                }
+                if let Some(byte) = byte {
+                    let read = ($self.state.offset - start) as usize;
+                    $self.state.incomplete = Some((byte, read));
+                }
+                // Stay inside markup, so the next read will continue it
+                $self.state.state = ParseState::InsideMarkup;
+                Err(Error::Syntax(e))
+            }
+            event => event,
         }
     }};
 }
@@ -430,11 +797,11 @@ macro_rules! read_to_end {
         let trim = config.trim_text_start;
         config.trim_text_start = false;
 
-        let start = $self.buffer_position();
+        let start = $self.next_event_position();
         let mut depth = 0;
         loop {
             $clear
-            let end = $self.buffer_position();
+            let end = $self.next_event_position();
             match $self.$read_event($buf) $(.$await)? {
                 Err(e) => {
                     $self.config_mut().trim_text_start = trim;
@@ -462,11 +829,13 @@ macro_rules! read_to_end {
 #[cfg(feature = "async-tokio")]
 mod async_tokio;
 mod buffered_reader;
+mod handler;
 mod ns_reader;
 mod slice_reader;
 mod state;
 
-pub use ns_reader::NsReader;
+pub use handler::{parse_with, EventHandler};
+pub use ns_reader::{DecodedEvent, DecodedName, NsReader};
 
 /// Range of input in bytes, that corresponds to some piece of XML
 pub type Span = Range<u64>;
@@ -528,6 +897,9 @@ enum ParseState {
 ///   Implicit    -- BOM            --> BomDetected
 ///   Implicit    -- "encoding=..." --> XmlDetected
 ///   BomDetected -- "encoding=..." --> XmlDetected
+///   Implicit    -- set_encoding   --> Explicit
+///   BomDetected -- set_encoding   --> Explicit
+///   XmlDetected -- set_encoding   --> Explicit
 /// ```
 #[cfg(feature = "encoding")]
 #[derive(Clone, Copy, Debug)]
@@ -689,6 +1061,15 @@ pub struct Reader<R> {
     reader: R,
     /// Configuration and current parse state
     state: ReaderState,
+    /// Event that was read by [`Self::peek_event_into`] and that should be
+    /// returned by the next `read_event_*` call, and the position before that event
+    peeked: Option<(Event<'static>, u64)>,
+    /// Buffer owned by the reader that is used by [`Self::read_event_owned`]
+    buf: Vec<u8>,
+    /// Used only by the borrowing slice reader (including the slice [`NsReader`]).
+    /// The input as it was when the reader started to read it, so
+    /// [`Reader::last_event_raw`] can slice it at the positions stored in the state
+    input: Option<R>,
 }
 
 /// Builder methods
@@ -698,9 +1079,37 @@ impl<R> Reader<R> {
         Self {
             reader,
             state: ReaderState::default(),
+            peeked: None,
+            buf: Vec::new(),
+            input: None,
         }
     }
 
+    /// Creates a `Reader` that reads from a given reader and uses the specified
+    /// configuration.
+    ///
+    /// This is useful when you need to parse many documents with the same
+    /// settings: configuration can be prepared once and cloned for each reader.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::reader::{Config, Reader};
+    ///
+    /// let mut config = Config::default();
+    /// config.trim_text(true);
+    ///
+    /// let mut reader = Reader::with_config("  <tag/>".as_bytes(), config);
+    /// assert_eq!(reader.read_event().unwrap(), Event::Empty(BytesStart::new("tag")));
+    /// ```
+    pub fn with_config(reader: R, config: Config) -> Self {
+        let mut reader = Self::from_reader(reader);
+        reader.state.config = config;
+        reader
+    }
+
     /// Returns reference to the parser configuration
     pub const fn config(&self) -> &Config {
         &self.state.config
@@ -772,6 +1181,37 @@ impl<R> Reader<R> {
         self.reader
     }
 
+    /// Converts this reader into a [`NsReader`] that continues reading from the
+    /// current position and performs namespace resolution.
+    ///
+    /// The state of the parser, including the bytes that were already consumed
+    /// from the underlying reader, is preserved. Because namespace declarations
+    /// of the elements that were opened before the conversion were not tracked,
+    /// they are unknown to the returned reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::name::{Namespace, ResolveResult};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str(r#"<root><ns:tag xmlns:ns="urn:ns"/></root>"#);
+    /// assert_eq!(reader.read_event().unwrap(), Event::Start(BytesStart::new("root")));
+    ///
+    /// let mut reader = reader.into_ns_reader();
+    /// let (ns, _) = reader.read_resolved_event().unwrap();
+    /// assert_eq!(ns, ResolveResult::Bound(Namespace(b"urn:ns")));
+    ///
+    /// let mut reader = reader.into_reader();
+    /// assert_eq!(reader.buffer_position(), 33);
+    /// ```
+    #[inline]
+    pub fn into_ns_reader(self) -> NsReader<R> {
+        NsReader::new(self)
+    }
+
     /// Gets a reference to the underlying reader.
     pub const fn get_ref(&self) -> &R {
         &self.reader
@@ -789,16 +1229,37 @@ impl<R> Reader<R> {
     }
 
     /// Gets the current byte position in the input data.
+    ///
+    /// When [`expand_empty_elements`] is set, both [`Start`] and [`End`] events
+    /// produced from a self-closed tag report the position just after the `/>`:
+    /// the whole tag is consumed when `Start` is returned and the synthesized
+    /// `End` does not consume any input. Thus the position never decreases.
+    ///
+    /// [`expand_empty_elements`]: Config::expand_empty_elements
+    /// [`Start`]: Event::Start
+    /// [`End`]: Event::End
     pub const fn buffer_position(&self) -> u64 {
         // when internal state is InsideMarkup, we have actually read until '<',
-        // which we don't want to show
-        if let ParseState::InsideMarkup = self.state.state {
+        // which we don't want to show. The exception is the incomplete markup,
+        // which bytes are already consumed from the reader
+        if let (ParseState::InsideMarkup, None) = (&self.state.state, &self.state.incomplete) {
             self.state.offset - 1
         } else {
             self.state.offset
         }
     }
 
+    /// Gets the position before the event that will be returned by the next
+    /// `read_event_*` call. Unlike [`Self::buffer_position`] it points before
+    /// the event peeked by [`Self::peek_event_into`].
+    #[inline]
+    fn next_event_position(&self) -> u64 {
+        match &self.peeked {
+            Some((_, position)) => *position,
+            None => self.buffer_position(),
+        }
+    }
+
     /// Gets the last error byte position in the input data. If there is no errors
     /// yet, returns `0`.
     ///
@@ -814,6 +1275,41 @@ impl<R> Reader<R> {
         self.state.last_error_offset
     }
 
+    /// Returns warnings collected by the reader so far. Warnings report non-fatal
+    /// oddities in the document and should be enabled in the [configuration].
+    ///
+    /// Warnings are accumulated until they are taken by [`take_warnings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::errors::Warning;
+    /// use quick_xml::events::Event;
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str("<tag>a\u{FEFF}b</tag>");
+    /// reader.config_mut().warn_mid_document_bom = true;
+    ///
+    /// while reader.read_event().unwrap() != Event::Eof {}
+    ///
+    /// assert_eq!(reader.warnings(), [Warning::MidDocumentBom(6)]);
+    /// ```
+    ///
+    /// [configuration]: Config
+    /// [`take_warnings()`]: Self::take_warnings
+    pub fn warnings(&self) -> &[Warning] {
+        &self.state.warnings
+    }
+
+    /// Returns warnings collected by the reader so far and clears the list
+    /// of warnings. See [`warnings()`] for details.
+    ///
+    /// [`warnings()`]: Self::warnings
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.state.warnings)
+    }
+
     /// Get the decoder, used to decode bytes, read by this reader, to the strings.
     ///
     /// If [`encoding`] feature is enabled, the used encoding may change after
@@ -828,6 +1324,36 @@ impl<R> Reader<R> {
         self.state.decoder()
     }
 
+    /// Forces the reader to use the specified encoding regardless of the encoding
+    /// detected from the BOM or the XML declaration. Neither BOM nor XML declaration
+    /// read after this call can change the encoding.
+    ///
+    /// This is useful to recover from mislabeled documents, when the encoding
+    /// declared by the document does not match the actual encoding of its content.
+    ///
+    /// Note, that the reader does not re-decode anything, so calling this method
+    /// in the middle of parsing can lead to incorrect decoding of the data that
+    /// was already read into the internal buffers. The best place to call it
+    /// is right after creating the reader.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use encoding_rs::WINDOWS_1251;
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_reader(b"<?xml encoding='UTF-8'?>".as_ref());
+    /// reader.set_encoding(WINDOWS_1251);
+    ///
+    /// reader.read_event_into(&mut Vec::new()).unwrap();
+    /// assert_eq!(reader.decoder().encoding(), WINDOWS_1251);
+    /// ```
+    #[cfg(feature = "encoding")]
+    pub fn set_encoding(&mut self, encoding: &'static Encoding) {
+        self.state.encoding = EncodingRef::Explicit(encoding);
+    }
+
     /// Get the direct access to the underlying reader, but tracks the amount of
     /// read data and update [`Reader::buffer_position()`] accordingly.
     ///
@@ -898,7 +1424,42 @@ impl<R> Reader<R> {
     where
         R: XmlSource<'i, B>,
     {
-        read_event_impl!(self, buf, self.reader, read_until_close)
+        if self.input.is_none() {
+            self.input = self.reader.snapshot();
+        }
+        match read_event_impl!(self, buf, self.reader, read_until_close) {
+            Err(Error::Syntax(e)) if self.state.config.syntax_error_context => {
+                Err(self.syntax_error_with_context(e))
+            }
+            event => event,
+        }
+    }
+
+    /// Creates an [`Error::SyntaxWithContext`] with the beginning of the markup
+    /// in which the error occurred, if the reader still has access to it.
+    /// Otherwise returns an ordinary [`Error::Syntax`].
+    fn syntax_error_with_context<'i, B>(&self, error: SyntaxError) -> Error
+    where
+        R: XmlSource<'i, B>,
+    {
+        /// Maximum length of the context in bytes, including `<`
+        const MAX_LEN: usize = 32;
+
+        let markup = match self.reader.remaining() {
+            Some(markup) => markup,
+            None => return Error::Syntax(error),
+        };
+        let mut len = markup.len().min(MAX_LEN - 1);
+        // Do not split multi-byte UTF-8 characters
+        while len < markup.len() && len > 0 && markup[len] & 0b1100_0000 == 0b1000_0000 {
+            len -= 1;
+        }
+        let markup = &markup[..len];
+        let markup = match self.decoder().decode(markup) {
+            Ok(markup) => markup,
+            Err(_) => String::from_utf8_lossy(markup),
+        };
+        Error::SyntaxWithContext(error, format!("<{}", markup))
     }
 
     /// Private function to read until `>` is found. This function expects that
@@ -913,6 +1474,91 @@ impl<R> Reader<R> {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Scans the whole document and collects values of all `xml:id` attributes
+/// together with spans of the elements where they are defined.
+///
+/// The span of an element starts at the `<` of its start tag and ends after the
+/// `>` of its end tag (or of the tag itself for self-closed elements), so it can
+/// be used later to get the element markup directly from the input. Values of
+/// attributes are unescaped. If the same id is defined several times, only
+/// the first definition is kept.
+///
+/// The reader is consumed until the [`Event::Eof`]. The configuration of the
+/// reader is respected, except the [`Config::expand_empty_elements`] and
+/// [`Config::trim_text_start`] that are temporary disabled while scanning.
+///
+/// Note that attributes declared with the `ID` type in a DTD are not recognized,
+/// because DTD is not parsed by quick-xml.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use quick_xml::reader::{index_ids, Reader};
+///
+/// let xml = r#"<doc><p xml:id="intro">Hello</p><img xml:id="logo"/></doc>"#;
+/// let mut reader = Reader::from_str(xml);
+///
+/// let ids = index_ids(&mut reader).unwrap();
+///
+/// assert_eq!(ids.len(), 2);
+/// assert_eq!(&xml[ids["intro"].start as usize..ids["intro"].end as usize], r#"<p xml:id="intro">Hello</p>"#);
+/// assert_eq!(&xml[ids["logo"].start as usize..ids["logo"].end as usize], r#"<img xml:id="logo"/>"#);
+/// ```
+pub fn index_ids<R: BufRead>(reader: &mut Reader<R>) -> Result<HashMap<String, Span>, Error> {
+    let config = reader.config().clone();
+    reader.config_mut().expand_empty_elements = false;
+    // Whitespaces before markup should not be skipped, so each markup starts
+    // right at the position where the previous event ended
+    reader.config_mut().trim_text_start = false;
+    let result = index_ids_impl(reader);
+    *reader.config_mut() = config;
+    result
+}
+
+fn index_ids_impl<R: BufRead>(reader: &mut Reader<R>) -> Result<HashMap<String, Span>, Error> {
+    let mut ids = HashMap::new();
+    // Start positions of opened elements with `xml:id` attribute
+    let mut opened: Vec<Option<(String, u64)>> = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        // Position of `<` of the next markup. The content of events cannot be
+        // used to calculate it, because it can be changed, for example, by
+        // the normalization of line endings
+        let start = reader.buffer_position();
+        let event = reader.read_event_into(&mut buf)?;
+        let end = reader.buffer_position();
+        match event {
+            Event::Start(e) => {
+                let id = match e.try_get_attribute("xml:id")? {
+                    Some(a) => Some((
+                        a.decode_and_unescape_value(reader.decoder())?.into_owned(),
+                        start,
+                    )),
+                    None => None,
+                };
+                opened.push(id);
+            }
+            Event::Empty(e) => {
+                if let Some(a) = e.try_get_attribute("xml:id")? {
+                    let id = a.decode_and_unescape_value(reader.decoder())?;
+                    ids.entry(id.into_owned()).or_insert(start..end);
+                }
+            }
+            Event::End(_) => {
+                if let Some(Some((id, start))) = opened.pop() {
+                    ids.entry(id).or_insert(start..end);
+                }
+            }
+            Event::Eof => return Ok(ids),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
 /// Result of an attempt to read XML textual data from the reader.
 enum ReadTextResult<'r, B> {
     /// Start of markup (`<` character) was found in the first byte.
@@ -923,6 +1569,8 @@ enum ReadTextResult<'r, B> {
     UpToMarkup(&'r [u8]),
     /// Contains text block up to EOF, start of markup (`<` character) was not found.
     UpToEof(&'r [u8]),
+    /// Text block is longer than allowed.
+    TooLong,
     /// IO error occurred.
     Err(io::Error),
 }
@@ -947,18 +1595,25 @@ trait XmlSource<'r, B> {
     fn remove_utf8_bom(&mut self) -> io::Result<()>;
 
     /// Determines encoding from the start of input and removes BOM if it is present
+    /// and `consume_bom` is `true`. Returns the detected encoding and the length
+    /// of the BOM (which is `0` if encoding was detected without BOM)
     #[cfg(feature = "encoding")]
-    fn detect_encoding(&mut self) -> io::Result<Option<&'static Encoding>>;
+    fn detect_encoding(
+        &mut self,
+        consume_bom: bool,
+    ) -> io::Result<Option<(&'static Encoding, usize)>>;
 
     /// Read input until start of markup (the `<`) is found or end of input is reached.
     ///
     /// # Parameters
     /// - `buf`: Buffer that could be filled from an input (`Self`) and
     ///   from which [events] could borrow their data
+    /// - `max_len`: The maximum length of text. If text is longer,
+    ///   [`ReadTextResult::TooLong`] is returned
     /// - `position`: Will be increased by amount of bytes consumed
     ///
     /// [events]: crate::events::Event
-    fn read_text(&mut self, buf: B, position: &mut u64) -> ReadTextResult<'r, B>;
+    fn read_text(&mut self, buf: B, max_len: usize, position: &mut u64) -> ReadTextResult<'r, B>;
 
     /// Read input until processing instruction is finished.
     ///
@@ -973,13 +1628,23 @@ trait XmlSource<'r, B> {
     /// # Parameters
     /// - `buf`: Buffer that could be filled from an input (`Self`) and
     ///   from which [events] could borrow their data
+    /// - `read`: Number of bytes of the thing that was already consumed and stored
+    ///   at the end of `buf` by the previous call, which was interrupted by the end
+    ///   of input. Always `0` if [`can_resume()`] returns `false`
     /// - `position`: Will be increased by amount of bytes consumed
     ///
     /// A `P` type parameter is used to preserve state between calls to the underlying
     /// reader which provides bytes fed into the parser.
     ///
     /// [events]: crate::events::Event
-    fn read_with<P>(&mut self, parser: P, buf: B, position: &mut u64) -> Result<&'r [u8], Error>
+    /// [`can_resume()`]: Self::can_resume
+    fn read_with<P>(
+        &mut self,
+        parser: P,
+        buf: B,
+        read: usize,
+        position: &mut u64,
+    ) -> Result<&'r [u8], Error>
     where
         P: Parser;
 
@@ -995,12 +1660,17 @@ trait XmlSource<'r, B> {
     /// # Parameters
     /// - `buf`: Buffer that could be filled from an input (`Self`) and
     ///   from which [events] could borrow their data
+    /// - `read`: Number of bytes of the element (including `!`) that was already
+    ///   consumed and stored at the end of `buf` by the previous call, which was
+    ///   interrupted by the end of input. Always `0` if [`can_resume()`] returns `false`
     /// - `position`: Will be increased by amount of bytes consumed
     ///
     /// [events]: crate::events::Event
+    /// [`can_resume()`]: Self::can_resume
     fn read_bang_element(
         &mut self,
         buf: B,
+        read: usize,
         position: &mut u64,
     ) -> Result<(BangType, &'r [u8]), Error>;
 
@@ -1014,6 +1684,70 @@ trait XmlSource<'r, B> {
     /// Return one character without consuming it, so that future `read_*` calls
     /// will still include it. On EOF, return `None`.
     fn peek_one(&mut self) -> io::Result<Option<u8>>;
+
+    /// Returns `true` if more data can become available after the end of input
+    /// was reached, so reading of an incomplete markup can be resumed later.
+    fn can_resume(&self) -> bool;
+
+    /// Returns the input which is not consumed yet, if it is available without
+    /// reading. After a syntax error that is the markup after the `<` in which
+    /// the error occurred.
+    fn remaining(&self) -> Option<&[u8]>;
+
+    /// Returns a copy of the not consumed input, if events borrow from the input
+    /// itself, so the raw bytes of the read markup can be sliced from that copy.
+    /// See [`Reader::last_event_raw`]
+    fn snapshot(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Kind of markup started with `<!`.
+///
+/// This classification is useful for tools that scan raw bytes of a document
+/// and want to know what kind of markup begins at `<!` before the full event
+/// is parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MarkupKind {
+    /// `<!--...-->`
+    Comment,
+    /// `<![CDATA[...]]>`
+    CData,
+    /// `<!DOCTYPE...>`
+    DocType,
+}
+
+impl MarkupKind {
+    /// Classifies markup by the byte that follows `<!`. Returns `None` if that
+    /// byte cannot start any markup (in which case the reader reports
+    /// [`SyntaxError::InvalidBangMarkup`]).
+    ///
+    /// Only the first byte is examined, so the markup still can be invalid,
+    /// for example, `<!-x>` is classified as a comment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::reader::MarkupKind;
+    ///
+    /// let markup = b"<!DOCTYPE html>";
+    /// assert_eq!(MarkupKind::from_bang_byte(markup[2]), Some(MarkupKind::DocType));
+    /// assert_eq!(MarkupKind::from_bang_byte(b'-'), Some(MarkupKind::Comment));
+    /// assert_eq!(MarkupKind::from_bang_byte(b'['), Some(MarkupKind::CData));
+    /// assert_eq!(MarkupKind::from_bang_byte(b'x'), None);
+    /// ```
+    pub const fn from_bang_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'[' => Some(Self::CData),
+            b'-' => Some(Self::Comment),
+            b'D' | b'd' => Some(Self::DocType),
+            _ => None,
+        }
+    }
 }
 
 /// Possible elements started with `<!`
@@ -1029,11 +1763,15 @@ enum BangType {
 impl BangType {
     #[inline(always)]
     const fn new(byte: Option<u8>) -> Result<Self, SyntaxError> {
-        Ok(match byte {
-            Some(b'[') => Self::CData,
-            Some(b'-') => Self::Comment,
-            Some(b'D') | Some(b'd') => Self::DocType(0),
-            _ => return Err(SyntaxError::InvalidBangMarkup),
+        let kind = match byte {
+            Some(byte) => MarkupKind::from_bang_byte(byte),
+            None => None,
+        };
+        Ok(match kind {
+            Some(MarkupKind::CData) => Self::CData,
+            Some(MarkupKind::Comment) => Self::Comment,
+            Some(MarkupKind::DocType) => Self::DocType(0),
+            None => return Err(SyntaxError::InvalidBangMarkup),
         })
     }
 
@@ -1151,7 +1889,7 @@ mod test {
                         let mut input = b"![]]>other content".as_ref();
                         //                ^= 1
 
-                        match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                        match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                             Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedCData),
                             x => panic!(
                                 "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1170,7 +1908,7 @@ mod test {
                         let mut input = b"![CDATA[other content".as_ref();
                         //                ^= 1                 ^= 22
 
-                        match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                        match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                             Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedCData),
                             x => panic!(
                                 "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1189,7 +1927,7 @@ mod test {
                         //                ^= 1       ^= 12
 
                         let (ty, bytes) = $source(&mut input)
-                            .read_bang_element(buf, &mut position)
+                            .read_bang_element(buf, 0, &mut position)
                             $(.$await)?
                             .unwrap();
                         assert_eq!(
@@ -1210,7 +1948,7 @@ mod test {
                         //                ^= 1                        ^= 29
 
                         let (ty, bytes) = $source(&mut input)
-                            .read_bang_element(buf, &mut position)
+                            .read_bang_element(buf, 0, &mut position)
                             $(.$await)?
                             .unwrap();
                         assert_eq!(
@@ -1249,7 +1987,7 @@ mod test {
                         let mut input = b"!- -->other content".as_ref();
                         //                ^= 1
 
-                        match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                        match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                             Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedComment),
                             x => panic!(
                                 "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1266,7 +2004,7 @@ mod test {
                         let mut input = b"!->other content".as_ref();
                         //                ^= 1            ^= 17
 
-                        match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                        match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                             Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedComment),
                             x => panic!(
                                 "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1283,7 +2021,7 @@ mod test {
                         let mut input = b"!--other content".as_ref();
                         //                ^= 1            ^= 17
 
-                        match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                        match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                             Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedComment),
                             x => panic!(
                                 "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1300,7 +2038,7 @@ mod test {
                         let mut input = b"!-->other content".as_ref();
                         //                ^= 1             ^= 18
 
-                        match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                        match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                             Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedComment),
                             x => panic!(
                                 "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1317,7 +2055,7 @@ mod test {
                         let mut input = b"!--->other content".as_ref();
                         //                ^= 1              ^= 19
 
-                        match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                        match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                             Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedComment),
                             x => panic!(
                                 "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1335,7 +2073,7 @@ mod test {
                         //                ^= 1  ^= 7
 
                         let (ty, bytes) = $source(&mut input)
-                            .read_bang_element(buf, &mut position)
+                            .read_bang_element(buf, 0, &mut position)
                             $(.$await)?
                             .unwrap();
                         assert_eq!(
@@ -1353,7 +2091,7 @@ mod test {
                         //                ^= 1             ^= 18
 
                         let (ty, bytes) = $source(&mut input)
-                            .read_bang_element(buf, &mut position)
+                            .read_bang_element(buf, 0, &mut position)
                             $(.$await)?
                             .unwrap();
                         assert_eq!(
@@ -1379,7 +2117,7 @@ mod test {
                             let mut input = b"!D other content".as_ref();
                             //                ^= 1            ^= 17
 
-                            match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                            match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                                 Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedDoctype),
                                 x => panic!(
                                     "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1396,7 +2134,7 @@ mod test {
                             let mut input = b"!DOCTYPEother content".as_ref();
                             //                ^= 1                 ^= 22
 
-                            match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                            match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                                 Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedDoctype),
                                 x => panic!(
                                     "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1414,7 +2152,7 @@ mod test {
                             //                ^= 1     ^= 10
 
                             let (ty, bytes) = $source(&mut input)
-                                .read_bang_element(buf, &mut position)
+                                .read_bang_element(buf, 0, &mut position)
                                 $(.$await)?
                                 .unwrap();
                             assert_eq!(
@@ -1431,7 +2169,7 @@ mod test {
                             let mut input = b"!DOCTYPE other content".as_ref();
                             //                ^= 1                  ^23
 
-                            match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                            match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                                 Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedDoctype),
                                 x => panic!(
                                     "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1453,7 +2191,7 @@ mod test {
                             let mut input = b"!d other content".as_ref();
                             //                ^= 1            ^= 17
 
-                            match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                            match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                                 Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedDoctype),
                                 x => panic!(
                                     "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1470,7 +2208,7 @@ mod test {
                             let mut input = b"!doctypeother content".as_ref();
                             //                ^= 1                 ^= 22
 
-                            match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                            match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                                 Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedDoctype),
                                 x => panic!(
                                     "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1488,7 +2226,7 @@ mod test {
                             //                ^= 1     ^= 10
 
                             let (ty, bytes) = $source(&mut input)
-                                .read_bang_element(buf, &mut position)
+                                .read_bang_element(buf, 0, &mut position)
                                 $(.$await)?
                                 .unwrap();
                             assert_eq!(
@@ -1505,7 +2243,7 @@ mod test {
                             let mut input = b"!doctype other content".as_ref();
                             //                ^= 1                  ^= 23
 
-                            match $source(&mut input).read_bang_element(buf, &mut position) $(.$await)? {
+                            match $source(&mut input).read_bang_element(buf, 0, &mut position) $(.$await)? {
                                 Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedDoctype),
                                 x => panic!(
                                     "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1533,7 +2271,7 @@ mod test {
                     let mut input = b"".as_ref();
                     //                ^= 1
 
-                    match $source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? {
+                    match $source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? {
                         Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
                         x => panic!(
                             "Expected `Err(Syntax(_))`, but got `{:?}`",
@@ -1555,7 +2293,7 @@ mod test {
                         //                 ^= 2
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"")
                         );
                         assert_eq!(position, 2);
@@ -1569,7 +2307,7 @@ mod test {
                         //                    ^= 5
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"tag")
                         );
                         assert_eq!(position, 5);
@@ -1583,7 +2321,7 @@ mod test {
                         //                  ^= 3
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b":")
                         );
                         assert_eq!(position, 3);
@@ -1597,7 +2335,7 @@ mod test {
                         //                     ^= 6
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b":tag")
                         );
                         assert_eq!(position, 6);
@@ -1611,7 +2349,7 @@ mod test {
                         //                                                        ^= 39
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(br#"tag  attr-1=">"  attr2  =  '>'  3attr"#)
                         );
                         assert_eq!(position, 39);
@@ -1630,7 +2368,7 @@ mod test {
                         //                  ^= 3
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"/")
                         );
                         assert_eq!(position, 3);
@@ -1644,7 +2382,7 @@ mod test {
                         //                     ^= 6
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"tag/")
                         );
                         assert_eq!(position, 6);
@@ -1658,7 +2396,7 @@ mod test {
                         //                   ^= 4
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b":/")
                         );
                         assert_eq!(position, 4);
@@ -1672,7 +2410,7 @@ mod test {
                         //                      ^= 7
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b":tag/")
                         );
                         assert_eq!(position, 7);
@@ -1686,7 +2424,7 @@ mod test {
                         //                                                           ^= 42
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(br#"tag  attr-1="/>"  attr2  =  '/>'  3attr/"#)
                         );
                         assert_eq!(position, 42);
@@ -1705,7 +2443,7 @@ mod test {
                         //                   ^= 4
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"/ ")
                         );
                         assert_eq!(position, 4);
@@ -1719,7 +2457,7 @@ mod test {
                         //                     ^= 6
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"/tag")
                         );
                         assert_eq!(position, 6);
@@ -1733,7 +2471,7 @@ mod test {
                         //                   ^= 4
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"/:")
                         );
                         assert_eq!(position, 4);
@@ -1747,7 +2485,7 @@ mod test {
                         //                      ^= 7
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(b"/:tag")
                         );
                         assert_eq!(position, 7);
@@ -1761,7 +2499,7 @@ mod test {
                         //                                                         ^= 40
 
                         assert_eq!(
-                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, &mut position) $(.$await)? .unwrap()),
+                            Bytes($source(&mut input).read_with(ElementParser::default(), buf, 0, &mut position) $(.$await)? .unwrap()),
                             Bytes(br#"/tag  attr-1=">"  attr2  =  '>'  3attr"#)
                         );
                         assert_eq!(position, 40);
@@ -1771,6 +2509,7 @@ mod test {
 
             /// Ensures, that no empty `Text` events are generated
             mod $read_event {
+                use crate::errors::{Error, IllFormedError};
                 use crate::events::{BytesCData, BytesDecl, BytesEnd, BytesPI, BytesStart, BytesText, Event};
                 use crate::reader::Reader;
                 use pretty_assertions::assert_eq;
@@ -1910,6 +2649,51 @@ mod test {
                         Event::Eof
                     );
                 }
+
+                /// After the end of input the reader stays in the terminal state
+                #[$test]
+                $($async)? fn eof_repeated() {
+                    let mut reader = Reader::from_str("<tag/>");
+
+                    assert_eq!(
+                        reader.$read_event($buf) $(.$await)? .unwrap(),
+                        Event::Empty(BytesStart::new("tag"))
+                    );
+                    for _ in 0..3 {
+                        assert_eq!(
+                            reader.$read_event($buf) $(.$await)? .unwrap(),
+                            Event::Eof
+                        );
+                    }
+                }
+
+                /// Ill-formed errors are recoverable, so the reader continues
+                /// reading after them and reaches the end of input
+                #[$test]
+                $($async)? fn eof_after_ill_formed() {
+                    let mut reader = Reader::from_str("<tag></other>");
+
+                    assert_eq!(
+                        reader.$read_event($buf) $(.$await)? .unwrap(),
+                        Event::Start(BytesStart::new("tag"))
+                    );
+                    match reader.$read_event($buf) $(.$await)? {
+                        Err(Error::IllFormed(IllFormedError::MismatchedEndTag { expected, found, .. })) => {
+                            assert_eq!(expected, "tag");
+                            assert_eq!(found, "other");
+                        }
+                        x => panic!(
+                            "Expected `Err(IllFormed(MismatchedEndTag {{ .. }}))`, but got `{:?}`",
+                            x
+                        ),
+                    }
+                    for _ in 0..3 {
+                        assert_eq!(
+                            reader.$read_event($buf) $(.$await)? .unwrap(),
+                            Event::Eof
+                        );
+                    }
+                }
             }
         };
     }
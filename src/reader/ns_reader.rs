@@ -9,10 +9,11 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::ops::Deref;
 use std::path::Path;
+use std::sync::Arc;
 
 use crate::errors::Result;
-use crate::events::Event;
-use crate::name::{LocalName, NamespaceResolver, PrefixIter, QName, ResolveResult};
+use crate::events::{BytesStart, Event};
+use crate::name::{LocalName, NamespaceError, NamespaceResolver, PrefixIter, QName, ResolveResult};
 use crate::reader::{Config, Reader, Span, XmlSource};
 
 /// A low level encoding-agnostic XML event reader that performs namespace resolution.
@@ -27,6 +28,59 @@ pub struct NsReader<R> {
     /// event will be processed by the user, so we only mark that we should that
     /// in the next [`Self::read_event_impl()`] call.
     pending_pop: bool,
+    /// Resolved values of `xml:base` attributes of all opened elements. Contains
+    /// `None` for elements without that attribute or when tracking of `xml:base`
+    /// is disabled.
+    xml_bases: Vec<Option<String>>,
+}
+
+/// An expanded name of an element or an attribute decoded to strings: the
+/// namespace URI (or `None` if name is not bound to any namespace) and the local name.
+pub type DecodedName = (Option<String>, String);
+
+/// An event with all names resolved and decoded and all content decoded and
+/// unescaped. Returned by [`NsReader::read_decoded_event_into()`] and
+/// [`NsReader::read_decoded_event()`].
+///
+/// In contrast to the [`Event`], this type owns all its data, so it is easy to
+/// work with it, but construction of it requires allocations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodedEvent {
+    /// Start tag (with attributes) `<tag attr="value">`.
+    Start {
+        /// Expanded name of the element
+        name: DecodedName,
+        /// Expanded names and unescaped values of attributes in the order of
+        /// their definition, including namespace declarations
+        attributes: Vec<(DecodedName, String)>,
+    },
+    /// Empty element tag (with attributes) `<tag attr="value" />`.
+    Empty {
+        /// Expanded name of the element
+        name: DecodedName,
+        /// Expanded names and unescaped values of attributes in the order of
+        /// their definition, including namespace declarations
+        attributes: Vec<(DecodedName, String)>,
+    },
+    /// End tag `</tag>`.
+    End {
+        /// Expanded name of the element
+        name: DecodedName,
+    },
+    /// Unescaped character data between tags.
+    Text(String),
+    /// Content of a CDATA section `<![CDATA[...]]>`.
+    CData(String),
+    /// Content of a comment `<!-- ... -->`.
+    Comment(String),
+    /// Content of an XML declaration `<?xml ...?>`.
+    Decl(String),
+    /// Content of a processing instruction `<?...?>`.
+    PI(String),
+    /// Content of a document type definition `<!DOCTYPE ...>`.
+    DocType(String),
+    /// End of XML document.
+    Eof,
 }
 
 /// Builder methods
@@ -37,6 +91,15 @@ impl<R> NsReader<R> {
         Self::new(Reader::from_reader(reader))
     }
 
+    /// Creates a `NsReader` that reads from a reader and uses the specified
+    /// configuration.
+    ///
+    /// See the [`Reader::with_config`] for details.
+    #[inline]
+    pub fn with_config(reader: R, config: Config) -> Self {
+        Self::new(Reader::with_config(reader, config))
+    }
+
     /// Returns reference to the parser configuration
     #[inline]
     pub const fn config(&self) -> &Config {
@@ -132,16 +195,66 @@ impl<R> NsReader<R> {
     pub const fn prefixes(&self) -> PrefixIter {
         self.ns_resolver.iter()
     }
+
+    /// Returns the base URI in scope of the current element, which is defined
+    /// by the [`xml:base`] attribute of the nearest element that has it. Relative
+    /// URIs in nested `xml:base` attributes are resolved against the base URI
+    /// of the parent element as described in [RFC 3986].
+    ///
+    /// The base URI of the element is available after reading its [`Start`]
+    /// or [`Empty`] event and until the next event after the corresponding
+    /// [`End`] or [`Empty`] event will be read.
+    ///
+    /// Always returns `None` if the [`track_xml_base`] option is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::NsReader;
+    ///
+    /// let mut reader = NsReader::from_str(r#"
+    ///     <doc xml:base="http://example.org/today/">
+    ///         <paragraph xml:base="../yesterday/news.xml"/>
+    ///     </doc>
+    /// "#);
+    /// reader.config_mut().trim_text(true);
+    /// reader.config_mut().track_xml_base = true;
+    ///
+    /// reader.read_event()?; // <doc>
+    /// assert_eq!(reader.base_uri(), Some("http://example.org/today/"));
+    ///
+    /// reader.read_event()?; // <paragraph/>
+    /// assert_eq!(reader.base_uri(), Some("http://example.org/yesterday/news.xml"));
+    ///
+    /// reader.read_event()?; // </doc>
+    /// assert_eq!(reader.base_uri(), Some("http://example.org/today/"));
+    ///
+    /// reader.read_event()?; // EOF
+    /// assert_eq!(reader.base_uri(), None);
+    /// # quick_xml::Result::Ok(())
+    /// ```
+    ///
+    /// [`xml:base`]: https://www.w3.org/TR/xmlbase/
+    /// [RFC 3986]: https://www.rfc-editor.org/rfc/rfc3986#section-5.2
+    /// [`Start`]: Event::Start
+    /// [`Empty`]: Event::Empty
+    /// [`End`]: Event::End
+    /// [`track_xml_base`]: Config::track_xml_base
+    pub fn base_uri(&self) -> Option<&str> {
+        self.xml_bases.iter().rev().find_map(|base| base.as_deref())
+    }
 }
 
 /// Private methods
 impl<R> NsReader<R> {
     #[inline]
-    fn new(reader: Reader<R>) -> Self {
+    pub(super) fn new(reader: Reader<R>) -> Self {
         Self {
             reader,
             ns_resolver: NamespaceResolver::default(),
             pending_pop: false,
+            xml_bases: Vec::new(),
         }
     }
 
@@ -157,24 +270,30 @@ impl<R> NsReader<R> {
     pub(super) fn pop(&mut self) {
         if self.pending_pop {
             self.ns_resolver.pop();
+            self.xml_bases.pop();
             self.pending_pop = false;
         }
     }
 
     pub(super) fn process_event<'i>(&mut self, event: Result<Event<'i>>) -> Result<Event<'i>> {
         match event {
-            Ok(Event::Start(e)) => {
+            Ok(Event::Start(mut e)) => {
                 self.ns_resolver.push(&e)?;
+                self.push_xml_base(&e)?;
+                e.namespace = self.attached_namespace(e.name());
                 Ok(Event::Start(e))
             }
-            Ok(Event::Empty(e)) => {
+            Ok(Event::Empty(mut e)) => {
                 self.ns_resolver.push(&e)?;
+                self.push_xml_base(&e)?;
+                e.namespace = self.attached_namespace(e.name());
                 // notify next `read_event_impl()` invocation that it needs to pop this
                 // namespace scope
                 self.pending_pop = true;
                 Ok(Event::Empty(e))
             }
-            Ok(Event::End(e)) => {
+            Ok(Event::End(mut e)) => {
+                e.namespace = self.attached_namespace(e.name());
                 // notify next `read_event_impl()` invocation that it needs to pop this
                 // namespace scope
                 self.pending_pop = true;
@@ -184,6 +303,50 @@ impl<R> NsReader<R> {
         }
     }
 
+    /// Returns a copy of the namespace URI of the element with the specified
+    /// name, if attaching of namespaces to events is enabled.
+    fn attached_namespace(&self, name: QName) -> Option<Arc<[u8]>> {
+        if !self.reader.config().attach_namespaces {
+            return None;
+        }
+        match self.ns_resolver.find(name) {
+            ResolveResult::Bound(ns) => Some(Arc::from(ns.into_inner())),
+            _ => None,
+        }
+    }
+
+    /// Remembers the base URI of the element, if tracking of `xml:base` is enabled.
+    ///
+    /// The namespace scope of the element is already pushed, so an entry is pushed
+    /// even if the base URI cannot be read, to keep both stacks in sync.
+    fn push_xml_base(&mut self, e: &BytesStart) -> Result<()> {
+        let (base, result) = match self.xml_base(e) {
+            Ok(base) => (base, Ok(())),
+            Err(err) => (None, Err(err)),
+        };
+        self.xml_bases.push(base);
+        result
+    }
+
+    /// Returns the base URI of the element resolved against the base URI of
+    /// its parent, if the element has an `xml:base` attribute and tracking of
+    /// `xml:base` is enabled.
+    fn xml_base(&self, e: &BytesStart) -> Result<Option<String>> {
+        if self.reader.config().track_xml_base {
+            for attr in e.attributes().with_checks(false) {
+                let attr = attr?;
+                if attr.key.as_ref() == b"xml:base" {
+                    let value = attr.decode_and_unescape_value(self.reader.decoder())?;
+                    return Ok(Some(match self.base_uri() {
+                        Some(parent) => resolve_uri(parent, &value),
+                        None => value.into_owned(),
+                    }));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     pub(super) fn resolve_event<'i>(
         &mut self,
         event: Result<Event<'i>>,
@@ -196,6 +359,54 @@ impl<R> NsReader<R> {
             Err(e) => Err(e),
         }
     }
+
+    fn decode_event(&self, event: Event) -> Result<DecodedEvent> {
+        let decoder = self.reader.decoder();
+        Ok(match event {
+            Event::Start(e) => DecodedEvent::Start {
+                name: self.decode_name(self.resolve_element(e.name()))?,
+                attributes: self.decode_attributes(&e)?,
+            },
+            Event::Empty(e) => DecodedEvent::Empty {
+                name: self.decode_name(self.resolve_element(e.name()))?,
+                attributes: self.decode_attributes(&e)?,
+            },
+            Event::End(e) => DecodedEvent::End {
+                name: self.decode_name(self.resolve_element(e.name()))?,
+            },
+            Event::Text(e) => DecodedEvent::Text(e.unescape()?.into_owned()),
+            Event::CData(e) => DecodedEvent::CData(decoder.decode(&e)?.into_owned()),
+            Event::Comment(e) => DecodedEvent::Comment(decoder.decode(&e)?.into_owned()),
+            Event::Decl(e) => DecodedEvent::Decl(decoder.decode(&e)?.into_owned()),
+            Event::PI(e) => DecodedEvent::PI(decoder.decode(&e)?.into_owned()),
+            Event::DocType(e) => DecodedEvent::DocType(decoder.decode(&e)?.into_owned()),
+            Event::Eof => DecodedEvent::Eof,
+        })
+    }
+
+    fn decode_attributes(&self, e: &BytesStart) -> Result<Vec<(DecodedName, String)>> {
+        let decoder = self.reader.decoder();
+        let mut attributes = Vec::new();
+        for attr in e.attributes() {
+            let attr = attr?;
+            let name = self.decode_name(self.resolve_attribute(attr.key))?;
+            let value = attr.decode_and_unescape_value(decoder)?.into_owned();
+            attributes.push((name, value));
+        }
+        Ok(attributes)
+    }
+
+    fn decode_name(&self, (ns, local): (ResolveResult, LocalName)) -> Result<DecodedName> {
+        let decoder = self.reader.decoder();
+        let ns = match ns {
+            ResolveResult::Unbound => None,
+            ResolveResult::Bound(ns) => Some(decoder.decode(ns.as_ref())?.into_owned()),
+            ResolveResult::Unknown(prefix) => {
+                return Err(NamespaceError::UnknownPrefix(prefix).into())
+            }
+        };
+        Ok((ns, decoder.decode(local.as_ref())?.into_owned()))
+    }
 }
 
 /// Getters
@@ -208,6 +419,18 @@ impl<R> NsReader<R> {
         self.reader.into_inner()
     }
 
+    /// Converts this reader into a [`Reader`] that continues reading from the
+    /// current position without namespace resolution.
+    ///
+    /// The state of the parser, including the bytes that were already consumed
+    /// from the underlying reader, is preserved. Namespace bindings are dropped.
+    ///
+    /// See the [`Reader::into_ns_reader`] for examples
+    #[inline]
+    pub fn into_reader(self) -> Reader<R> {
+        self.reader
+    }
+
     /// Gets a mutable reference to the underlying reader.
     pub fn get_mut(&mut self) -> &mut R {
         self.reader.get_mut()
@@ -502,6 +725,49 @@ impl<R: BufRead> NsReader<R> {
         self.resolve_event(event)
     }
 
+    /// Reads the next event into given buffer, resolves namespaces of the element
+    /// and attribute names and decodes all names and content to strings.
+    ///
+    /// This is a convenience method which is useful when performance is not
+    /// critical: it allocates new strings for each name and content of each
+    /// event. Names of elements and attributes are returned as [`DecodedName`]
+    /// pairs of the namespace URI and the local name. Text and attribute values
+    /// are unescaped.
+    ///
+    /// If prefix of a name is not declared, the [`NamespaceError::UnknownPrefix`]
+    /// error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::reader::{DecodedEvent, NsReader};
+    ///
+    /// let mut reader = NsReader::from_reader(r#"<x:tag xmlns:x="www.xxxx" x:attr="&lt;"/>"#.as_bytes());
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(
+    ///     reader.read_decoded_event_into(&mut buf).unwrap(),
+    ///     DecodedEvent::Empty {
+    ///         name: (Some("www.xxxx".to_string()), "tag".to_string()),
+    ///         attributes: vec![
+    ///             (
+    ///                 (Some("http://www.w3.org/2000/xmlns/".to_string()), "x".to_string()),
+    ///                 "www.xxxx".to_string(),
+    ///             ),
+    ///             ((Some("www.xxxx".to_string()), "attr".to_string()), "<".to_string()),
+    ///         ],
+    ///     }
+    /// );
+    /// assert_eq!(reader.read_decoded_event_into(&mut buf).unwrap(), DecodedEvent::Eof);
+    /// ```
+    ///
+    /// [`NamespaceError::UnknownPrefix`]: crate::name::NamespaceError::UnknownPrefix
+    pub fn read_decoded_event_into(&mut self, buf: &mut Vec<u8>) -> Result<DecodedEvent> {
+        let event = self.read_event_impl(buf)?;
+        self.decode_event(event)
+    }
+
     /// Reads until end element is found using provided buffer as intermediate
     /// storage for events content. This function is supposed to be called after
     /// you already read a [`Start`] event.
@@ -746,6 +1012,18 @@ impl<'i> NsReader<&'i [u8]> {
         self.resolve_event(event)
     }
 
+    /// Reads the next event, resolves namespaces of the element and attribute
+    /// names and decodes all names and content to strings.
+    ///
+    /// This is a convenience method that allocates new strings for each name
+    /// and content of each event. See [`read_decoded_event_into()`] for details.
+    ///
+    /// [`read_decoded_event_into()`]: NsReader::read_decoded_event_into
+    pub fn read_decoded_event(&mut self) -> Result<DecodedEvent> {
+        let event = self.read_event_impl(())?;
+        self.decode_event(event)
+    }
+
     /// Reads until end element is found. This function is supposed to be called
     /// after you already read a [`Start`] event.
     ///
@@ -921,3 +1199,154 @@ impl<R> Deref for NsReader<R> {
         &self.reader
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Components of a URI reference as defined in [RFC 3986].
+///
+/// [RFC 3986]: https://www.rfc-editor.org/rfc/rfc3986#section-3
+struct UriRef<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UriRef<'a> {
+    /// Splits URI reference into components as described in [Appendix B] of RFC 3986.
+    ///
+    /// [Appendix B]: https://www.rfc-editor.org/rfc/rfc3986#appendix-B
+    fn parse(uri: &'a str) -> Self {
+        let (rest, fragment) = match uri.find('#') {
+            Some(i) => (&uri[..i], Some(&uri[i + 1..])),
+            None => (uri, None),
+        };
+        let (rest, query) = match rest.find('?') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.find(|c| c == ':' || c == '/') {
+            Some(i) if i > 0 && rest.as_bytes()[i] == b':' => (Some(&rest[..i]), &rest[i + 1..]),
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => match rest.find('/') {
+                Some(i) => (Some(&rest[..i]), &rest[i..]),
+                None => (Some(rest), ""),
+            },
+            None => (None, rest),
+        };
+        Self {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Resolves `reference` against `base` URI as described in [section 5.2] of RFC 3986.
+///
+/// [section 5.2]: https://www.rfc-editor.org/rfc/rfc3986#section-5.2
+fn resolve_uri(base: &str, reference: &str) -> String {
+    let base = UriRef::parse(base);
+    let r = UriRef::parse(reference);
+
+    let (scheme, authority, path, query) = if r.scheme.is_some() {
+        (r.scheme, r.authority, remove_dot_segments(r.path), r.query)
+    } else if r.authority.is_some() {
+        (
+            base.scheme,
+            r.authority,
+            remove_dot_segments(r.path),
+            r.query,
+        )
+    } else if r.path.is_empty() {
+        (
+            base.scheme,
+            base.authority,
+            base.path.to_string(),
+            r.query.or(base.query),
+        )
+    } else if r.path.starts_with('/') {
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(r.path),
+            r.query,
+        )
+    } else {
+        // Merge paths (section 5.2.3)
+        let merged = if base.authority.is_some() && base.path.is_empty() {
+            format!("/{}", r.path)
+        } else {
+            match base.path.rfind('/') {
+                Some(i) => format!("{}{}", &base.path[..=i], r.path),
+                None => r.path.to_string(),
+            }
+        };
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(&merged),
+            r.query,
+        )
+    };
+
+    // Component recomposition (section 5.3)
+    let mut result = String::new();
+    if let Some(scheme) = scheme {
+        result.push_str(scheme);
+        result.push(':');
+    }
+    if let Some(authority) = authority {
+        result.push_str("//");
+        result.push_str(authority);
+    }
+    result.push_str(&path);
+    if let Some(query) = query {
+        result.push('?');
+        result.push_str(query);
+    }
+    if let Some(fragment) = r.fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+    result
+}
+
+/// Removes `.` and `..` segments from a path as described in [section 5.2.4] of RFC 3986.
+///
+/// [section 5.2.4]: https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+    while !input.is_empty() {
+        if let Some(rest) = input.strip_prefix("../") {
+            input = rest;
+        } else if let Some(rest) = input.strip_prefix("./") {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") || input == "/.." {
+            input = if input == "/.." { "/" } else { &input[3..] };
+            output.truncate(output.rfind('/').unwrap_or(0));
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            // Move the first path segment (including the initial `/`, if any)
+            let start = if input.starts_with('/') { 1 } else { 0 };
+            let end = match input[start..].find('/') {
+                Some(i) => start + i,
+                None => input.len(),
+            };
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output
+}
@@ -10,12 +10,12 @@ use crate::reader::EncodingRef;
 #[cfg(feature = "encoding")]
 use encoding_rs::{Encoding, UTF_8};
 
-use crate::errors::{Error, Result};
+use crate::errors::{Error, Result, SyntaxError};
 use crate::events::Event;
 use crate::name::QName;
-use crate::parser::Parser;
-use crate::reader::{BangType, ReadTextResult, Reader, Span, XmlSource};
-use crate::utils::is_whitespace;
+use crate::parser::{ElementParser, Parser, PiParser};
+use crate::reader::{BangType, Config, ParseState, ReadTextResult, Reader, Span, XmlSource};
+use crate::utils::{is_whitespace, name_len};
 
 /// This is an implementation for reading from a `&[u8]` as underlying byte stream.
 /// This implementation supports not using an intermediate buffer as the byte slice
@@ -36,12 +36,39 @@ impl<'a> Reader<&'a [u8]> {
         Self::from_reader(s.as_bytes())
     }
 
+    /// Creates an XML reader from a string slice with the [strict] configuration,
+    /// which enables all well-formedness checks.
+    ///
+    /// [strict]: crate::reader::Config::strict
+    pub fn from_str_strict(s: &'a str) -> Self {
+        let mut reader = Self::from_str(s);
+        reader.state.config = Config::strict();
+        reader
+    }
+
     /// Read an event that borrows from the input rather than a buffer.
     ///
     /// There is no asynchronous `read_event_async()` version of this function,
     /// because it is not necessary -- the contents are already in memory and no IO
     /// is needed, therefore there is no potential for blocking.
     ///
+    /// Returned events borrow from the input slice with the lifetime `'a`, not from
+    /// the reader, so they can be kept while reading next events and across
+    /// `.await` points in asynchronous code. The only constraint is that the
+    /// input should outlive events. Use [`Event::into_owned()`] if an event should
+    /// live longer than the input.
+    #[cfg_attr(
+        feature = "async-tokio",
+        doc = "Wrapping the slice in an asynchronous reader to use \
+               [`read_event_into_async()`](Self::read_event_into_async) \
+               would copy each event into a buffer instead."
+    )]
+    ///
+    /// When the end of input is reached, [`Event::Eof`] is returned. After that
+    /// all subsequent calls also return [`Event::Eof`], so the reader can be safely
+    /// polled again. Reading usually can be continued after [`Error::IllFormed`]
+    /// errors up to the end of input.
+    ///
     /// # Examples
     ///
     /// ```
@@ -75,6 +102,108 @@ impl<'a> Reader<&'a [u8]> {
         self.read_event_impl(())
     }
 
+    /// Returns raw bytes of the last markup event (such as [`Start`], [`End`],
+    /// [`Empty`], [`Comment`], [`CData`], [`Decl`], [`PI`] or [`DocType`])
+    /// returned by [`read_event()`], starting from the opening `<` and ending
+    /// with the closing `>` inclusive. The bytes are returned as they are
+    /// in the input, so they could be copied to the output without changes.
+    ///
+    /// The method is also available on the [`NsReader`] over a slice and
+    /// returns raw bytes of events read by it.
+    ///
+    /// Returns an empty slice if the last returned event was not a markup event
+    /// or an error, or if the reader was moved by other methods, such as
+    /// [`read_to_end()`] or [`skip_element()`]. The event peeked by
+    /// [`peek_event_into()`] is not read by [`read_event()`], so its raw bytes
+    /// are not available either.
+    ///
+    /// The [`End`] event generated for an empty element when [`expand_empty_elements`]
+    /// is set has the same raw bytes as the corresponding [`Start`] event.
+    ///
+    /// This method is available only for the borrowing slice reader, because
+    /// readers from [`BufRead`] do not keep the read data after returning an event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str("<tag  attr = 'value' >text</tag >");
+    ///
+    /// assert_eq!(
+    ///     reader.read_event().unwrap(),
+    ///     Event::Start(BytesStart::from_content("tag  attr = 'value' ", 3))
+    /// );
+    /// assert_eq!(reader.last_event_raw(), b"<tag  attr = 'value' >");
+    ///
+    /// assert!(matches!(reader.read_event().unwrap(), Event::Text(_)));
+    /// assert_eq!(reader.last_event_raw(), b"");
+    ///
+    /// assert!(matches!(reader.read_event().unwrap(), Event::End(_)));
+    /// assert_eq!(reader.last_event_raw(), b"</tag >");
+    /// ```
+    ///
+    /// [`Start`]: Event::Start
+    /// [`End`]: Event::End
+    /// [`Empty`]: Event::Empty
+    /// [`Comment`]: Event::Comment
+    /// [`CData`]: Event::CData
+    /// [`Decl`]: Event::Decl
+    /// [`PI`]: Event::PI
+    /// [`DocType`]: Event::DocType
+    /// [`read_event()`]: Self::read_event
+    /// [`read_to_end()`]: Self::read_to_end
+    /// [`skip_element()`]: Self::skip_element
+    /// [`peek_event_into()`]: Self::peek_event_into
+    /// [`NsReader`]: crate::reader::NsReader
+    /// [`expand_empty_elements`]: Config::expand_empty_elements
+    /// [`BufRead`]: std::io::BufRead
+    pub fn last_event_raw(&self) -> &'a [u8] {
+        let (input, markup) = match (self.input, &self.state.markup) {
+            (Some(input), Some(markup)) => (input, markup),
+            _ => return &[],
+        };
+        // Both `input` and the rest of the data end at the same byte, so the
+        // position is converted to the index from the end of `input`
+        let index = |position: u64| {
+            let rest = self.state.offset.checked_sub(position)? as usize + self.reader.len();
+            input.len().checked_sub(rest)
+        };
+        match (index(markup.start), index(markup.end)) {
+            (Some(start), Some(end)) => &input[start..end],
+            _ => &[],
+        }
+    }
+
+    /// Reads the next event without consuming it, so the next call to
+    /// [`read_event()`] returns the same event.
+    ///
+    /// Because events borrow from the input, no copying is performed and the
+    /// state of the reader (including [`buffer_position()`]) is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str("<tag/>");
+    ///
+    /// assert_eq!(reader.peek_event().unwrap(), Event::Empty(BytesStart::new("tag")));
+    /// assert_eq!(reader.buffer_position(), 0);
+    /// assert_eq!(reader.read_event().unwrap(), Event::Empty(BytesStart::new("tag")));
+    /// assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    /// ```
+    ///
+    /// [`read_event()`]: Self::read_event
+    /// [`buffer_position()`]: Reader::buffer_position
+    pub fn peek_event(&self) -> Result<Event<'a>> {
+        self.clone().read_event()
+    }
+
     /// Reads until end element is found. This function is supposed to be called
     /// after you already read a [`Start`] event.
     ///
@@ -156,7 +285,141 @@ impl<'a> Reader<&'a [u8]> {
     /// [`check_end_names`]: crate::reader::Config::check_end_names
     /// [the specification]: https://www.w3.org/TR/xml11/#dt-etag
     pub fn read_to_end(&mut self, end: QName) -> Result<Span> {
-        Ok(read_to_end!(self, end, (), read_event_impl, {}))
+        let span = read_to_end!(self, end, (), read_event_impl, {});
+        self.state.markup = None;
+        Ok(span)
+    }
+
+    /// Skips the content of an element up to and including its end tag. This
+    /// function is supposed to be called after you already read a [`Start`] event.
+    ///
+    /// This is a faster alternative to [`read_to_end()`] which does not generate
+    /// events for the skipped content. Instead, it scans the raw bytes for the
+    /// matching closing tag, only determining boundaries of the markup. Comments,
+    /// CDATA sections and processing instructions are skipped as a whole, so
+    /// `<` and `>` inside them do not confuse the search. Attributes are not parsed.
+    ///
+    /// Returns the same span as [`read_to_end()`] would return.
+    ///
+    /// Manages nested cases where parent and child elements have the _literally_
+    /// same name. As in [`read_to_end()`], namespaces are not taken into account
+    /// when comparing names.
+    ///
+    /// The skipped content is not checked for well-formedness: mismatched nested
+    /// tags, double hyphens in comments and other problems are not reported.
+    /// Only the final end tag is checked against the opened element, if the
+    /// [`check_end_names`] option is enabled.
+    ///
+    /// If a corresponding end tag is not found, an error of type [`Error::IllFormed`]
+    /// will be returned. Syntax errors are returned if some markup in the skipped
+    /// content is not closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesEnd, BytesStart, Event};
+    /// use quick_xml::reader::Reader;
+    ///
+    /// let mut reader = Reader::from_str(r#"
+    ///     <root>
+    ///         <skip>
+    ///             <skip>nested</skip>
+    ///             <!-- </skip> -->
+    ///             <![CDATA[</skip>]]>
+    ///         </skip>
+    ///         <keep/>
+    ///     </root>
+    /// "#);
+    /// reader.config_mut().trim_text(true);
+    ///
+    /// let start = BytesStart::new("skip");
+    /// let end   = start.to_end().into_owned();
+    ///
+    /// assert_eq!(reader.read_event().unwrap(), Event::Start(BytesStart::new("root")));
+    /// assert_eq!(reader.read_event().unwrap(), Event::Start(start));
+    ///
+    /// reader.skip_element(end.name()).unwrap();
+    ///
+    /// assert_eq!(reader.read_event().unwrap(), Event::Empty(BytesStart::new("keep")));
+    /// assert_eq!(reader.read_event().unwrap(), Event::End(BytesEnd::new("root")));
+    /// assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    /// ```
+    ///
+    /// [`Start`]: Event::Start
+    /// [`read_to_end()`]: Self::read_to_end
+    /// [`check_end_names`]: crate::reader::Config::check_end_names
+    pub fn skip_element(&mut self, name: QName) -> Result<Span> {
+        // The content of an expanded empty element is empty, nothing to scan
+        if let ParseState::InsideEmpty = self.state.state {
+            return self.read_to_end(name);
+        }
+
+        self.state.markup = None;
+        let start = self.buffer_position();
+        let mut input = self.reader;
+        // Number of bytes scanned from `input`
+        let mut position = 0;
+        let mut depth = 0;
+        loop {
+            let i = match memchr::memchr(b'<', input) {
+                Some(i) => i,
+                None => {
+                    self.reader = &[];
+                    self.state.offset += position + input.len() as u64;
+                    return Err(Error::missed_end(name, self.decoder()));
+                }
+            };
+            // Position of `<` relative to the start of scanning
+            let markup = position + i as u64;
+            let rest = &input[i..];
+            input = &input[i + 1..];
+            position = markup + 1;
+
+            let result = match input.first() {
+                Some(b'!') => input.read_bang_element((), 0, &mut position).map(|_| ()),
+                Some(b'?') => input
+                    .read_with(PiParser(false), (), 0, &mut position)
+                    .map(|_| ()),
+                Some(b'/') => match input.read_with(ElementParser::Outside, (), 0, &mut position) {
+                    Ok(content) if &content[1..1 + name_len(&content[1..])] == name.as_ref() => {
+                        if depth == 0 {
+                            // Leave the end tag to the usual parsing routine, which
+                            // will check it and update the stack of opened elements
+                            self.reader = rest;
+                            self.state.offset += markup;
+                            let event = self.read_event_impl(());
+                            self.state.markup = None;
+                            return match event? {
+                                Event::End(_) => Ok(start..start + markup),
+                                _ => unreachable!("end tag should produce an End event"),
+                            };
+                        }
+                        depth -= 1;
+                        Ok(())
+                    }
+                    result => result.map(|_| ()),
+                },
+                Some(_) => match input.read_with(ElementParser::Outside, (), 0, &mut position) {
+                    Ok(content) => {
+                        if !content.ends_with(b"/")
+                            && &content[..name_len(content)] == name.as_ref()
+                        {
+                            depth += 1;
+                        }
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                },
+                None => Err(Error::Syntax(SyntaxError::UnclosedTag)),
+            };
+            if let Err(e) = result {
+                self.reader = input;
+                self.state.offset += position;
+                self.state.last_error_offset = start + markup;
+                return Err(e);
+            }
+        }
     }
 
     /// Reads content between start and end tags, including any markup. This
@@ -253,17 +516,29 @@ impl<'a> XmlSource<'a, ()> for &'a [u8] {
 
     #[cfg(feature = "encoding")]
     #[inline]
-    fn detect_encoding(&mut self) -> io::Result<Option<&'static Encoding>> {
+    fn detect_encoding(
+        &mut self,
+        consume_bom: bool,
+    ) -> io::Result<Option<(&'static Encoding, usize)>> {
         if let Some((enc, bom_len)) = crate::encoding::detect_encoding(self) {
-            *self = &self[bom_len..];
-            return Ok(Some(enc));
+            if consume_bom {
+                *self = &self[bom_len..];
+            }
+            return Ok(Some((enc, bom_len)));
         }
         Ok(None)
     }
 
     #[inline]
-    fn read_text(&mut self, _buf: (), position: &mut u64) -> ReadTextResult<'a, ()> {
+    fn read_text(
+        &mut self,
+        _buf: (),
+        max_len: usize,
+        position: &mut u64,
+    ) -> ReadTextResult<'a, ()> {
         match memchr::memchr(b'<', self) {
+            Some(i) if i > max_len => ReadTextResult::TooLong,
+            None if self.len() > max_len => ReadTextResult::TooLong,
             Some(0) => {
                 *position += 1;
                 *self = &self[1..];
@@ -285,7 +560,13 @@ impl<'a> XmlSource<'a, ()> for &'a [u8] {
     }
 
     #[inline]
-    fn read_with<P>(&mut self, mut parser: P, _buf: (), position: &mut u64) -> Result<&'a [u8]>
+    fn read_with<P>(
+        &mut self,
+        mut parser: P,
+        _buf: (),
+        _read: usize,
+        position: &mut u64,
+    ) -> Result<&'a [u8]>
     where
         P: Parser,
     {
@@ -302,7 +583,12 @@ impl<'a> XmlSource<'a, ()> for &'a [u8] {
     }
 
     #[inline]
-    fn read_bang_element(&mut self, _buf: (), position: &mut u64) -> Result<(BangType, &'a [u8])> {
+    fn read_bang_element(
+        &mut self,
+        _buf: (),
+        _read: usize,
+        position: &mut u64,
+    ) -> Result<(BangType, &'a [u8])> {
         // Peeked one bang ('!') before being called, so it's guaranteed to
         // start with it.
         debug_assert_eq!(self[0], b'!');
@@ -334,6 +620,21 @@ impl<'a> XmlSource<'a, ()> for &'a [u8] {
     fn peek_one(&mut self) -> io::Result<Option<u8>> {
         Ok(self.first().copied())
     }
+
+    #[inline]
+    fn can_resume(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+
+    #[inline]
+    fn snapshot(&self) -> Option<Self> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,11 @@
 #[cfg(feature = "encoding")]
 use encoding_rs::UTF_8;
 
-use crate::encoding::Decoder;
-use crate::errors::{Error, IllFormedError, Result, SyntaxError};
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::encoding::{Decoder, UTF8_BOM};
+use crate::errors::{Error, IllFormedError, Result, SyntaxError, Warning};
 use crate::events::{BytesCData, BytesDecl, BytesEnd, BytesPI, BytesStart, BytesText, Event};
 #[cfg(feature = "encoding")]
 use crate::reader::EncodingRef;
@@ -25,6 +28,13 @@ pub(super) struct ReaderState {
     pub state: ParseState,
     /// User-defined settings that affect parsing
     pub config: Config,
+    /// If reading of markup was interrupted by the end of input and can be resumed,
+    /// contains the first byte after `<` and the number of bytes of markup that
+    /// was already consumed. See [`Config::resume_incomplete_markup`]
+    pub incomplete: Option<(u8, usize)>,
+    /// Positions of the `<` and after the `>` of the last returned markup event,
+    /// or `None`, if the last returned event was not a markup event
+    pub markup: Option<Range<u64>>,
     /// All currently Started elements which didn't have a matching
     /// End element yet.
     ///
@@ -46,6 +56,17 @@ pub(super) struct ReaderState {
     /// Opened name start indexes into [`Self::opened_buffer`]. See documentation
     /// for that field for details
     opened_starts: Vec<usize>,
+    /// Positions of the `<` characters of the opened tags in the input. Each item
+    /// corresponds to the item in [`Self::opened_starts`] with the same index.
+    opened_positions: Vec<u64>,
+
+    /// Position of `<` at which an XML declaration is allowed: the start of the
+    /// document or the end of a BOM returned as a text. Becomes `None` after
+    /// reading any other event. See [`Config::check_decl_position`]
+    pub decl_position: Option<u64>,
+
+    /// Warnings collected during reading. See [`Config::warn_mid_document_bom`]
+    pub warnings: Vec<Warning>,
 
     #[cfg(feature = "encoding")]
     /// Reference to the encoding used to read an XML
@@ -53,7 +74,18 @@ pub(super) struct ReaderState {
 }
 
 impl ReaderState {
-    /// Trims end whitespaces from `bytes`, if required, and returns a text event.
+    /// Replaces `\r\n` and lone `\r` in `bytes` with `\n`, if that is enabled
+    /// by the [`Config::normalize_line_endings`] option.
+    fn normalize<'b>(&self, bytes: &'b [u8]) -> Cow<'b, [u8]> {
+        if self.config.normalize_line_endings {
+            normalize_line_endings(bytes)
+        } else {
+            Cow::Borrowed(bytes)
+        }
+    }
+
+    /// Trims end whitespaces from `bytes` and normalizes line endings, if required,
+    /// and returns a text event.
     ///
     /// # Parameters
     /// - `bytes`: data from the start of stream to the first `<` or from `>` to `<`
@@ -68,7 +100,48 @@ impl ReaderState {
                 .map_or(0, |p| p + 1);
             content = &bytes[..len];
         }
-        BytesText::wrap(content, self.decoder())
+        BytesText::wrap(self.normalize(content), self.decoder())
+    }
+
+    /// Checks that text does not contain the `]]>` sequence, if that check is
+    /// enabled by the [`Config::check_characters`] option, and collects warnings
+    /// about BOMs in the text, if the [`Config::warn_mid_document_bom`] option
+    /// is enabled.
+    ///
+    /// # Parameters
+    /// - `bytes`: data of the text event
+    /// - `start`: position of the first byte of `bytes` in the input
+    pub fn check_text(&mut self, bytes: &[u8], start: u64) -> Result<()> {
+        if self.config.check_characters {
+            let mut iter = memchr::memchr_iter(b'>', bytes);
+            if let Some(p) = iter.find(|&p| p >= 2 && &bytes[p - 2..p] == b"]]") {
+                // Report position of the `]]>`
+                self.last_error_offset = start + p as u64 - 2;
+                return Err(Error::IllFormed(IllFormedError::CDataEndInText));
+            }
+        }
+        if self.config.warn_mid_document_bom && self.is_utf8() {
+            for p in memchr::memchr_iter(UTF8_BOM[0], bytes) {
+                let position = start + p as u64;
+                // BOM at the start of the document is not consumed if
+                // `consume_bom` is disabled, but it is not a mid-document BOM
+                let at_start = position == 0 && !self.config.consume_bom;
+                if !at_start && bytes[p..].starts_with(UTF8_BOM) {
+                    self.warnings.push(Warning::MidDocumentBom(position));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the document is read in UTF-8
+    #[inline]
+    fn is_utf8(&self) -> bool {
+        #[cfg(feature = "encoding")]
+        return self.encoding.encoding() == UTF_8;
+
+        #[cfg(not(feature = "encoding"))]
+        true
     }
 
     /// Returns `Comment`, `CData` or `DocType` event.
@@ -136,7 +209,7 @@ impl ReaderState {
                 debug_assert!(buf.ends_with(b"]]"));
                 Ok(Event::CData(BytesCData::wrap(
                     // Cut of `![CDATA[` and `]]` from start and end
-                    &buf[8..len - 2],
+                    self.normalize(&buf[8..len - 2]),
                     self.decoder(),
                 )))
             }
@@ -200,6 +273,7 @@ impl ReaderState {
         // Get the index in self.opened_buffer of the name of the last opened tag
         match self.opened_starts.pop() {
             Some(start) => {
+                let expected_position = self.opened_positions.pop().unwrap_or_default();
                 if self.config.check_end_names {
                     let expected = &self.opened_buffer[start..];
                     if name != expected {
@@ -213,6 +287,8 @@ impl ReaderState {
                         return Err(Error::IllFormed(IllFormedError::MismatchedEndTag {
                             expected,
                             found: decoder.decode(name).unwrap_or_default().into_owned(),
+                            expected_position,
+                            found_position: self.last_error_offset,
                         }));
                     }
                 }
@@ -251,6 +327,14 @@ impl ReaderState {
             let len = content.len();
 
             if content.starts_with(b"xml") && (len == 3 || is_whitespace(content[3])) {
+                // Position of `<` (-2 for `<` and `>`, -2 for two `?`)
+                let start = self.offset - len as u64 - 4;
+                // Whitespaces skipped due to `trim_text_start` also make
+                // the declaration misplaced, so check the position
+                if self.config.check_decl_position && self.decl_position != Some(start) {
+                    self.last_error_offset = start;
+                    return Err(Error::IllFormed(IllFormedError::MisplacedDeclaration));
+                }
                 let event = BytesDecl::from_start(BytesStart::wrap(content, 3));
 
                 // Try getting encoding from the declaration event
@@ -279,33 +363,49 @@ impl ReaderState {
     /// # Parameters
     /// - `content`: Content of a tag between `<` and `>`
     pub fn emit_start<'b>(&mut self, content: &'b [u8]) -> Event<'b> {
+        // Position of `<`: `self.offset` is after `>`, -2 for `<` and `>`
+        let position = self.offset - content.len() as u64 - 2;
         if let Some(content) = content.strip_suffix(b"/") {
             // This is self-closed tag `<something/>`
-            let event = BytesStart::wrap(content, name_len(content));
+            let event = self.wrap_start(content);
 
             if self.config.expand_empty_elements {
                 self.state = ParseState::InsideEmpty;
                 self.opened_starts.push(self.opened_buffer.len());
+                self.opened_positions.push(position);
                 self.opened_buffer.extend(event.name().as_ref());
                 Event::Start(event)
             } else {
                 Event::Empty(event)
             }
         } else {
-            let event = BytesStart::wrap(content, name_len(content));
+            let event = self.wrap_start(content);
 
             // #514: Always store names event when .check_end_names == false,
             // because checks can be temporary disabled and when they would be
             // enabled, we should have that information
             self.opened_starts.push(self.opened_buffer.len());
+            self.opened_positions.push(position);
             self.opened_buffer.extend(event.name().as_ref());
             Event::Start(event)
         }
     }
 
+    /// Wraps content of a tag between `<` and `>` (without `/` for self-closed
+    /// tags) into a `BytesStart`, normalizing line endings in attributes, if required.
+    fn wrap_start<'b>(&self, content: &'b [u8]) -> BytesStart<'b> {
+        // Names cannot contain `\r`, so normalization does not change the name length
+        BytesStart {
+            name_len: name_len(content),
+            buf: self.normalize(content),
+            namespace: None,
+        }
+    }
+
     #[inline]
     pub fn close_expanded_empty(&mut self) -> BytesEnd<'static> {
         self.state = ParseState::InsideText;
+        self.opened_positions.pop();
         let name = self
             .opened_buffer
             .split_off(self.opened_starts.pop().unwrap());
@@ -336,11 +436,41 @@ impl Default for ReaderState {
             last_error_offset: 0,
             state: ParseState::Init,
             config: Config::default(),
+            incomplete: None,
+            markup: None,
             opened_buffer: Vec::new(),
             opened_starts: Vec::new(),
+            opened_positions: Vec::new(),
+            decl_position: Some(0),
+            warnings: Vec::new(),
 
             #[cfg(feature = "encoding")]
             encoding: EncodingRef::Implicit(UTF_8),
         }
     }
 }
+
+/// Replaces `\r\n` and lone `\r` in `bytes` with `\n`, as required by the
+/// [XML specification]. Allocates only when `bytes` contains `\r`.
+///
+/// [XML specification]: https://www.w3.org/TR/xml11/#sec-line-ends
+fn normalize_line_endings(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let mut iter = memchr::memchr_iter(b'\r', bytes).peekable();
+    if iter.peek().is_none() {
+        return Cow::Borrowed(bytes);
+    }
+    let mut normalized = Vec::with_capacity(bytes.len());
+    let mut last = 0;
+    for p in iter {
+        normalized.extend_from_slice(&bytes[last..p]);
+        normalized.push(b'\n');
+        // Skip `\n` after `\r`
+        last = if bytes.get(p + 1) == Some(&b'\n') {
+            p + 2
+        } else {
+            p + 1
+        };
+    }
+    normalized.extend_from_slice(&bytes[last..]);
+    Cow::Owned(normalized)
+}
@@ -2,8 +2,12 @@
 
 use crate::de::TEXT_KEY;
 use crate::se::element::{ElementSerializer, Struct, Tuple};
+use crate::se::key::QNameSerializer;
 use crate::se::simple_type::{QuoteTarget, SimpleTypeSerializer};
-use crate::se::{Indent, QuoteLevel, SeError, WriteResult, XmlName};
+use crate::se::{
+    check_comment, check_pi, Indent, QuoteLevel, SeError, WriteResult, XmlName, COMMENT_NAME,
+    PI_NAME,
+};
 use serde::ser::{
     Impossible, Serialize, SerializeSeq, SerializeTuple, SerializeTupleStruct, Serializer,
 };
@@ -82,6 +86,9 @@ pub struct ContentSerializer<'w, 'i, W: Write> {
     // If `true`, then empty elements will be serialized as `<element></element>`
     // instead of `<element/>`.
     pub expand_empty_elements: bool,
+    /// Function that converts names of struct fields to names of XML elements
+    /// and attributes. `None` if names are written as is.
+    pub rename_fields: Option<fn(&str) -> String>,
 }
 
 impl<'w, 'i, W: Write> ContentSerializer<'w, 'i, W> {
@@ -118,6 +125,7 @@ impl<'w, 'i, W: Write> ContentSerializer<'w, 'i, W> {
             write_indent: self.write_indent,
             allow_primitive,
             expand_empty_elements: self.expand_empty_elements,
+            rename_fields: self.rename_fields,
         }
     }
 
@@ -161,6 +169,38 @@ impl<'w, 'i, W: Write> ContentSerializer<'w, 'i, W> {
         Ok(WriteResult::Element)
     }
 
+    /// Writes `value` as a comment (if `name` is [`COMMENT_NAME`]) or as
+    /// a processing instruction (if `name` is [`PI_NAME`]).
+    pub(super) fn write_markup<T>(mut self, name: &str, value: &T) -> Result<WriteResult, SeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let content = value.serialize(QNameSerializer {
+            writer: String::new(),
+        })?;
+        if name == COMMENT_NAME {
+            check_comment(&content)?;
+            self.write_indent()?;
+            self.writer.write_str("<!--")?;
+            self.writer.write_str(&content)?;
+            self.writer.write_str("-->")?;
+        } else {
+            // Content of the `Pi` is a target optionally followed by a space and data
+            let (target, data) = content.split_once(' ').unwrap_or((&content, ""));
+            if target.is_empty() {
+                return Err(SeError::Unsupported(
+                    "processing instruction target cannot be empty".into(),
+                ));
+            }
+            check_pi(target, data)?;
+            self.write_indent()?;
+            self.writer.write_str("<?")?;
+            self.writer.write_str(&content)?;
+            self.writer.write_str("?>")?;
+        }
+        Ok(WriteResult::Element)
+    }
+
     pub(super) fn write_indent(&mut self) -> Result<(), SeError> {
         if self.write_indent {
             self.indent.write_indent(&mut self.writer)?;
@@ -259,12 +299,20 @@ impl<'w, 'i, W: Write> Serializer for ContentSerializer<'w, 'i, W> {
         }
     }
 
+    /// Writes comments and processing instructions for [`Comment`] and [`Pi`]
+    /// types and serializes an inner value using the same serializer otherwise.
+    ///
+    /// [`Comment`]: crate::se::Comment
+    /// [`Pi`]: crate::se::Pi
     fn serialize_newtype_struct<T: ?Sized + Serialize>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error> {
-        value.serialize(self)
+        match name {
+            COMMENT_NAME | PI_NAME => self.write_markup(name, value),
+            _ => value.serialize(self),
+        }
     }
 
     /// If `variant` is a special `$text` variant, then writes `value` as a `xs:simpleType`,
@@ -409,7 +457,7 @@ impl<'w, 'i, W: Write> SerializeSeq for Seq<'w, 'i, W> {
     where
         T: ?Sized + Serialize,
     {
-        self.last = value.serialize(self.ser.new_seq_element_serializer(self.last.is_text()))?;
+        self.last = value.serialize(self.ser.new_seq_element_serializer(!self.last.is_text()))?;
         // Write indent for next element if indents are used
         self.ser.write_indent = self.last.allow_indent();
         Ok(())
@@ -597,6 +645,7 @@ pub(super) mod tests {
                         write_indent: false,
                         allow_primitive: true,
                         expand_empty_elements: false,
+                        rename_fields: None,
                     };
 
                     let result = $data.serialize(ser).unwrap();
@@ -620,6 +669,7 @@ pub(super) mod tests {
                         write_indent: false,
                         allow_primitive: true,
                         expand_empty_elements: false,
+                        rename_fields: None,
                     };
 
                     match $data.serialize(ser).unwrap_err() {
@@ -702,6 +752,9 @@ pub(super) mod tests {
         serialize_as!(enum_tuple: Enum::Tuple("first", 42)
             => "<Tuple>first</Tuple>\
                 <Tuple>42</Tuple>");
+        // Primitives are allowed when they are delimited by elements
+        serialize_as!(tuple_mixed: (42, Enum::Unit, "text")
+            => "42<Unit/>text", SensitiveText);
 
         // Structured types cannot be serialized without surrounding tag, which
         // only `enum` can provide
@@ -1046,10 +1099,11 @@ pub(super) mod tests {
                     let ser = ContentSerializer {
                         writer: &mut buffer,
                         level: QuoteLevel::Full,
-                        indent: Indent::Owned(Indentation::new(b' ', 2)),
+                        indent: Indent::Owned(Indentation::new(b' ', 2, "\n")),
                         write_indent: false,
                         allow_primitive: true,
                         expand_empty_elements: false,
+                        rename_fields: None,
                     };
 
                     let result = $data.serialize(ser).unwrap();
@@ -1069,10 +1123,11 @@ pub(super) mod tests {
                     let ser = ContentSerializer {
                         writer: &mut buffer,
                         level: QuoteLevel::Full,
-                        indent: Indent::Owned(Indentation::new(b' ', 2)),
+                        indent: Indent::Owned(Indentation::new(b' ', 2, "\n")),
                         write_indent: false,
                         allow_primitive: true,
                         expand_empty_elements: false,
+                        rename_fields: None,
                     };
 
                     match $data.serialize(ser).unwrap_err() {
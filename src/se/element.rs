@@ -5,7 +5,7 @@ use crate::se::content::ContentSerializer;
 use crate::se::key::QNameSerializer;
 use crate::se::simple_type::{QuoteTarget, SimpleSeq, SimpleTypeSerializer};
 use crate::se::text::TextSerializer;
-use crate::se::{SeError, WriteResult, XmlName};
+use crate::se::{SeError, WriteResult, XmlName, COMMENT_NAME, PI_NAME};
 use serde::ser::{
     Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
     SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
@@ -155,12 +155,21 @@ impl<'w, 'k, W: Write> Serializer for ElementSerializer<'w, 'k, W> {
         }
     }
 
+    /// Writes comments and processing instructions for [`Comment`] and [`Pi`]
+    /// types without wrapping them in `key` tag. Other newtypes are serialized
+    /// as an inner value.
+    ///
+    /// [`Comment`]: crate::se::Comment
+    /// [`Pi`]: crate::se::Pi
     fn serialize_newtype_struct<T: ?Sized + Serialize>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error> {
-        value.serialize(self)
+        match name {
+            COMMENT_NAME | PI_NAME => self.ser.write_markup(name, value),
+            _ => value.serialize(self),
+        }
     }
 
     /// Always returns [`SeError::Unsupported`]. Newtype variants can be serialized
@@ -445,6 +454,7 @@ impl<'w, 'k, W: Write> Struct<'w, 'k, W> {
             write_indent: self.write_indent,
             allow_primitive: true,
             expand_empty_elements: self.ser.ser.expand_empty_elements,
+            rename_fields: self.ser.ser.rename_fields,
         };
 
         if key == TEXT_KEY {
@@ -474,7 +484,13 @@ impl<'w, 'k, W: Write> SerializeStruct for Struct<'w, 'k, W> {
     where
         T: ?Sized + Serialize,
     {
-        self.write_field(key, value)
+        match self.ser.ser.rename_fields {
+            Some(rename) if key != TEXT_KEY && key != VALUE_KEY => match key.strip_prefix('@') {
+                Some(name) => self.write_field(&format!("@{}", rename(name)), value),
+                None => self.write_field(&rename(key), value),
+            },
+            _ => self.write_field(key, value),
+        }
     }
 
     fn end(mut self) -> Result<Self::Ok, Self::Error> {
@@ -637,6 +653,7 @@ mod tests {
                             write_indent: false,
                             allow_primitive: true,
                             expand_empty_elements: false,
+                            rename_fields: None,
                         },
                         key: XmlName("root"),
                     };
@@ -663,6 +680,7 @@ mod tests {
                             write_indent: false,
                             allow_primitive: true,
                             expand_empty_elements: false,
+                            rename_fields: None,
                         },
                         key: XmlName("root"),
                     };
@@ -1350,10 +1368,11 @@ mod tests {
                         ser: ContentSerializer {
                             writer: &mut buffer,
                             level: QuoteLevel::Full,
-                            indent: Indent::Owned(Indentation::new(b' ', 2)),
+                            indent: Indent::Owned(Indentation::new(b' ', 2, "\n")),
                             write_indent: false,
                             allow_primitive: true,
                             expand_empty_elements: false,
+                            rename_fields: None,
                         },
                         key: XmlName("root"),
                     };
@@ -1376,10 +1395,11 @@ mod tests {
                         ser: ContentSerializer {
                             writer: &mut buffer,
                             level: QuoteLevel::Full,
-                            indent: Indent::Owned(Indentation::new(b' ', 2)),
+                            indent: Indent::Owned(Indentation::new(b' ', 2, "\n")),
                             write_indent: false,
                             allow_primitive: true,
                             expand_empty_elements: false,
+                            rename_fields: None,
                         },
                         key: XmlName("root"),
                     };
@@ -2083,6 +2103,7 @@ mod tests {
                             write_indent: false,
                             allow_primitive: true,
                             expand_empty_elements: true,
+                            rename_fields: None,
                         },
                         key: XmlName("root"),
                     };
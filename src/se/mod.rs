@@ -82,6 +82,7 @@ mod text;
 use self::content::ContentSerializer;
 use self::element::{ElementSerializer, Map, Struct, Tuple};
 use crate::de::TEXT_KEY;
+use crate::utils::{is_xml11_name_char, is_xml11_name_start_char};
 use crate::writer::{Indentation, ToFmtWrite};
 use serde::ser::{self, Serialize};
 use serde::serde_if_integer128;
@@ -186,6 +187,12 @@ where
 
 /// Serialize struct into a `String`.
 ///
+/// The name of the root element is deduced from the name of a struct, so
+/// primitive types, sequences, unnamed tuples and maps cannot be serialized with
+/// this function and an [`SeError::Unsupported`] error is returned for them,
+/// because it is impossible to produce a well-formed XML document for them.
+/// Use [`to_string_with_root`] to serialize such types.
+///
 /// # Examples
 ///
 /// ```
@@ -231,6 +238,11 @@ where
 ///
 /// Returns the classification of the last written type.
 ///
+/// Top-level sequences are written item by item directly into the `writer`,
+/// so they can be of any size. Note, however, that the elements of a struct
+/// are collected in a buffer until the struct is finished, because attributes
+/// of the struct can be defined after its elements.
+///
 /// # Examples
 ///
 /// ```
@@ -401,6 +413,111 @@ impl WriteResult {
     }
 }
 
+/// Name of the newtype struct which content is written as an XML comment
+pub(crate) const COMMENT_NAME: &str = "$quick_xml::Comment";
+/// Name of the newtype struct which content is written as an XML processing instruction
+pub(crate) const PI_NAME: &str = "$quick_xml::Pi";
+
+/// A marker type that is serialized as an XML comment `<!--...-->` in the place
+/// where it is found.
+///
+/// Comments are written in the document order, so they could be placed between
+/// elements of a struct:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// # use serde::Serialize;
+/// use quick_xml::se::{to_string, Comment};
+///
+/// #[derive(Serialize)]
+/// struct Root {
+///     first: u32,
+///     note: Comment,
+///     second: u32,
+/// }
+///
+/// let data = Root {
+///     first: 1,
+///     note: Comment(" between fields ".into()),
+///     second: 2,
+/// };
+/// assert_eq!(
+///     to_string(&data).unwrap(),
+///     "<Root><first>1</first><!-- between fields --><second>2</second></Root>"
+/// );
+/// ```
+///
+/// The name of the field is not written. The text is not escaped, so an error
+/// is returned if it contains `--` or ends with `-`, as XML does not allow that.
+///
+/// Other serializers see this type as a newtype struct around a string.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Comment(pub String);
+
+impl Serialize for Comment {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(COMMENT_NAME, &self.0)
+    }
+}
+
+/// A marker type that is serialized as an XML processing instruction
+/// `<?target data?>` in the place where it is found.
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// # use serde::Serialize;
+/// use quick_xml::se::{to_string, Pi};
+///
+/// #[derive(Serialize)]
+/// struct Root {
+///     style: Pi,
+///     field: u32,
+/// }
+///
+/// let data = Root {
+///     style: Pi {
+///         target: "xml-stylesheet".into(),
+///         data: r#"href="style.css""#.into(),
+///     },
+///     field: 42,
+/// };
+/// assert_eq!(
+///     to_string(&data).unwrap(),
+///     r#"<Root><?xml-stylesheet href="style.css"?><field>42</field></Root>"#
+/// );
+/// ```
+///
+/// The name of the field is not written. The data is not escaped, so an error
+/// is returned if it contains `?>`. An error is also returned if the target is
+/// not a valid XML name.
+///
+/// Other serializers see this type as a newtype struct around a string with
+/// the target and the data separated by a space.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pi {
+    /// The target of the processing instruction
+    pub target: String,
+    /// The content of the processing instruction after the target. If empty,
+    /// only the target is written
+    pub data: String,
+}
+
+impl Serialize for Pi {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // The target is checked here, because a space in it cannot be
+        // distinguished from the separator after joining it with the data
+        if let Err(SeError::Unsupported(e)) = XmlName::try_from(&self.target) {
+            return Err(ser::Error::custom(e));
+        }
+        if self.data.is_empty() {
+            serializer.serialize_newtype_struct(PI_NAME, &self.target)
+        } else {
+            let content = format!("{} {}", self.target, self.data);
+            serializer.serialize_newtype_struct(PI_NAME, &content)
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Implements serialization method by forwarding it to the serializer created by
@@ -415,47 +532,6 @@ macro_rules! forward {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
-/// Almost all characters can form a name. Citation from <https://www.w3.org/TR/xml11/#sec-xml11>:
-///
-/// > The overall philosophy of names has changed since XML 1.0. Whereas XML 1.0
-/// > provided a rigid definition of names, wherein everything that was not permitted
-/// > was forbidden, XML 1.1 names are designed so that everything that is not
-/// > forbidden (for a specific reason) is permitted. Since Unicode will continue
-/// > to grow past version 4.0, further changes to XML can be avoided by allowing
-/// > almost any character, including those not yet assigned, in names.
-///
-/// <https://www.w3.org/TR/xml11/#NT-NameStartChar>
-const fn is_xml11_name_start_char(ch: char) -> bool {
-    match ch {
-        ':'
-        | 'A'..='Z'
-        | '_'
-        | 'a'..='z'
-        | '\u{00C0}'..='\u{00D6}'
-        | '\u{00D8}'..='\u{00F6}'
-        | '\u{00F8}'..='\u{02FF}'
-        | '\u{0370}'..='\u{037D}'
-        | '\u{037F}'..='\u{1FFF}'
-        | '\u{200C}'..='\u{200D}'
-        | '\u{2070}'..='\u{218F}'
-        | '\u{2C00}'..='\u{2FEF}'
-        | '\u{3001}'..='\u{D7FF}'
-        | '\u{F900}'..='\u{FDCF}'
-        | '\u{FDF0}'..='\u{FFFD}'
-        | '\u{10000}'..='\u{EFFFF}' => true,
-        _ => false,
-    }
-}
-/// <https://www.w3.org/TR/xml11/#NT-NameChar>
-const fn is_xml11_name_char(ch: char) -> bool {
-    match ch {
-        '-' | '.' | '0'..='9' | '\u{00B7}' | '\u{0300}'..='\u{036F}' | '\u{203F}'..='\u{2040}' => {
-            true
-        }
-        _ => is_xml11_name_start_char(ch),
-    }
-}
-
 /// Helper struct to self-defense from errors
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub(self) struct XmlName<'n>(&'n str);
@@ -481,6 +557,32 @@ impl<'n> XmlName<'n> {
     }
 }
 
+/// Checks that `text` can be written as a content of an XML comment
+fn check_comment(text: &str) -> Result<(), SeError> {
+    if text.contains("--") || text.ends_with('-') {
+        return Err(SeError::Unsupported(
+            format!("comment cannot contain `--` or end with `-`: `{}`", text).into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `target` and `data` can be written as an XML processing instruction
+fn check_pi<'n>(target: &'n str, data: &str) -> Result<XmlName<'n>, SeError> {
+    let target = XmlName::try_from(target)?;
+    if target.0.eq_ignore_ascii_case("xml") {
+        return Err(SeError::Unsupported(
+            "processing instruction target cannot be `xml`".into(),
+        ));
+    }
+    if data.contains("?>") {
+        return Err(SeError::Unsupported(
+            format!("processing instruction cannot contain `?>`: `{}`", data).into(),
+        ));
+    }
+    Ok(target)
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub(crate) enum Indent<'i> {
@@ -522,11 +624,11 @@ impl<'i> Indent<'i> {
         match self {
             Self::None => {}
             Self::Owned(i) => {
-                writer.write_char('\n')?;
+                writer.write_str(i.newline())?;
                 writer.write_str(from_utf8(i.current())?)?;
             }
             Self::Borrow(i) => {
-                writer.write_char('\n')?;
+                writer.write_str(i.newline())?;
                 writer.write_str(from_utf8(i.current())?)?;
             }
         }
@@ -548,9 +650,29 @@ pub struct Serializer<'w, 'r, W: Write> {
 impl<'w, 'r, W: Write> Serializer<'w, 'r, W> {
     /// Creates a new `Serializer` that uses struct name as a root tag name.
     ///
-    /// Note, that attempt to serialize a non-struct (including unit structs
-    /// and newtype structs) will end up to an error. Use `with_root` to create
-    /// serializer with explicitly defined root element name
+    /// Note, that attempt to serialize a primitive type, a sequence, an unnamed
+    /// tuple or a map will end up to an [`SeError::Unsupported`] error, because
+    /// the name of the root element cannot be deduced for them. Use [`with_root`]
+    /// to create serializer with explicitly defined root element name.
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// # use serde::Serialize;
+    /// # use quick_xml::se::{Serializer, SeError};
+    /// let mut buffer = String::new();
+    ///
+    /// match 42.serialize(Serializer::new(&mut buffer)) {
+    ///     Err(SeError::Unsupported(e)) => assert_eq!(
+    ///         e,
+    ///         "cannot serialize `i32` without defined root tag, use `Serializer::with_root` to define it"
+    ///     ),
+    ///     x => panic!("Expected `Err(Unsupported(_))`, but got `{:?}`", x),
+    /// }
+    /// // Nothing was written
+    /// assert_eq!(buffer, "");
+    /// ```
+    ///
+    /// [`with_root`]: Self::with_root
     pub fn new(writer: &'w mut W) -> Self {
         Self {
             ser: ContentSerializer {
@@ -560,6 +682,7 @@ impl<'w, 'r, W: Write> Serializer<'w, 'r, W> {
                 write_indent: false,
                 allow_primitive: true,
                 expand_empty_elements: false,
+                rename_fields: None,
             },
             root_tag: None,
         }
@@ -626,6 +749,7 @@ impl<'w, 'r, W: Write> Serializer<'w, 'r, W> {
                 write_indent: false,
                 allow_primitive: true,
                 expand_empty_elements: false,
+                rename_fields: None,
             },
             root_tag: root_tag.map(|tag| XmlName::try_from(tag)).transpose()?,
         })
@@ -664,9 +788,59 @@ impl<'w, 'r, W: Write> Serializer<'w, 'r, W> {
         self
     }
 
+    /// Sets a function that converts names of struct fields to names of XML
+    /// elements and attributes, for example, to write `snake_case` fields as
+    /// `kebab-case` XML names without a `#[serde(rename)]` on each field.
+    ///
+    /// The function is applied to all fields of all structs in the document,
+    /// but not to the special `$text` and `$value` fields and not to keys of maps.
+    /// Because serde handles `#[serde(flatten)]` fields as maps, names of fields
+    /// of flattened structs are also not converted.
+    /// For fields which names starts with `@` the function receives the name
+    /// without `@`. Fields renamed by serde are also passed to the function.
+    ///
+    /// Use the same function in [`Deserializer::rename_fields`] to read
+    /// the document back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// # use serde::Serialize;
+    /// # use quick_xml::se::Serializer;
+    ///
+    /// #[derive(Debug, PartialEq, Serialize)]
+    /// struct Book {
+    ///     #[serde(rename = "@page_count")]
+    ///     page_count: u32,
+    ///     first_author: String,
+    /// }
+    ///
+    /// let mut buffer = String::new();
+    /// let mut ser = Serializer::with_root(&mut buffer, Some("book")).unwrap();
+    /// ser.rename_fields(|name| name.replace('_', "-"));
+    ///
+    /// let data = Book {
+    ///     page_count: 42,
+    ///     first_author: "Douglas Adams".into(),
+    /// };
+    ///
+    /// data.serialize(ser).unwrap();
+    /// assert_eq!(
+    ///     buffer,
+    ///     r#"<book page-count="42"><first-author>Douglas Adams</first-author></book>"#
+    /// );
+    /// ```
+    ///
+    /// [`Deserializer::rename_fields`]: crate::de::Deserializer::rename_fields
+    pub fn rename_fields(&mut self, rename: fn(&str) -> String) -> &mut Self {
+        self.ser.rename_fields = Some(rename);
+        self
+    }
+
     /// Configure indent for a serializer
     pub fn indent(&mut self, indent_char: char, indent_size: usize) -> &mut Self {
-        self.ser.indent = Indent::Owned(Indentation::new(indent_char as u8, indent_size));
+        self.ser.indent = Indent::Owned(Indentation::new(indent_char as u8, indent_size, "\n"));
         self
     }
 
@@ -678,6 +852,105 @@ impl<'w, 'r, W: Write> Serializer<'w, 'r, W> {
         self
     }
 
+    /// Writes a comment before the root element. Can be called several times,
+    /// comments are written in the order of calls. When indentation is
+    /// [configured](Self::indent), each prologue item and the root element are
+    /// written on its own line.
+    ///
+    /// Because serialization consumes the serializer, prologue can be written
+    /// only before the value.
+    ///
+    /// Returns [`SeError::Unsupported`] if `text` contains `--` or ends with `-`,
+    /// because such comments are not allowed by the [XML specification].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// # use serde::Serialize;
+    /// # use quick_xml::se::Serializer;
+    /// #[derive(Serialize)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// let mut buffer = String::new();
+    /// let mut ser = Serializer::new(&mut buffer);
+    /// ser.indent(' ', 2);
+    /// ser.write_prologue_comment(" Generated file, do not edit ")?;
+    ///
+    /// Config { port: 8080 }.serialize(ser)?;
+    /// assert_eq!(
+    ///     buffer,
+    ///     "<!-- Generated file, do not edit -->\n\
+    ///      <Config>\n  \
+    ///        <port>8080</port>\n\
+    ///      </Config>"
+    /// );
+    /// # Ok::<(), quick_xml::se::SeError>(())
+    /// ```
+    ///
+    /// [XML specification]: https://www.w3.org/TR/xml11/#sec-comments
+    pub fn write_prologue_comment(&mut self, text: &str) -> Result<&mut Self, SeError> {
+        check_comment(text)?;
+        self.write_prologue("<!--", text, "-->")?;
+        Ok(self)
+    }
+
+    /// Writes a processing instruction `<?target data?>` before the root element.
+    /// Can be called several times, processing instructions and [comments] are
+    /// written in the order of calls. If `data` is empty, only target is written.
+    ///
+    /// Returns [`SeError::Unsupported`] if `target` is not a valid [XML name],
+    /// if it is `xml` in any case (use [`Writer`] to write an XML declaration),
+    /// or if `data` contains `?>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// # use serde::Serialize;
+    /// # use quick_xml::se::Serializer;
+    /// #[derive(Serialize)]
+    /// struct Page {}
+    ///
+    /// let mut buffer = String::new();
+    /// let mut ser = Serializer::new(&mut buffer);
+    /// ser.write_prologue_pi("xml-stylesheet", r#"href="style.css""#)?;
+    ///
+    /// Page {}.serialize(ser)?;
+    /// assert_eq!(buffer, r#"<?xml-stylesheet href="style.css"?><Page/>"#);
+    /// # Ok::<(), quick_xml::se::SeError>(())
+    /// ```
+    ///
+    /// [comments]: Self::write_prologue_comment
+    /// [XML name]: https://www.w3.org/TR/xml11/#NT-Name
+    /// [`Writer`]: crate::Writer
+    pub fn write_prologue_pi(&mut self, target: &str, data: &str) -> Result<&mut Self, SeError> {
+        let target = check_pi(target, data)?;
+        if data.is_empty() {
+            self.write_prologue("<?", target.0, "?>")?;
+        } else {
+            self.write_prologue("<?", target.0, " ")?;
+            self.ser.writer.write_str(data)?;
+            self.ser.writer.write_str("?>")?;
+        }
+        Ok(self)
+    }
+
+    /// Writes a prologue item, separating it from the previous one by the indent
+    fn write_prologue(&mut self, before: &str, value: &str, after: &str) -> Result<(), SeError> {
+        if self.ser.write_indent {
+            self.ser.indent.write_indent(&mut self.ser.writer)?;
+        }
+        self.ser.writer.write_str(before)?;
+        self.ser.writer.write_str(value)?;
+        self.ser.writer.write_str(after)?;
+        // The next prologue item or the root element should be written on a new line
+        self.ser.write_indent = true;
+        Ok(())
+    }
+
     /// Set the indent object for a serializer
     pub(crate) fn set_indent(&mut self, indent: Indent<'r>) -> &mut Self {
         self.ser.indent = indent;
@@ -691,7 +964,11 @@ impl<'w, 'r, W: Write> Serializer<'w, 'r, W> {
             Ok(ElementSerializer { ser: self.ser, key })
         } else {
             Err(SeError::Unsupported(
-                format!("cannot serialize {} without defined root tag", err).into(),
+                format!(
+                    "cannot serialize {} without defined root tag, use `Serializer::with_root` to define it",
+                    err
+                )
+                .into(),
             ))
         }
     }
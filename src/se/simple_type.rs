@@ -3,7 +3,7 @@
 //! [simple types]: https://www.w3schools.com/xml/el_simpletype.asp
 //! [as defined]: https://www.w3.org/TR/xmlschema11-1/#Simple_Type_Definition
 
-use crate::escape::_escape;
+use crate::escape::escape_with;
 use crate::se::{QuoteLevel, SeError};
 use serde::ser::{
     Impossible, Serialize, SerializeSeq, SerializeTuple, SerializeTupleStruct,
@@ -30,7 +30,7 @@ fn escape_item(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str>
     use QuoteTarget::*;
 
     match (target, level) {
-        (_, Full) => _escape(value, |ch| match ch {
+        (_, Full) => escape_with(value, |ch| match ch {
             // Spaces used as delimiters of list items, cannot be used in the item
             b' ' | b'\r' | b'\n' | b'\t' => true,
             // Required characters to escape
@@ -38,22 +38,22 @@ fn escape_item(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str>
             _ => false,
         }),
         //----------------------------------------------------------------------
-        (Text, Partial) => _escape(value, |ch| match ch {
+        (Text, Partial) => escape_with(value, |ch| match ch {
             // Spaces used as delimiters of list items, cannot be used in the item
             b' ' | b'\r' | b'\n' | b'\t' => true,
             // Required characters to escape
             b'&' | b'<' | b'>' => true,
             _ => false,
         }),
-        (Text, Minimal) => _escape(value, |ch| match ch {
+        (Text, Minimal) => escape_cdata_end(escape_with(value, |ch| match ch {
             // Spaces used as delimiters of list items, cannot be used in the item
             b' ' | b'\r' | b'\n' | b'\t' => true,
             // Required characters to escape
             b'&' | b'<' => true,
             _ => false,
-        }),
+        })),
         //----------------------------------------------------------------------
-        (DoubleQAttr, Partial) => _escape(value, |ch| match ch {
+        (DoubleQAttr, Partial) => escape_with(value, |ch| match ch {
             // Spaces used as delimiters of list items, cannot be used in the item
             b' ' | b'\r' | b'\n' | b'\t' => true,
             // Required characters to escape
@@ -62,7 +62,7 @@ fn escape_item(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str>
             b'"' => true,
             _ => false,
         }),
-        (DoubleQAttr, Minimal) => _escape(value, |ch| match ch {
+        (DoubleQAttr, Minimal) => escape_with(value, |ch| match ch {
             // Spaces used as delimiters of list items, cannot be used in the item
             b' ' | b'\r' | b'\n' | b'\t' => true,
             // Required characters to escape
@@ -72,7 +72,7 @@ fn escape_item(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str>
             _ => false,
         }),
         //----------------------------------------------------------------------
-        (SingleQAttr, Partial) => _escape(value, |ch| match ch {
+        (SingleQAttr, Partial) => escape_with(value, |ch| match ch {
             // Spaces used as delimiters of list items
             b' ' | b'\r' | b'\n' | b'\t' => true,
             // Required characters to escape
@@ -81,7 +81,7 @@ fn escape_item(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str>
             b'\'' => true,
             _ => false,
         }),
-        (SingleQAttr, Minimal) => _escape(value, |ch| match ch {
+        (SingleQAttr, Minimal) => escape_with(value, |ch| match ch {
             // Spaces used as delimiters of list items
             b' ' | b'\r' | b'\n' | b'\t' => true,
             // Required characters to escape
@@ -93,37 +93,46 @@ fn escape_item(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str>
     }
 }
 
+/// Escapes `>` in the `]]>` sequence, which is not allowed in a text content
+fn escape_cdata_end(value: Cow<str>) -> Cow<str> {
+    if value.contains("]]>") {
+        Cow::Owned(value.replace("]]>", "]]&gt;"))
+    } else {
+        value
+    }
+}
+
 /// Escapes XSD simple type value
 fn escape_list(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str> {
     use QuoteLevel::*;
     use QuoteTarget::*;
 
     match (target, level) {
-        (_, Full) => _escape(value, |ch| match ch {
+        (_, Full) => escape_with(value, |ch| match ch {
             // Required characters to escape
             b'&' | b'<' | b'>' | b'\'' | b'\"' => true,
             _ => false,
         }),
         //----------------------------------------------------------------------
-        (Text, Partial) => _escape(value, |ch| match ch {
+        (Text, Partial) => escape_with(value, |ch| match ch {
             // Required characters to escape
             b'&' | b'<' | b'>' => true,
             _ => false,
         }),
-        (Text, Minimal) => _escape(value, |ch| match ch {
+        (Text, Minimal) => escape_cdata_end(escape_with(value, |ch| match ch {
             // Required characters to escape
             b'&' | b'<' => true,
             _ => false,
-        }),
+        })),
         //----------------------------------------------------------------------
-        (DoubleQAttr, Partial) => _escape(value, |ch| match ch {
+        (DoubleQAttr, Partial) => escape_with(value, |ch| match ch {
             // Required characters to escape
             b'&' | b'<' | b'>' => true,
             // Double quoted attribute should escape quote
             b'"' => true,
             _ => false,
         }),
-        (DoubleQAttr, Minimal) => _escape(value, |ch| match ch {
+        (DoubleQAttr, Minimal) => escape_with(value, |ch| match ch {
             // Required characters to escape
             b'&' | b'<' => true,
             // Double quoted attribute should escape quote
@@ -131,14 +140,14 @@ fn escape_list(value: &str, target: QuoteTarget, level: QuoteLevel) -> Cow<str>
             _ => false,
         }),
         //----------------------------------------------------------------------
-        (SingleQAttr, Partial) => _escape(value, |ch| match ch {
+        (SingleQAttr, Partial) => escape_with(value, |ch| match ch {
             // Required characters to escape
             b'&' | b'<' | b'>' => true,
             // Single quoted attribute should escape quote
             b'\'' => true,
             _ => false,
         }),
-        (SingleQAttr, Minimal) => _escape(value, |ch| match ch {
+        (SingleQAttr, Minimal) => escape_with(value, |ch| match ch {
             // Required characters to escape
             b'&' | b'<' => true,
             // Single quoted attribute should escape quote
@@ -748,6 +757,15 @@ mod tests {
                 );
             }
 
+            /// `]]>` is not allowed in a text, so `>` should be escaped
+            #[test]
+            fn text_cdata_end() {
+                assert_eq!(
+                    escape_item("a]]>b]>c>", QuoteTarget::Text, QuoteLevel::Minimal),
+                    "a]]&gt;b]>c>"
+                );
+            }
+
             #[test]
             fn double_quote_attr() {
                 assert_eq!(
@@ -871,6 +889,15 @@ mod tests {
                 );
             }
 
+            /// `]]>` is not allowed in a text, so `>` should be escaped
+            #[test]
+            fn text_cdata_end() {
+                assert_eq!(
+                    escape_list("a]]>b]>c>", QuoteTarget::Text, QuoteLevel::Minimal),
+                    "a]]&gt;b]>c>"
+                );
+            }
+
             #[test]
             fn double_quote_attr() {
                 assert_eq!(
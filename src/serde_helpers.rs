@@ -2,6 +2,11 @@
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "chrono-types")]
+mod xs;
+#[cfg(feature = "chrono-types")]
+pub use xs::{xs_date_time, xs_duration};
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! deserialize_variant {
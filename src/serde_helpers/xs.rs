@@ -0,0 +1,562 @@
+//! Helpers to (de)serialize [`chrono`] types from and to lexical representations
+//! of [XML Schema built-in datatypes].
+//!
+//! [XML Schema built-in datatypes]: https://www.w3.org/TR/xmlschema11-2/#built-in-datatypes
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Timelike};
+use std::fmt::Write;
+
+/// Characters that XML considers as whitespaces. Values of XSD types are
+/// [collapsed], so leading and trailing whitespaces are ignored.
+///
+/// [collapsed]: https://www.w3.org/TR/xmlschema11-2/#rf-whiteSpace
+const WHITESPACES: &[char] = &[' ', '\t', '\r', '\n'];
+
+/// Parses exactly `len` ASCII digits from the start of `s`.
+fn digits(s: &str, len: usize) -> Option<(u32, &str)> {
+    if s.len() < len || !s.as_bytes()[..len].iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    Some((s[..len].parse().ok()?, &s[len..]))
+}
+
+/// Parses a non-empty sequence of ASCII digits from the start of `s`.
+fn number(s: &str) -> Option<(u64, &str)> {
+    let len = s.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 {
+        return None;
+    }
+    Some((s[..len].parse().ok()?, &s[len..]))
+}
+
+/// Parses a fractional part of seconds (digits after `.`) into nanoseconds.
+/// Digits after the ninth are ignored.
+fn fraction(s: &str) -> Option<(u32, &str)> {
+    let len = s.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 {
+        return None;
+    }
+    let nanos = s[..len]
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |acc, digit| acc * 10 + (digit - b'0') as u32);
+    Some((nanos, &s[len..]))
+}
+
+/// Writes nanoseconds as a fractional part of seconds, including leading `.`,
+/// without trailing zeroes. Nothing is written if `nanos` is zero.
+fn write_fraction(out: &mut String, nanos: u32) {
+    if nanos != 0 {
+        let fraction = format!(".{:09}", nanos);
+        out.push_str(fraction.trim_end_matches('0'));
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Parses a lexical representation of [`xs:dateTime`]. Value without timezone
+/// is considered to be in UTC.
+///
+/// [`xs:dateTime`]: https://www.w3.org/TR/xmlschema11-2/#dateTime
+fn parse_date_time(s: &str) -> Option<DateTime<FixedOffset>> {
+    let s = s.trim_matches(WHITESPACES);
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    // Year has at least 4 digits and leading zeroes are not allowed in longer years
+    let year_len = s.bytes().take_while(u8::is_ascii_digit).count();
+    if year_len < 4 || (year_len > 4 && s.starts_with('0')) {
+        return None;
+    }
+    let year: i32 = s[..year_len].parse().ok()?;
+    let year = if negative { -year } else { year };
+
+    let s = s[year_len..].strip_prefix('-')?;
+    let (month, s) = digits(s, 2)?;
+    let s = s.strip_prefix('-')?;
+    let (day, s) = digits(s, 2)?;
+    let s = s.strip_prefix('T')?;
+    let (hour, s) = digits(s, 2)?;
+    let s = s.strip_prefix(':')?;
+    let (minute, s) = digits(s, 2)?;
+    let s = s.strip_prefix(':')?;
+    let (second, s) = digits(s, 2)?;
+    let (nanos, s) = match s.strip_prefix('.') {
+        Some(s) => fraction(s)?,
+        None => (0, s),
+    };
+    let offset = match s {
+        "" | "Z" => FixedOffset::east_opt(0)?,
+        _ => {
+            let (sign, s) = match (s.strip_prefix('+'), s.strip_prefix('-')) {
+                (Some(s), _) => (1, s),
+                (_, Some(s)) => (-1, s),
+                _ => return None,
+            };
+            let (hours, s) = digits(s, 2)?;
+            let s = s.strip_prefix(':')?;
+            let (minutes, s) = digits(s, 2)?;
+            if !s.is_empty() || minutes > 59 || hours > 14 || (hours == 14 && minutes != 0) {
+                return None;
+            }
+            FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60) as i32)?
+        }
+    };
+
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+    // 24:00:00 is the first moment of the next day
+    let date_time = if hour == 24 && minute == 0 && second == 0 && nanos == 0 {
+        date.succ_opt()?.and_time(NaiveTime::MIN)
+    } else {
+        date.and_time(NaiveTime::from_hms_nano_opt(hour, minute, second, nanos)?)
+    };
+    offset.from_local_datetime(&date_time).single()
+}
+
+/// Formats `value` as a canonical representation of [`xs:dateTime`].
+/// Returns `None` if offset of the value contains seconds, which cannot be
+/// represented in XML.
+///
+/// [`xs:dateTime`]: https://www.w3.org/TR/xmlschema11-2/#dateTime
+fn format_date_time(value: &DateTime<FixedOffset>) -> Option<String> {
+    let mut out = String::new();
+    let year = value.year();
+    if year < 0 {
+        out.push('-');
+    }
+    // Writing to a string cannot fail
+    let _ = write!(
+        out,
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year.unsigned_abs(),
+        value.month(),
+        value.day(),
+        value.hour(),
+        value.minute(),
+        value.second(),
+    );
+    // Leap second is represented by nanoseconds greater than a second,
+    // but XML does not allow 60 seconds, so ignore it
+    write_fraction(&mut out, value.nanosecond() % 1_000_000_000);
+
+    let offset = value.offset().local_minus_utc();
+    if offset % 60 != 0 {
+        return None;
+    }
+    if offset == 0 {
+        out.push('Z');
+    } else {
+        let sign = if offset < 0 { '-' } else { '+' };
+        let minutes = offset.unsigned_abs() / 60;
+        let _ = write!(out, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60);
+    }
+    Some(out)
+}
+
+/// Parses a lexical representation of [`xs:duration`]. Returns `Err(())`
+/// for valid durations that contains years or months, because they cannot
+/// be converted to a fixed duration, and `Ok(None)` for invalid durations.
+///
+/// [`xs:duration`]: https://www.w3.org/TR/xmlschema11-2/#duration
+fn parse_duration(s: &str) -> Result<Option<Duration>, ()> {
+    let s = s.trim_matches(WHITESPACES);
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let s = match s.strip_prefix('P') {
+        Some(s) if !s.is_empty() && !s.ends_with('T') => s,
+        _ => return Ok(None),
+    };
+    let (date, time) = match s.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+
+    let mut has_years_or_months = false;
+    let mut secs: u64 = 0;
+    let mut nanos = 0;
+
+    // Each designator may appear only once and only in this order
+    let mut rest = date;
+    for (designator, multiplier) in [('Y', 0), ('M', 0), ('D', 86400)] {
+        if let Some((value, s)) = number(rest) {
+            if let Some(s) = s.strip_prefix(designator) {
+                has_years_or_months |= multiplier == 0 && value != 0;
+                secs = match value
+                    .checked_mul(multiplier)
+                    .and_then(|v| v.checked_add(secs))
+                {
+                    Some(secs) => secs,
+                    None => return Ok(None),
+                };
+                rest = s;
+            }
+        }
+    }
+    if !rest.is_empty() {
+        return Ok(None);
+    }
+    if let Some(time) = time {
+        let mut rest = time;
+        for (designator, multiplier) in [('H', 3600), ('M', 60), ('S', 1)] {
+            if let Some((value, s)) = number(rest) {
+                let (subsec_nanos, s) = match s.strip_prefix('.') {
+                    Some(s) if designator == 'S' => match fraction(s) {
+                        Some(f) => f,
+                        None => return Ok(None),
+                    },
+                    _ => (0, s),
+                };
+                if let Some(s) = s.strip_prefix(designator) {
+                    secs = match value
+                        .checked_mul(multiplier)
+                        .and_then(|v| v.checked_add(secs))
+                    {
+                        Some(secs) => secs,
+                        None => return Ok(None),
+                    };
+                    nanos = subsec_nanos;
+                    rest = s;
+                }
+            }
+        }
+        if !rest.is_empty() {
+            return Ok(None);
+        }
+    }
+
+    if has_years_or_months {
+        return Err(());
+    }
+    let duration = i64::try_from(secs)
+        .ok()
+        .and_then(|secs| Duration::new(secs, nanos));
+    Ok(if negative {
+        duration.map(|d| -d)
+    } else {
+        duration
+    })
+}
+
+/// Formats `value` as a representation of [`xs:duration`] which uses days,
+/// hours, minutes and seconds.
+///
+/// [`xs:duration`]: https://www.w3.org/TR/xmlschema11-2/#duration
+fn format_duration(value: &Duration) -> String {
+    let mut out = String::new();
+    let value = if *value < Duration::zero() {
+        out.push('-');
+        -*value
+    } else {
+        *value
+    };
+    out.push('P');
+
+    let secs = value.num_seconds();
+    let nanos = value.subsec_nanos() as u32;
+    let days = secs / 86400;
+    let hours = secs % 86400 / 3600;
+    let minutes = secs % 3600 / 60;
+    let seconds = secs % 60;
+
+    // Writing to a string cannot fail
+    if days != 0 {
+        let _ = write!(out, "{}D", days);
+    }
+    if hours != 0 || minutes != 0 || seconds != 0 || nanos != 0 || days == 0 {
+        out.push('T');
+        if hours != 0 {
+            let _ = write!(out, "{}H", hours);
+        }
+        if minutes != 0 {
+            let _ = write!(out, "{}M", minutes);
+        }
+        if seconds != 0 || nanos != 0 || (hours == 0 && minutes == 0) {
+            let _ = write!(out, "{}", seconds);
+            write_fraction(&mut out, nanos);
+            out.push('S');
+        }
+    }
+    out
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Provides functions to (de)serialize [`DateTime<FixedOffset>`] from and to
+/// a lexical representation of an [`xs:dateTime`] type. Intended to use with
+/// `#[serde(with = "quick_xml::serde_helpers::xs_date_time")]`.
+///
+/// Values without timezone are deserialized as values in UTC. Values are always
+/// serialized with a timezone, UTC is written as `Z`. Leading and trailing
+/// whitespaces are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use chrono::{DateTime, FixedOffset};
+/// use quick_xml::de::from_str;
+/// use quick_xml::se::to_string;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(rename = "@at", with = "quick_xml::serde_helpers::xs_date_time")]
+///     at: DateTime<FixedOffset>,
+/// }
+///
+/// let event: Event = from_str(r#"<Event at="2024-02-29T13:45:00.5+03:00"/>"#).unwrap();
+/// assert_eq!(
+///     event.at,
+///     DateTime::parse_from_rfc3339("2024-02-29T13:45:00.5+03:00").unwrap()
+/// );
+///
+/// assert_eq!(
+///     to_string(&event).unwrap(),
+///     r#"<Event at="2024-02-29T13:45:00.5+03:00"/>"#
+/// );
+/// ```
+///
+/// [`DateTime<FixedOffset>`]: chrono::DateTime
+/// [`xs:dateTime`]: https://www.w3.org/TR/xmlschema11-2/#dateTime
+pub mod xs_date_time {
+    use chrono::{DateTime, FixedOffset};
+    use serde::de::{Error as _, Unexpected};
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes `value` as an [`xs:dateTime`]. Returns an error if offset of
+    /// the value contains seconds.
+    ///
+    /// [`xs:dateTime`]: https://www.w3.org/TR/xmlschema11-2/#dateTime
+    pub fn serialize<S>(value: &DateTime<FixedOffset>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match super::format_date_time(value) {
+            Some(s) => serializer.serialize_str(&s),
+            None => Err(S::Error::custom(format!(
+                "timezone offset of `{}` cannot be represented in `xs:dateTime`",
+                value
+            ))),
+        }
+    }
+
+    /// Deserializes an [`xs:dateTime`].
+    ///
+    /// [`xs:dateTime`]: https://www.w3.org/TR/xmlschema11-2/#dateTime
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        super::parse_date_time(&s)
+            .ok_or_else(|| D::Error::invalid_value(Unexpected::Str(&s), &"an xs:dateTime"))
+    }
+}
+
+/// Provides functions to (de)serialize [`Duration`] from and to a lexical
+/// representation of an [`xs:duration`] type. Intended to use with
+/// `#[serde(with = "quick_xml::serde_helpers::xs_duration")]`.
+///
+/// Because length of years and months is not fixed, durations with non-zero
+/// years or months cannot be deserialized. Durations are serialized using
+/// days, hours, minutes and seconds, for example, `P1DT2H30M` or `-PT0.5S`.
+/// Leading and trailing whitespaces are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use chrono::Duration;
+/// use quick_xml::de::from_str;
+/// use quick_xml::se::to_string;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Timeout {
+///     #[serde(rename = "$text", with = "quick_xml::serde_helpers::xs_duration")]
+///     value: Duration,
+/// }
+///
+/// let timeout: Timeout = from_str("<Timeout>PT1H30M</Timeout>").unwrap();
+/// assert_eq!(timeout.value, Duration::minutes(90));
+///
+/// assert_eq!(to_string(&timeout).unwrap(), "<Timeout>PT1H30M</Timeout>");
+/// ```
+///
+/// [`Duration`]: chrono::Duration
+/// [`xs:duration`]: https://www.w3.org/TR/xmlschema11-2/#duration
+pub mod xs_duration {
+    use chrono::Duration;
+    use serde::de::{Error as _, Unexpected};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes `value` as an [`xs:duration`].
+    ///
+    /// [`xs:duration`]: https://www.w3.org/TR/xmlschema11-2/#duration
+    pub fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&super::format_duration(value))
+    }
+
+    /// Deserializes an [`xs:duration`] without years and months.
+    ///
+    /// [`xs:duration`]: https://www.w3.org/TR/xmlschema11-2/#duration
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match super::parse_duration(&s) {
+            Ok(Some(duration)) => Ok(duration),
+            Ok(None) => Err(D::Error::invalid_value(
+                Unexpected::Str(&s),
+                &"an xs:duration",
+            )),
+            Err(()) => Err(D::Error::invalid_value(
+                Unexpected::Str(&s),
+                &"an xs:duration without years and months",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn date_time(s: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(s).unwrap()
+    }
+
+    #[test]
+    fn date_time_parse() {
+        assert_eq!(
+            parse_date_time("2002-10-10T12:00:00-05:00"),
+            Some(date_time("2002-10-10T12:00:00-05:00"))
+        );
+        assert_eq!(
+            parse_date_time(" 2002-10-10T17:00:00Z\n"),
+            Some(date_time("2002-10-10T17:00:00Z"))
+        );
+        // Without timezone
+        assert_eq!(
+            parse_date_time("2002-10-10T12:00:00"),
+            Some(date_time("2002-10-10T12:00:00Z"))
+        );
+        assert_eq!(
+            parse_date_time("2002-10-10T12:00:00.123456789123"),
+            Some(date_time("2002-10-10T12:00:00.123456789Z"))
+        );
+        assert_eq!(
+            parse_date_time("2002-12-31T24:00:00+14:00"),
+            Some(date_time("2003-01-01T00:00:00+14:00"))
+        );
+        assert_eq!(
+            parse_date_time("-0044-03-15T12:00:00Z").map(|d| d.year()),
+            Some(-44)
+        );
+        assert_eq!(
+            parse_date_time("12345-01-01T00:00:00Z").map(|d| d.year()),
+            Some(12345)
+        );
+
+        assert_eq!(parse_date_time("2002-10-10"), None);
+        assert_eq!(parse_date_time("02-10-10T12:00:00"), None);
+        assert_eq!(parse_date_time("02002-10-10T12:00:00"), None);
+        assert_eq!(parse_date_time("2002-10-10T12:00"), None);
+        assert_eq!(parse_date_time("2002-10-10T12:00:00."), None);
+        assert_eq!(parse_date_time("2002-02-30T12:00:00"), None);
+        assert_eq!(parse_date_time("2002-10-10T24:00:01"), None);
+        assert_eq!(parse_date_time("2002-10-10T12:00:00+15:00"), None);
+        assert_eq!(parse_date_time("2002-10-10T12:00:00+05"), None);
+        assert_eq!(parse_date_time("2002-10-10T12:00:00z"), None);
+        assert_eq!(parse_date_time("2002-10-10 12:00:00"), None);
+        assert_eq!(parse_date_time("2002-10-10T12:00:00Ж"), None);
+    }
+
+    #[test]
+    fn date_time_format() {
+        let format = |s| format_date_time(&date_time(s));
+        assert_eq!(
+            format("2002-10-10T12:00:00-05:00"),
+            Some("2002-10-10T12:00:00-05:00".into())
+        );
+        assert_eq!(
+            format("2002-10-10T12:00:00+00:00"),
+            Some("2002-10-10T12:00:00Z".into())
+        );
+        assert_eq!(
+            format("2002-10-10T12:00:00.120+05:30"),
+            Some("2002-10-10T12:00:00.12+05:30".into())
+        );
+        assert_eq!(
+            parse_date_time("-0044-03-15T12:00:00Z").and_then(|d| format_date_time(&d)),
+            Some("-0044-03-15T12:00:00Z".into())
+        );
+
+        let offset = FixedOffset::east_opt(30).unwrap();
+        let value = offset.with_ymd_and_hms(2002, 10, 10, 12, 0, 0).unwrap();
+        assert_eq!(format_date_time(&value), None);
+    }
+
+    #[test]
+    fn duration_parse() {
+        assert_eq!(parse_duration("PT0S"), Ok(Some(Duration::zero())));
+        assert_eq!(parse_duration("P0Y0M"), Ok(Some(Duration::zero())));
+        assert_eq!(parse_duration("P2D"), Ok(Some(Duration::days(2))));
+        assert_eq!(
+            parse_duration(" P1DT2H3M4.5S "),
+            Ok(Some(
+                Duration::days(1)
+                    + Duration::hours(2)
+                    + Duration::minutes(3)
+                    + Duration::milliseconds(4500)
+            ))
+        );
+        assert_eq!(parse_duration("PT90M"), Ok(Some(Duration::minutes(90))));
+        assert_eq!(
+            parse_duration("-PT0.25S"),
+            Ok(Some(Duration::milliseconds(-250)))
+        );
+
+        assert_eq!(parse_duration("P1Y"), Err(()));
+        assert_eq!(parse_duration("P1M2D"), Err(()));
+
+        assert_eq!(parse_duration(""), Ok(None));
+        assert_eq!(parse_duration("P"), Ok(None));
+        assert_eq!(parse_duration("PT"), Ok(None));
+        assert_eq!(parse_duration("P1DT"), Ok(None));
+        assert_eq!(parse_duration("PT1D"), Ok(None));
+        assert_eq!(parse_duration("P1H"), Ok(None));
+        assert_eq!(parse_duration("PT1S2M"), Ok(None));
+        assert_eq!(parse_duration("P1D1D"), Ok(None));
+        assert_eq!(parse_duration("P1.5D"), Ok(None));
+        assert_eq!(parse_duration("PT1.S"), Ok(None));
+        assert_eq!(parse_duration("P-1D"), Ok(None));
+        assert_eq!(parse_duration("1D"), Ok(None));
+    }
+
+    #[test]
+    fn duration_format() {
+        assert_eq!(format_duration(&Duration::zero()), "PT0S");
+        assert_eq!(format_duration(&Duration::days(2)), "P2D");
+        assert_eq!(format_duration(&Duration::minutes(90)), "PT1H30M");
+        assert_eq!(
+            format_duration(&(Duration::days(1) + Duration::seconds(4))),
+            "P1DT4S"
+        );
+        assert_eq!(format_duration(&Duration::milliseconds(-250)), "-PT0.25S");
+        assert_eq!(
+            format_duration(&(Duration::hours(2) + Duration::nanoseconds(1))),
+            "PT2H0.000000001S"
+        );
+    }
+}
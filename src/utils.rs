@@ -118,6 +118,7 @@ impl<'i, 's> CowRef<'i, 's, str> {
     /// [valid] boolean representation, otherwise calls [`Self::deserialize_str`].
     ///
     /// The valid boolean representations are only `"true"`, `"false"`, `"1"`, and `"0"`.
+    /// Leading and trailing XML whitespaces are ignored.
     ///
     /// [valid]: https://www.w3.org/TR/xmlschema11-2/#boolean
     #[cfg(feature = "serialize")]
@@ -126,7 +127,7 @@ impl<'i, 's> CowRef<'i, 's, str> {
         V: Visitor<'i>,
         E: Error,
     {
-        match self.as_ref() {
+        match trim_xml_spaces(self.as_ref()) {
             "1" | "true" => visitor.visit_bool(true),
             "0" | "false" => visitor.visit_bool(false),
             _ => self.deserialize_str(visitor),
@@ -374,6 +375,74 @@ pub const fn trim_xml_end(mut bytes: &[u8]) -> &[u8] {
     bytes
 }
 
+/// Returns a string slice with leading and trailing XML whitespace characters removed.
+///
+/// 'Whitespace' refers to the definition used by [`is_whitespace`].
+#[inline]
+pub fn trim_xml_spaces(text: &str) -> &str {
+    text.trim_matches(|c: char| c.is_ascii() && is_whitespace(c as u8))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Almost all characters can form a name. Citation from <https://www.w3.org/TR/xml11/#sec-xml11>:
+///
+/// > The overall philosophy of names has changed since XML 1.0. Whereas XML 1.0
+/// > provided a rigid definition of names, wherein everything that was not permitted
+/// > was forbidden, XML 1.1 names are designed so that everything that is not
+/// > forbidden (for a specific reason) is permitted. Since Unicode will continue
+/// > to grow past version 4.0, further changes to XML can be avoided by allowing
+/// > almost any character, including those not yet assigned, in names.
+///
+/// <https://www.w3.org/TR/xml11/#NT-NameStartChar>
+pub(crate) const fn is_xml11_name_start_char(ch: char) -> bool {
+    matches!(
+        ch,
+        ':'
+        | 'A'..='Z'
+        | '_'
+        | 'a'..='z'
+        | '\u{00C0}'..='\u{00D6}'
+        | '\u{00D8}'..='\u{00F6}'
+        | '\u{00F8}'..='\u{02FF}'
+        | '\u{0370}'..='\u{037D}'
+        | '\u{037F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+/// <https://www.w3.org/TR/xml11/#NT-NameChar>
+pub(crate) const fn is_xml11_name_char(ch: char) -> bool {
+    match ch {
+        '-' | '.' | '0'..='9' | '\u{00B7}' | '\u{0300}'..='\u{036F}' | '\u{203F}'..='\u{2040}' => {
+            true
+        }
+        _ => is_xml11_name_start_char(ch),
+    }
+}
+
+/// Checks that `name` is a valid [XML name]: starts from a name start character
+/// and contains only name characters.
+///
+/// [XML name]: https://www.w3.org/TR/xml11/#NT-Name
+pub(crate) fn is_xml11_name(name: &[u8]) -> bool {
+    match std::str::from_utf8(name) {
+        Ok(name) => {
+            let mut chars = name.chars();
+            match chars.next() {
+                Some(ch) if is_xml11_name_start_char(ch) => chars.all(is_xml11_name_char),
+                _ => false,
+            }
+        }
+        Err(_) => false,
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
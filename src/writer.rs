@@ -3,13 +3,19 @@
 use std::borrow::Cow;
 use std::io::{self, Write};
 
-use crate::encoding::UTF8_BOM;
-use crate::events::{attributes::Attribute, BytesCData, BytesPI, BytesStart, BytesText, Event};
+use crate::encoding::{EncodingError, UTF8_BOM};
+use crate::events::{
+    attributes::Attribute, BytesCData, BytesDecl, BytesPI, BytesStart, BytesText, Event,
+};
+use crate::reader::Reader;
+use crate::utils::{is_whitespace, is_xml11_name};
 
 #[cfg(feature = "async-tokio")]
 mod async_tokio;
 
 /// XML writer. Writes XML [`Event`]s to a [`std::io::Write`] or [`tokio::io::AsyncWrite`] implementor.
+#[cfg(feature = "encoding")]
+use encoding_rs::{Encoding, UTF_8};
 #[cfg(feature = "serialize")]
 use {crate::se::SeError, serde::Serialize};
 
@@ -24,6 +30,7 @@ use {crate::se::SeError, serde::Serialize};
 /// use quick_xml::writer::Writer;
 /// use std::io::Cursor;
 ///
+/// # fn main() -> quick_xml::Result<()> {
 /// let xml = r#"<this_tag k1="v1" k2="v2"><child>text</child></this_tag>"#;
 /// let mut reader = Reader::from_str(xml);
 /// let mut writer = Writer::new(Cursor::new(Vec::new()));
@@ -35,8 +42,8 @@ use {crate::se::SeError, serde::Serialize};
 ///             // `e.into_owned()`
 ///             let mut elem = BytesStart::new("my_elem");
 ///
-///             // collect existing attributes
-///             elem.extend_attributes(e.attributes().map(|attr| attr.unwrap()));
+///             // collect existing attributes, stopping at the malformed one
+///             elem.try_extend_attributes(e.attributes())?;
 ///
 ///             // copy existing attributes, adds a new my-key="some value" attribute
 ///             elem.push_attribute(("my-key", "some value"));
@@ -57,12 +64,27 @@ use {crate::se::SeError, serde::Serialize};
 /// let result = writer.into_inner().into_inner();
 /// let expected = r#"<my_elem k1="v1" k2="v2" my-key="some value"><child>text</child></my_elem>"#;
 /// assert_eq!(result, expected.as_bytes());
+/// # Ok(())
+/// # }
 /// ```
 #[derive(Clone)]
 pub struct Writer<W> {
     /// underlying writer
     writer: W,
     indent: Option<Indentation>,
+    /// Count of currently opened elements. Tracked regardless of indentation,
+    /// so indentation can be enabled in the middle of a document
+    depth: usize,
+    /// If `true`, names of elements and attributes are checked before writing
+    check_names: bool,
+    /// Preferred quote character for values of attributes added by the writer.
+    /// If `None`, values are written as is in double quotes
+    attribute_quote: Option<Quote>,
+    /// Count of bytes written to the underlying writer
+    bytes_written: u64,
+    /// Encoding in which the output is written
+    #[cfg(feature = "encoding")]
+    encoding: &'static Encoding,
 }
 
 impl<W> Writer<W> {
@@ -71,17 +93,139 @@ impl<W> Writer<W> {
         Writer {
             writer: inner,
             indent: None,
+            depth: 0,
+            check_names: false,
+            attribute_quote: None,
+            bytes_written: 0,
+            #[cfg(feature = "encoding")]
+            encoding: UTF_8,
         }
     }
 
     /// Creates a `Writer` with configured indents from a generic writer.
+    ///
+    /// Each level of nesting is indented by `indent_size` repetitions of
+    /// `indent_char`. Any byte can be used as indent character, but usually
+    /// that is a space (`b' '`) or a tab (`b'\t'`). Lines are separated by `\n`,
+    /// use [`new_with_indent_and_newline`] if you need another line separator.
+    ///
+    /// [`new_with_indent_and_newline`]: Self::new_with_indent_and_newline
     pub fn new_with_indent(inner: W, indent_char: u8, indent_size: usize) -> Writer<W> {
+        Self::new_with_indent_and_newline(inner, indent_char, indent_size, "\n")
+    }
+
+    /// Creates a `Writer` with configured indents and line separator from a
+    /// generic writer.
+    ///
+    /// The `newline` string is written before each indented line instead of `\n`,
+    /// which, for example, allows to produce documents with Windows line endings:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new_with_indent_and_newline(Vec::new(), b'\t', 1, "\r\n");
+    /// writer.create_element("root").write_inner_content(|writer| {
+    ///     writer.write_event(Event::Empty(BytesStart::new("child")))?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// assert_eq!(writer.into_inner(), b"<root>\r\n\t<child/>\r\n</root>");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn new_with_indent_and_newline(
+        inner: W,
+        indent_char: u8,
+        indent_size: usize,
+        newline: &'static str,
+    ) -> Writer<W> {
         Writer {
             writer: inner,
-            indent: Some(Indentation::new(indent_char, indent_size)),
+            indent: Some(Indentation::new(indent_char, indent_size, newline)),
+            depth: 0,
+            check_names: false,
+            attribute_quote: None,
+            bytes_written: 0,
+            #[cfg(feature = "encoding")]
+            encoding: UTF_8,
         }
     }
 
+    /// Creates a `Writer` that produces human-readable output.
+    ///
+    /// This preset configures:
+    /// - indentation by 2 spaces (`b' '`) per level of nesting;
+    /// - `\n` as the line separator;
+    /// - the default values of all other options, as in [`new`].
+    ///
+    /// The same as `Writer::new_with_indent(inner, b' ', 2)`. There is no special
+    /// handling of mixed content: a line break and indentation are not inserted
+    /// right after a text or a CDATA section, but they are inserted after any
+    /// other markup, so whitespaces are added to the content of elements with
+    /// mixed content. For example, `<mixed>text<b/></mixed>` is written as
+    /// `<mixed>text<b/>\n</mixed>` (with indentation before `</mixed>`).
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesText;
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::pretty(Vec::new());
+    /// writer.create_element("root").write_inner_content(|writer| {
+    ///     writer.create_element("empty").write_empty()?;
+    ///     writer
+    ///         .create_element("text")
+    ///         .write_text_content(BytesText::new("content"))?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// assert_eq!(
+    ///     writer.into_inner(),
+    ///     b"<root>\n  <empty/>\n  <text>content</text>\n</root>"
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`new`]: Self::new
+    pub fn pretty(inner: W) -> Writer<W> {
+        Self::new_with_indent(inner, b' ', 2)
+    }
+
+    /// Creates a `Writer` that produces output without any insignificant
+    /// whitespaces.
+    ///
+    /// This preset configures:
+    /// - no indentation and no line breaks between markup;
+    /// - the default values of all other options, as in [`new`].
+    ///
+    /// The same as [`new`], but makes the intent explicit. The writer does not
+    /// change the events it writes, so to get the shortest output write empty
+    /// elements as [`Event::Empty`] events or with [`ElementWriter::write_empty`].
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::BytesText;
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::compact(Vec::new());
+    /// writer.create_element("root").write_inner_content(|writer| {
+    ///     writer.create_element("empty").write_empty()?;
+    ///     writer
+    ///         .create_element("text")
+    ///         .write_text_content(BytesText::new("content"))?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// assert_eq!(writer.into_inner(), b"<root><empty/><text>content</text></root>");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`new`]: Self::new
+    pub const fn compact(inner: W) -> Writer<W> {
+        Self::new(inner)
+    }
+
     /// Consumes this `Writer`, returning the underlying writer.
     pub fn into_inner(self) -> W {
         self.writer
@@ -97,6 +241,102 @@ impl<W> Writer<W> {
         &self.writer
     }
 
+    /// Returns the number of bytes written to the underlying writer by this
+    /// `Writer`, including indentation.
+    #[cfg_attr(
+        feature = "encoding",
+        doc = "If the output is [transcoded](Self::set_encoding), the bytes are counted after transcoding."
+    )]
+    ///
+    /// Bytes written directly to the underlying writer obtained by [`get_mut()`]
+    /// are not counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesText, Event};
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    /// writer
+    ///     .create_element("root")
+    ///     .write_inner_content(|writer| {
+    ///         writer.write_empty("child", [])?;
+    ///         Ok(())
+    ///     })?;
+    ///
+    /// assert_eq!(writer.bytes_written(), 25);
+    /// assert_eq!(writer.into_inner(), b"<root>\n  <child/>\n</root>");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`get_mut()`]: Self::get_mut
+    pub const fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Returns the indent character and the count of them used for each level of
+    /// nesting, or `None` if indentation is disabled.
+    pub fn get_indent(&self) -> Option<(u8, usize)> {
+        self.indent.as_ref().map(|i| (i.indent_char, i.indent_size))
+    }
+
+    /// Enables, disables or changes indentation for subsequently written events.
+    ///
+    /// `Some((indent_char, indent_size))` indents each level of nesting with
+    /// `indent_size` repetitions of `indent_char` as in [`new_with_indent`], and
+    /// `None` disables indentation, so the following events are written compact.
+    /// The nesting level is kept, so elements written after indentation was
+    /// enabled again are indented at their actual depth. The line separator is
+    /// kept if indentation is changed, otherwise `\n` is used.
+    ///
+    /// Note, that indentation is written *before* an event, so to start a compact
+    /// section on a new line call [`write_indent`] before disabling indentation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    /// writer.write_event(Event::Start(BytesStart::new("root")))?;
+    /// writer.write_event(Event::Empty(BytesStart::new("pretty")))?;
+    ///
+    /// writer.write_indent()?;
+    /// writer.set_indent(None);
+    /// writer.write_event(Event::Start(BytesStart::new("compact")))?;
+    /// writer.write_event(Event::Empty(BytesStart::new("child")))?;
+    /// writer.write_event(Event::End(BytesEnd::new("compact")))?;
+    ///
+    /// writer.set_indent(Some((b' ', 2)));
+    /// writer.write_event(Event::End(BytesEnd::new("root")))?;
+    ///
+    /// assert_eq!(
+    ///     std::str::from_utf8(&writer.into_inner()).unwrap(),
+    ///     "<root>\n  <pretty/>\n  <compact><child/></compact>\n</root>"
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`new_with_indent`]: Self::new_with_indent
+    /// [`write_indent`]: Self::write_indent
+    pub fn set_indent(&mut self, indent: Option<(u8, usize)>) {
+        let old = self.indent.take();
+        self.indent = indent.map(|(indent_char, indent_size)| {
+            let mut i = Indentation::new(
+                indent_char,
+                indent_size,
+                old.as_ref().map_or("\n", |i| i.newline()),
+            );
+            i.should_line_break = old.map_or(true, |i| i.should_line_break);
+            i.set_depth(self.depth);
+            i
+        });
+    }
+
     /// Provides a simple, high-level API for writing XML elements.
     ///
     /// Returns an [`ElementWriter`] that simplifies setting attributes and writing
@@ -152,6 +392,206 @@ impl<W> Writer<W> {
             spaces: Vec::new(),
         }
     }
+
+    /// Enables or disables checking of element and attribute names. When enabled,
+    /// writing of [`Start`], [`Empty`] or [`End`] event with a name that is not
+    /// a valid [XML name] returns an error of kind [`InvalidInput`] and nothing
+    /// is written.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use std::io::ErrorKind;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.check_names(true);
+    ///
+    /// let error = writer.write_event(Event::Empty(BytesStart::new("1bad"))).unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    ///
+    /// let error = writer
+    ///     .write_event(Event::Empty(BytesStart::new("tag").with_attributes([("a b", "")])))
+    ///     .unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    ///
+    /// assert_eq!(writer.into_inner(), b"");
+    /// ```
+    ///
+    /// [`Start`]: Event::Start
+    /// [`Empty`]: Event::Empty
+    /// [`End`]: Event::End
+    /// [XML name]: https://www.w3.org/TR/xml11/#NT-Name
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    pub fn check_names(&mut self, check: bool) {
+        self.check_names = check;
+    }
+
+    /// Sets the preferred quote character for values of attributes added by
+    /// the [`create_element()`] builder and the [`write_empty()`] method.
+    ///
+    /// If the value contains the preferred quote, but not the other one, the
+    /// value is enclosed in the other quote instead. Quotes which are not used
+    /// to enclose the value are written unescaped, so values of attributes
+    /// usually do not contain `&quot;` or `&apos;` references. If the value
+    /// contains both quotes, the preferred quote is used and escaped.
+    ///
+    /// By default values are written as is in double quotes, with both quote
+    /// characters escaped. Attributes of events passed to [`write_event()`]
+    /// are always written as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::writer::{Quote, Writer};
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.attribute_quote(Quote::Single);
+    /// writer
+    ///     .create_element("tag")
+    ///     .with_attribute(("a", r#"say "hi""#))
+    ///     .with_attribute(("b", "it's"))
+    ///     .with_attribute(("c", r#"it's "hi""#))
+    ///     .write_empty()?;
+    ///
+    /// assert_eq!(
+    ///     std::str::from_utf8(&writer.into_inner()).unwrap(),
+    ///     r#"<tag a='say "hi"' b="it's" c='it&apos;s "hi"'/>"#
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`create_element()`]: Self::create_element
+    /// [`write_empty()`]: Self::write_empty
+    /// [`write_event()`]: Self::write_event
+    pub fn attribute_quote(&mut self, quote: Quote) {
+        self.attribute_quote = Some(quote);
+    }
+
+    /// Returns the encoding in which the output is written. [`UTF-8`] by default.
+    ///
+    /// [`UTF-8`]: encoding_rs::UTF_8
+    #[cfg(feature = "encoding")]
+    pub const fn encoding(&self) -> &'static Encoding {
+        self.encoding
+    }
+
+    /// Sets the encoding in which the output is written.
+    ///
+    /// Events always contain UTF-8 text, which is transcoded to the specified
+    /// encoding when written. Characters that cannot be represented in the target
+    /// encoding are written as numeric character references (`&#NNNN;`). Note, that
+    /// such references are recognized only in the text and attribute values, so
+    /// names, comments and `CDATA` sections should use only representable characters.
+    ///
+    /// The `encoding` attribute of written [`Decl`] events is replaced with the
+    /// name of the output encoding (or added if it is missing), so the document
+    /// can be correctly read back. [`write_bom()`] writes nothing for encodings
+    /// other than UTF-8.
+    ///
+    /// Only ASCII-compatible encodings are supported. Like in [`Encoding::encode`],
+    /// UTF-16 encodings are replaced by UTF-8.
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use encoding_rs::WINDOWS_1252;
+    /// use quick_xml::events::{BytesDecl, BytesText, Event};
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.set_encoding(WINDOWS_1252);
+    /// writer.write_event(Event::Decl(BytesDecl::new("1.0", None, None)))?;
+    /// writer
+    ///     .create_element("price")
+    ///     .write_text_content(BytesText::new("5 € ≈ 5 ¥"))?;
+    ///
+    /// assert_eq!(
+    ///     writer.into_inner(),
+    ///     b"<?xml version=\"1.0\" encoding=\"windows-1252\"?><price>5 \x80 &#8776; 5 \xA5</price>",
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`Decl`]: Event::Decl
+    /// [`write_bom()`]: Self::write_bom
+    #[cfg(feature = "encoding")]
+    pub fn set_encoding(&mut self, encoding: &'static Encoding) {
+        self.encoding = encoding.output_encoding();
+    }
+
+    /// Transcodes UTF-8 bytes to the output encoding. Bytes that are not valid
+    /// UTF-8 are considered as already encoded and returned as is.
+    #[inline]
+    fn encode<'b>(&self, value: &'b [u8]) -> Cow<'b, [u8]> {
+        #[cfg(feature = "encoding")]
+        if self.encoding != UTF_8 {
+            if let Ok(s) = std::str::from_utf8(value) {
+                return self.encoding.encode(s).0;
+            }
+        }
+        Cow::Borrowed(value)
+    }
+
+    /// Returns a declaration with the `encoding` attribute set to the output
+    /// encoding, if the declaration does not already specify it.
+    fn encoded_decl(&self, _decl: &BytesDecl) -> Option<BytesDecl<'static>> {
+        #[cfg(feature = "encoding")]
+        if self.encoding != UTF_8 {
+            if let Some(Ok(label)) = _decl.encoding() {
+                if Encoding::for_label(&label) == Some(self.encoding) {
+                    return None;
+                }
+            }
+            let version = _decl.version().ok()?;
+            let standalone = match _decl.standalone() {
+                Some(Ok(s)) => Some(String::from_utf8_lossy(&s).into_owned()),
+                _ => None,
+            };
+            return Some(BytesDecl::new(
+                &String::from_utf8_lossy(&version),
+                Some(self.encoding.name()),
+                standalone.as_deref(),
+            ));
+        }
+        None
+    }
+
+    /// Checks names of elements and attributes in the event if that is requested
+    /// by the [`Self::check_names`] option.
+    fn check_event_names(&self, event: &Event) -> io::Result<()> {
+        if !self.check_names {
+            return Ok(());
+        }
+        let invalid = |name: &[u8]| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid XML name `{}`", String::from_utf8_lossy(name)),
+            )
+        };
+        match event {
+            Event::Start(e) | Event::Empty(e) => {
+                if !is_xml11_name(e.name().as_ref()) {
+                    return Err(invalid(e.name().as_ref()));
+                }
+                for attr in e.attributes().with_checks(false) {
+                    let attr = attr.map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                    if !is_xml11_name(attr.key.as_ref()) {
+                        return Err(invalid(attr.key.as_ref()));
+                    }
+                }
+            }
+            Event::End(e) if !is_xml11_name(e.name().as_ref()) => {
+                return Err(invalid(e.name().as_ref()));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
 }
 
 impl<W: Write> Writer<W> {
@@ -186,41 +626,255 @@ impl<W: Write> Writer<W> {
     /// ```
     /// [Byte-Order-Mark]: https://unicode.org/faq/utf_bom.html#BOM
     pub fn write_bom(&mut self) -> io::Result<()> {
+        #[cfg(feature = "encoding")]
+        if self.encoding != UTF_8 {
+            return Ok(());
+        }
         self.write(UTF8_BOM)
     }
 
+    /// Writes an XML declaration with the given version and optional encoding
+    /// and standalone flag. This is a shortcut for writing of [`Event::Decl`] event.
+    ///
+    /// The declaration, if present, must be the first thing in the document
+    /// (only a [BOM] may precede it), so this method should be called before
+    /// writing any other events. If indentation is enabled, the next event
+    /// will be written on a new line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    ///
+    /// writer.write_declaration("1.0", Some("UTF-8"), Some("yes"))?;
+    /// writer.write_empty("root", [])?;
+    ///
+    /// assert_eq!(
+    ///     std::str::from_utf8(&writer.into_inner()).unwrap(),
+    ///     "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<root/>"
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [BOM]: Self::write_bom
+    pub fn write_declaration(
+        &mut self,
+        version: &str,
+        encoding: Option<&str>,
+        standalone: Option<&str>,
+    ) -> io::Result<()> {
+        self.write_event(Event::Decl(BytesDecl::new(version, encoding, standalone)))
+    }
+
+    /// Writes the default XML declaration `<?xml version="1.0" encoding="UTF-8"?>`.
+    /// See [`write_declaration()`] for details.
+    ///
+    /// If the writer was configured to use another encoding with `set_encoding()`
+    /// (requires the `encoding` feature), that encoding will be written in the
+    /// declaration instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    ///
+    /// writer.write_default_declaration()?;
+    /// writer.write_empty("root", [])?;
+    ///
+    /// assert_eq!(
+    ///     std::str::from_utf8(&writer.into_inner()).unwrap(),
+    ///     r#"<?xml version="1.0" encoding="UTF-8"?><root/>"#
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`write_declaration()`]: Self::write_declaration
+    pub fn write_default_declaration(&mut self) -> io::Result<()> {
+        self.write_declaration("1.0", Some("UTF-8"), None)
+    }
+
+    /// Writes a text that is already escaped, as is. This is a shortcut for
+    /// writing of [`Event::Text`] event created with [`BytesText::from_escaped`].
+    ///
+    /// Use this method when the text comes, for example, from a text event of
+    /// another [`Reader`], to avoid double escaping. If the text is not escaped,
+    /// write [`BytesText::new`] event instead.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if `already_escaped` contains a `<` character,
+    /// which is never allowed in an escaped text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesText, Event};
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    ///
+    /// writer.write_escaped_text("Tom &amp; Jerry")?;
+    /// writer.write_event(Event::Text(BytesText::new(" & Spike")))?;
+    ///
+    /// assert_eq!(writer.into_inner(), b"Tom &amp; Jerry &amp; Spike");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`Reader`]: crate::reader::Reader
+    pub fn write_escaped_text(&mut self, already_escaped: &str) -> io::Result<()> {
+        debug_assert!(
+            !already_escaped.contains('<'),
+            "escaped text cannot contain `<`: {:?}",
+            already_escaped
+        );
+        self.write_event(Event::Text(BytesText::from_escaped(already_escaped)))
+    }
+
+    /// Writes a comment with the given text. This is a shortcut for writing of
+    /// [`Event::Comment`] event, that checks that the comment will be well-formed.
+    ///
+    /// A [comment] cannot contain the `--` sequence and cannot end with `-`,
+    /// because that would produce the `--->` sequence at the end. An error of
+    /// kind [`InvalidInput`] is returned for such text and nothing is written.
+    /// Use [`write_comment_safe()`] to write arbitrary text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use std::io::ErrorKind;
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    ///
+    /// writer.write_comment(" comment ")?;
+    /// let error = writer.write_comment("a -- b").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    ///
+    /// assert_eq!(writer.into_inner(), b"<!-- comment -->");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [comment]: https://www.w3.org/TR/xml11/#sec-comments
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    /// [`write_comment_safe()`]: Self::write_comment_safe
+    pub fn write_comment(&mut self, text: &str) -> io::Result<()> {
+        if text.contains("--") || text.ends_with('-') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("comment cannot contain `--` or end with `-`: {:?}", text),
+            ));
+        }
+        self.write_event(Event::Comment(BytesText::from_escaped(text)))
+    }
+
+    /// Writes a comment with the given arbitrary text. Unlike [`write_comment()`],
+    /// this method makes the text suitable for a comment instead of returning
+    /// an error: a space is inserted between each two consecutive `-` characters
+    /// and after the trailing `-`, so `--` becomes `- -`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    ///
+    /// writer.write_comment_safe("a -- b ---> c -")?;
+    ///
+    /// assert_eq!(writer.into_inner(), b"<!--a - - b - - -> c - -->");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`write_comment()`]: Self::write_comment
+    pub fn write_comment_safe(&mut self, text: &str) -> io::Result<()> {
+        self.write_event(Event::Comment(BytesText::from_escaped(escape_comment(
+            text,
+        ))))
+    }
+
     /// Writes the given event to the underlying writer.
     pub fn write_event<'a, E: Into<Event<'a>>>(&mut self, event: E) -> io::Result<()> {
+        self.write_event_ref(&event.into())
+    }
+
+    /// Writes the given event to the underlying writer without consuming it.
+    ///
+    /// This is useful when the event is inspected after writing, for example,
+    /// when events, read by a [`Reader`], are forwarded to the writer, so it is
+    /// not required to clone an event or [convert] it to an owned one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::Event;
+    /// use quick_xml::reader::Reader;
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut reader = Reader::from_str("<root><item/><item/></root>");
+    /// let mut writer = Writer::new(Vec::new());
+    /// let mut items = 0;
+    /// loop {
+    ///     let event = reader.read_event().unwrap();
+    ///     writer.write_event_ref(&event).unwrap();
+    ///     match event {
+    ///         Event::Empty(e) if e.name().as_ref() == b"item" => items += 1,
+    ///         Event::Eof => break,
+    ///         _ => {}
+    ///     }
+    /// }
+    /// assert_eq!(items, 2);
+    /// assert_eq!(writer.into_inner(), b"<root><item/><item/></root>");
+    /// ```
+    ///
+    /// [`Reader`]: crate::reader::Reader
+    /// [convert]: Event::into_owned
+    pub fn write_event_ref(&mut self, event: &Event) -> io::Result<()> {
+        self.check_event_names(event)?;
         let mut next_should_line_break = true;
-        let result = match event.into() {
+        let result = match event {
             Event::Start(e) => {
-                let result = self.write_wrapped(b"<", &e, b">");
+                let result = self.write_wrapped(b"<", e, b">");
                 if let Some(i) = self.indent.as_mut() {
                     i.grow();
                 }
+                self.depth += 1;
                 result
             }
             Event::End(e) => {
                 if let Some(i) = self.indent.as_mut() {
                     i.shrink();
                 }
-                self.write_wrapped(b"</", &e, b">")
+                self.depth = self.depth.saturating_sub(1);
+                self.write_wrapped(b"</", e, b">")
             }
-            Event::Empty(e) => self.write_wrapped(b"<", &e, b"/>"),
+            Event::Empty(e) => self.write_wrapped(b"<", e, b"/>"),
             Event::Text(e) => {
                 next_should_line_break = false;
-                self.write(&e)
+                self.write(e)
             }
-            Event::Comment(e) => self.write_wrapped(b"<!--", &e, b"-->"),
+            Event::Comment(e) => self.write_wrapped(b"<!--", e, b"-->"),
             Event::CData(e) => {
                 next_should_line_break = false;
                 self.write(b"<![CDATA[")?;
-                self.write(&e)?;
+                self.write(e)?;
                 self.write(b"]]>")
             }
-            Event::Decl(e) => self.write_wrapped(b"<?", &e, b"?>"),
-            Event::PI(e) => self.write_wrapped(b"<?", &e, b"?>"),
-            Event::DocType(e) => self.write_wrapped(b"<!DOCTYPE ", &e, b">"),
+            Event::Decl(e) => {
+                let decl = self.encoded_decl(e);
+                self.write_wrapped(b"<?", decl.as_ref().unwrap_or(e), b"?>")
+            }
+            Event::PI(e) => self.write_wrapped(b"<?", e, b"?>"),
+            Event::DocType(e) => self.write_wrapped(b"<!DOCTYPE ", e, b">"),
             Event::Eof => Ok(()),
         };
         if let Some(i) = self.indent.as_mut() {
@@ -229,18 +883,63 @@ impl<W: Write> Writer<W> {
         result
     }
 
+    /// Writes a self-closed element with the given name and attributes. This is
+    /// a shortcut for writing of [`Event::Empty`] event. Indentation is applied
+    /// as for any other element.
+    ///
+    /// Attributes are written in the order of iteration and their values are escaped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use quick_xml::events::{BytesStart, Event};
+    /// use quick_xml::writer::Writer;
+    ///
+    /// let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    ///
+    /// writer.write_event(Event::Start(BytesStart::new("p")))?;
+    /// writer.write_empty("br", [])?;
+    /// writer.write_empty("img", [("src", "image.png"), ("alt", "<image>")])?;
+    /// writer.write_event(Event::End(BytesStart::new("p").to_end()))?;
+    ///
+    /// assert_eq!(
+    ///     std::str::from_utf8(&writer.into_inner()).unwrap(),
+    ///     r#"<p>
+    ///   <br/>
+    ///   <img src="image.png" alt="&lt;image&gt;"/>
+    /// </p>"#
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write_empty<'b, I>(&mut self, name: &str, attributes: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = (&'b str, &'b str)>,
+    {
+        let mut start = BytesStart::new(name);
+        for attr in attributes {
+            let (attr, quote) = quote_attribute(attr.into(), self.attribute_quote);
+            start.push_quoted_attribute(attr, quote);
+        }
+        self.write_event(Event::Empty(start))
+    }
+
     /// Writes bytes
     #[inline]
     pub(crate) fn write(&mut self, value: &[u8]) -> io::Result<()> {
-        self.writer.write_all(value).map_err(Into::into)
+        let value = self.encode(value);
+        self.writer.write_all(&value)?;
+        self.bytes_written += value.len() as u64;
+        Ok(())
     }
 
     #[inline]
     fn write_wrapped(&mut self, before: &[u8], value: &[u8], after: &[u8]) -> io::Result<()> {
         if let Some(ref i) = self.indent {
             if i.should_line_break {
-                self.writer.write_all(b"\n")?;
+                self.writer.write_all(i.newline().as_bytes())?;
                 self.writer.write_all(i.current())?;
+                self.bytes_written += (i.newline().len() + i.current().len()) as u64;
             }
         }
         self.write(before)?;
@@ -262,8 +961,9 @@ impl<W: Write> Writer<W> {
     /// [`new_with_indent`]: Self::new_with_indent
     pub fn write_indent(&mut self) -> io::Result<()> {
         if let Some(ref i) = self.indent {
-            self.writer.write_all(b"\n")?;
+            self.writer.write_all(i.newline().as_bytes())?;
             self.writer.write_all(i.current())?;
+            self.bytes_written += (i.newline().len() + i.current().len()) as u64;
         }
         Ok(())
     }
@@ -322,7 +1022,24 @@ impl<W: Write> Writer<W> {
         use crate::se::{Indent, Serializer};
 
         self.write_indent()?;
-        let mut fmt = ToFmtWrite(&mut self.writer);
+        #[cfg(feature = "encoding")]
+        if self.encoding != UTF_8 {
+            // Serializer produces UTF-8 text, so collect it to transcode later
+            let mut buf = String::new();
+            let mut serializer = Serializer::with_root(&mut buf, Some(tag_name))?;
+
+            if let Some(indent) = &mut self.indent {
+                serializer.set_indent(Indent::Borrow(indent));
+            }
+
+            content.serialize(serializer)?;
+
+            return Ok(self.write(buf.as_bytes())?);
+        }
+        let mut fmt = ToFmtWrite(CountingWrite {
+            writer: &mut self.writer,
+            count: &mut self.bytes_written,
+        });
         let mut serializer = Serializer::with_root(&mut fmt, Some(tag_name))?;
 
         if let Some(indent) = &mut self.indent {
@@ -369,6 +1086,77 @@ enum AttributeIndent {
     Configured(usize),
 }
 
+/// A quote character used to enclose values of attributes.
+/// See [`Writer::attribute_quote()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quote {
+    /// Double quote (`"`)
+    Double,
+    /// Single quote (`'`)
+    Single,
+}
+
+/// Inserts a space between consecutive `-` characters and after the trailing `-`,
+/// so the text can be written inside a comment.
+fn escape_comment(text: &str) -> Cow<'_, str> {
+    if !text.contains("--") && !text.ends_with('-') {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 2);
+    for ch in text.chars() {
+        if ch == '-' && escaped.ends_with('-') {
+            escaped.push(' ');
+        }
+        escaped.push(ch);
+    }
+    if escaped.ends_with('-') {
+        escaped.push(' ');
+    }
+    Cow::Owned(escaped)
+}
+
+/// Chooses a quote character for the escaped value of an attribute according
+/// to the preferred quote and unescapes quotes which do not need escaping.
+/// Returns the attribute with the adjusted value and the chosen quote character.
+fn quote_attribute<'a>(attr: Attribute<'a>, preferred: Option<Quote>) -> (Attribute<'a>, u8) {
+    let preferred = match preferred {
+        Some(quote) => quote,
+        None => return (attr, b'"'),
+    };
+    let contains = |r: &[u8]| attr.value.windows(r.len()).any(|w| w == r);
+    let has_quot = contains(b"&quot;");
+    let has_apos = contains(b"&apos;");
+
+    let quote = match preferred {
+        Quote::Double if has_quot && !has_apos => Quote::Single,
+        Quote::Single if has_apos && !has_quot => Quote::Double,
+        quote => quote,
+    };
+    let (quote, reference, unescaped, found) = match quote {
+        Quote::Double => (b'"', b"&apos;", b'\'', has_apos),
+        Quote::Single => (b'\'', b"&quot;", b'"', has_quot),
+    };
+    if !found {
+        return (attr, quote);
+    }
+
+    let mut value = Vec::with_capacity(attr.value.len());
+    let mut rest = &attr.value[..];
+    while let Some(i) = rest.windows(reference.len()).position(|w| w == reference) {
+        value.extend_from_slice(&rest[..i]);
+        value.push(unescaped);
+        rest = &rest[i + reference.len()..];
+    }
+    value.extend_from_slice(rest);
+    (
+        Attribute {
+            key: attr.key,
+            value: Cow::Owned(value),
+        },
+        quote,
+    )
+}
+
 /// A struct to write an element. Contains methods to add attributes and inner
 /// elements to the element
 pub struct ElementWriter<'a, W> {
@@ -397,10 +1185,8 @@ impl<'a, W> ElementWriter<'a, W> {
         I: IntoIterator,
         I::Item: Into<Attribute<'b>>,
     {
-        let mut iter = attributes.into_iter();
-        if let Some(attr) = iter.next() {
+        for attr in attributes {
             self.write_attr(attr.into());
-            self.start_tag.extend_attributes(iter);
         }
         self
     }
@@ -471,13 +1257,14 @@ impl<'a, W> ElementWriter<'a, W> {
                     self.state = AttributeIndent::WriteConfigured(indent)
                 }
             }
-            self.start_tag.push_newline();
+            self.start_tag.push_newline(i.newline());
         };
         self
     }
 
     /// Writes attribute and maintain indentation state
     fn write_attr<'b>(&mut self, attr: Attribute<'b>) {
+        let (attr, quote) = quote_attribute(attr, self.writer.attribute_quote);
         if let Some(i) = self.writer.indent.as_mut() {
             // Save the indent that we should use next time when .new_line() be called
             self.state = match self.state {
@@ -485,7 +1272,7 @@ impl<'a, W> ElementWriter<'a, W> {
                 // If newline inside attributes will be requested, we should indent them
                 // by the length of tag name and +1 for `<` and +1 for one space
                 AttributeIndent::NoneAttributesWritten => {
-                    self.start_tag.push_attribute(attr);
+                    self.start_tag.push_quoted_attribute(attr, quote);
                     AttributeIndent::Spaces(self.start_tag.name().as_ref().len() + 2)
                 }
 
@@ -496,13 +1283,13 @@ impl<'a, W> ElementWriter<'a, W> {
                         self.spaces.resize(indent, b' ');
                     }
                     self.start_tag.push_indent(&self.spaces[..indent]);
-                    self.start_tag.push_attr(attr.into());
+                    self.start_tag.push_quoted_attr(attr, quote);
                     AttributeIndent::Spaces(indent)
                 }
                 // .new_line() was not called, but .with_attribute() was.
                 // use the previously calculated indent
                 AttributeIndent::Spaces(indent) => {
-                    self.start_tag.push_attribute(attr);
+                    self.start_tag.push_quoted_attribute(attr, quote);
                     AttributeIndent::Spaces(indent)
                 }
 
@@ -510,18 +1297,18 @@ impl<'a, W> ElementWriter<'a, W> {
                 // New line was already written
                 AttributeIndent::WriteConfigured(indent) => {
                     self.start_tag.push_indent(i.additional(indent));
-                    self.start_tag.push_attr(attr.into());
+                    self.start_tag.push_quoted_attr(attr, quote);
                     AttributeIndent::Configured(indent)
                 }
                 // .new_line() was not called, but .with_attribute() was.
                 // use the previously calculated indent
                 AttributeIndent::Configured(indent) => {
-                    self.start_tag.push_attribute(attr);
+                    self.start_tag.push_quoted_attribute(attr, quote);
                     AttributeIndent::Configured(indent)
                 }
             };
         } else {
-            self.start_tag.push_attribute(attr);
+            self.start_tag.push_quoted_attribute(attr, quote);
         }
     }
 }
@@ -576,6 +1363,216 @@ impl<'a, W: Write> ElementWriter<'a, W> {
         Ok(self.writer)
     }
 }
+
+/// A destination of [`Event`]s, implemented by the [`Writer`] and the [`EventSink`].
+///
+/// Write code that produces events against this trait to be able to pass it
+/// either a real writer or a sink that collects events, for example, in tests.
+pub trait WriteEvent {
+    /// Writes the given event without consuming it.
+    fn write_event_ref(&mut self, event: &Event) -> io::Result<()>;
+
+    /// Writes the given event.
+    fn write_event<'a, E: Into<Event<'a>>>(&mut self, event: E) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        self.write_event_ref(&event.into())
+    }
+}
+
+impl<W: Write> WriteEvent for Writer<W> {
+    #[inline]
+    fn write_event_ref(&mut self, event: &Event) -> io::Result<()> {
+        Writer::write_event_ref(self, event)
+    }
+}
+
+/// An event writer that collects owned copies of written [`Event`]s into a [`Vec`]
+/// instead of writing XML.
+///
+/// It implements the [`WriteEvent`] trait as the [`Writer`] does, so it is
+/// useful for testing code that transforms events: the result can be checked
+/// without parsing the written XML back.
+///
+/// Events are stored as is: no checks or escaping is performed and the
+/// [`Event::Eof`] events are stored as any other event.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+/// use quick_xml::reader::Reader;
+/// use quick_xml::writer::{EventSink, WriteEvent, Writer};
+///
+/// /// Renames all `<b>` elements to `<strong>`
+/// fn transform<W: WriteEvent>(xml: &str, out: &mut W) -> std::io::Result<()> {
+///     let mut reader = Reader::from_str(xml);
+///     loop {
+///         match reader.read_event().unwrap() {
+///             Event::Start(e) if e.name().as_ref() == b"b" => {
+///                 out.write_event(Event::Start(BytesStart::new("strong")))?
+///             }
+///             Event::End(e) if e.name().as_ref() == b"b" => {
+///                 out.write_event(Event::End(BytesEnd::new("strong")))?
+///             }
+///             Event::Eof => return Ok(()),
+///             e => out.write_event(e)?,
+///         }
+///     }
+/// }
+///
+/// let mut sink = EventSink::new();
+/// transform("<b>bold</b>", &mut sink).unwrap();
+///
+/// assert_eq!(
+///     sink.into_events(),
+///     [
+///         Event::Start(BytesStart::new("strong")),
+///         Event::Text(BytesText::new("bold")),
+///         Event::End(BytesEnd::new("strong")),
+///     ]
+/// );
+///
+/// // The same code writes XML
+/// let mut writer = Writer::new(Vec::new());
+/// transform("<b>bold</b>", &mut writer).unwrap();
+///
+/// assert_eq!(writer.into_inner(), b"<strong>bold</strong>");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EventSink {
+    events: Vec<Event<'static>>,
+}
+
+impl EventSink {
+    /// Creates a sink without events.
+    pub const fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+
+    /// Returns events written so far.
+    pub fn events(&self) -> &[Event<'static>] {
+        &self.events
+    }
+
+    /// Consumes the sink and returns all written events.
+    pub fn into_events(self) -> Vec<Event<'static>> {
+        self.events
+    }
+}
+
+impl WriteEvent for EventSink {
+    /// Stores an owned copy of the given event. Never fails.
+    fn write_event_ref(&mut self, event: &Event) -> io::Result<()> {
+        self.events.push(event.borrow().into_owned());
+        Ok(())
+    }
+
+    /// Stores an owned copy of the given event. Never fails.
+    fn write_event<'a, E: Into<Event<'a>>>(&mut self, event: E) -> io::Result<()> {
+        self.events.push(event.into().into_owned());
+        Ok(())
+    }
+}
+
+/// Reformats an XML document with the specified indentation.
+///
+/// Each level of nesting is indented by `indent_size` repetitions of `indent_char`,
+/// as in [`Writer::new_with_indent`]. Texts that consist only of whitespaces
+/// between markup are considered as an old indentation and are removed.
+/// Comments, processing instructions and CDATA sections are kept.
+///
+/// Elements with [mixed content], i.e. those that directly contain a non-whitespace
+/// text or a CDATA section, are written as is, with all their content, because
+/// any added whitespace would change their text. The element itself is indented
+/// as any other element.
+///
+/// # Example
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// let xml = "<root><item id='1'>first</item><list><item/><!--end--></list>\
+///            <p>Some <b>bold</b> text</p></root>";
+///
+/// assert_eq!(
+///     quick_xml::reformat(xml, b' ', 2)?,
+///     "<root>\n  \
+///        <item id='1'>first</item>\n  \
+///        <list>\n    \
+///          <item/>\n    \
+///          <!--end-->\n  \
+///        </list>\n  \
+///        <p>Some <b>bold</b> text</p>\n\
+///      </root>"
+/// );
+/// # Ok::<(), quick_xml::Error>(())
+/// ```
+///
+/// [mixed content]: https://www.w3.org/TR/xml11/#sec-mixed-content
+pub fn reformat(input: &str, indent_char: u8, indent_size: usize) -> crate::Result<String> {
+    let is_blank = |e: &BytesText| e.iter().all(|b| is_whitespace(*b));
+
+    // The first pass: for each start tag in document order determine whether
+    // the element contains mixed content
+    let mut mixed = Vec::new();
+    let mut opened = Vec::new();
+    let mut reader = Reader::from_str(input);
+    loop {
+        match reader.read_event()? {
+            Event::Start(_) => {
+                opened.push(mixed.len());
+                mixed.push(false);
+            }
+            Event::End(_) => {
+                opened.pop();
+            }
+            Event::Text(e) if is_blank(&e) => {}
+            Event::Text(_) | Event::CData(_) => {
+                if let Some(&i) = opened.last() {
+                    mixed[i] = true;
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    // The second pass: write events, disabling indentation inside elements with mixed content
+    let mut mixed = mixed.into_iter();
+    let mut writer = Writer::new_with_indent(Vec::new(), indent_char, indent_size);
+    let mut reader = Reader::from_str(input);
+    let mut depth = 0;
+    // Depth of the outermost element with mixed content which is written now
+    let mut verbatim = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                depth += 1;
+                writer.write_event(Event::Start(e))?;
+                let is_mixed = mixed.next() == Some(true);
+                if verbatim.is_none() && is_mixed {
+                    verbatim = Some(depth);
+                    writer.set_indent(None);
+                }
+            }
+            Event::End(e) => {
+                writer.write_event(Event::End(e))?;
+                if verbatim == Some(depth) {
+                    verbatim = None;
+                    writer.set_indent(Some((indent_char, indent_size)));
+                }
+                depth -= 1;
+            }
+            Event::Text(e) if verbatim.is_none() && is_blank(&e) => {}
+            Event::Eof => break,
+            event => writer.write_event(event)?,
+        }
+    }
+    String::from_utf8(writer.into_inner()).map_err(|e| EncodingError::from(e.utf8_error()).into())
+}
+
 #[cfg(feature = "serialize")]
 pub(crate) struct ToFmtWrite<T>(pub T);
 
@@ -589,6 +1586,26 @@ where
     }
 }
 
+/// An adapter that counts bytes written to the inner writer
+#[cfg(feature = "serialize")]
+struct CountingWrite<'w, W> {
+    writer: &'w mut W,
+    count: &'w mut u64,
+}
+
+#[cfg(feature = "serialize")]
+impl<'w, W: Write> Write for CountingWrite<'w, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.writer.write(buf)?;
+        *self.count += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct Indentation {
     /// todo: this is an awkward fit as it has no impact on indentation logic, but it is
@@ -598,6 +1615,8 @@ pub(crate) struct Indentation {
     indent_char: u8,
     /// How many instances of the indent character ought to be used for each level of indentation
     indent_size: usize,
+    /// The line separator written before each indented line (e.g. `\n` or `\r\n`)
+    newline: &'static str,
     /// Used as a cache for the bytes used for indentation
     indents: Vec<u8>,
     /// The current amount of indentation
@@ -605,11 +1624,12 @@ pub(crate) struct Indentation {
 }
 
 impl Indentation {
-    pub fn new(indent_char: u8, indent_size: usize) -> Self {
+    pub fn new(indent_char: u8, indent_size: usize, newline: &'static str) -> Self {
         Self {
             should_line_break: false,
             indent_char,
             indent_size,
+            newline,
             indents: vec![indent_char; 128],
             current_indent_len: 0, // invariant - needs to remain less than indents.len()
         }
@@ -626,6 +1646,17 @@ impl Indentation {
         self.current_indent_len = self.current_indent_len.saturating_sub(self.indent_size);
     }
 
+    /// Sets indentation to the specified level of nesting
+    pub fn set_depth(&mut self, depth: usize) {
+        self.current_indent_len = depth * self.indent_size;
+        self.ensure(self.current_indent_len);
+    }
+
+    /// Returns line separator that should be written before the indent
+    pub fn newline(&self) -> &'static str {
+        self.newline
+    }
+
     /// Returns indent string for current level
     pub fn current(&self) -> &[u8] {
         &self.indents[..self.current_indent_len]
@@ -10,19 +10,23 @@ use crate::{ElementWriter, Writer};
 impl<W: AsyncWrite + Unpin> Writer<W> {
     /// Writes the given event to the underlying writer. Async version of [`Writer::write_event`].
     pub async fn write_event_async<'a, E: Into<Event<'a>>>(&mut self, event: E) -> Result<()> {
+        let event = event.into();
+        self.check_event_names(&event)?;
         let mut next_should_line_break = true;
-        let result = match event.into() {
+        let result = match event {
             Event::Start(e) => {
                 let result = self.write_wrapped_async(b"<", &e, b">").await;
                 if let Some(i) = self.indent.as_mut() {
                     i.grow();
                 }
+                self.depth += 1;
                 result
             }
             Event::End(e) => {
                 if let Some(i) = self.indent.as_mut() {
                     i.shrink();
                 }
+                self.depth = self.depth.saturating_sub(1);
                 self.write_wrapped_async(b"</", &e, b">").await
             }
             Event::Empty(e) => self.write_wrapped_async(b"<", &e, b"/>").await,
@@ -37,7 +41,11 @@ impl<W: AsyncWrite + Unpin> Writer<W> {
                 self.write_async(&e).await?;
                 self.write_async(b"]]>").await
             }
-            Event::Decl(e) => self.write_wrapped_async(b"<?", &e, b"?>").await,
+            Event::Decl(e) => {
+                let decl = self.encoded_decl(&e);
+                self.write_wrapped_async(b"<?", decl.as_ref().unwrap_or(&e), b"?>")
+                    .await
+            }
             Event::PI(e) => self.write_wrapped_async(b"<?", &e, b"?>").await,
             Event::DocType(e) => self.write_wrapped_async(b"<!DOCTYPE ", &e, b">").await,
             Event::Eof => Ok(()),
@@ -54,15 +62,19 @@ impl<W: AsyncWrite + Unpin> Writer<W> {
     /// This method will do nothing if `Writer` was not constructed with [`Writer::new_with_indent`].
     pub async fn write_indent_async(&mut self) -> Result<()> {
         if let Some(ref i) = self.indent {
-            self.writer.write_all(b"\n").await?;
+            self.writer.write_all(i.newline().as_bytes()).await?;
             self.writer.write_all(i.current()).await?;
+            self.bytes_written += (i.newline().len() + i.current().len()) as u64;
         }
         Ok(())
     }
 
     #[inline]
     async fn write_async(&mut self, value: &[u8]) -> Result<()> {
-        self.writer.write_all(value).await.map_err(Into::into)
+        let value = self.encode(value);
+        self.writer.write_all(&value).await?;
+        self.bytes_written += value.len() as u64;
+        Ok(())
     }
 
     #[inline]
@@ -74,8 +86,9 @@ impl<W: AsyncWrite + Unpin> Writer<W> {
     ) -> Result<()> {
         if let Some(ref i) = self.indent {
             if i.should_line_break {
-                self.writer.write_all(b"\n").await?;
+                self.writer.write_all(i.newline().as_bytes()).await?;
                 self.writer.write_all(i.current()).await?;
+                self.bytes_written += (i.newline().len() + i.current().len()) as u64;
             }
         }
         self.write_async(before).await?;
@@ -573,4 +586,35 @@ mod indentation_async {
 </paired>"#
         );
     }
+
+    #[tokio::test]
+    async fn bytes_written() {
+        let mut buffer = Vec::new();
+        let mut writer = Writer::new_with_indent(&mut buffer, b' ', 4);
+
+        let start = BytesStart::new("paired");
+        writer
+            .write_event_async(Event::Start(start.clone()))
+            .await
+            .expect("write start tag failed");
+        writer
+            .write_event_async(Event::Empty(BytesStart::new("inner")))
+            .await
+            .expect("write inner tag failed");
+        writer
+            .write_indent_async()
+            .await
+            .expect("write indent failed");
+        writer
+            .write_event_async(Event::End(start.to_end()))
+            .await
+            .expect("write end tag failed");
+
+        let count = writer.bytes_written();
+        assert_eq!(count, buffer.len() as u64);
+        assert_eq!(
+            std::str::from_utf8(&buffer).unwrap(),
+            "<paired>\n    <inner/>\n    \n</paired>"
+        );
+    }
 }
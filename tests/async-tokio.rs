@@ -219,3 +219,42 @@ async fn issue774() {
         End(BytesEnd::new("tag"))
     );
 }
+
+/// Reading from a slice does not block, so the synchronous `read_event()` can be
+/// used in asynchronous code. Events borrow from the input, so they can be kept
+/// across `.await` points
+#[tokio::test]
+async fn borrow_from_slice() {
+    async fn consume(events: &[quick_xml::events::Event<'_>]) -> usize {
+        tokio::task::yield_now().await;
+        events.len()
+    }
+
+    let input = String::from("<root>text</root>");
+    let mut reader = Reader::from_str(&input);
+    let mut events = Vec::new();
+    loop {
+        let event = reader.read_event().unwrap();
+        if event == Eof {
+            break;
+        }
+        events.push(event);
+        assert_eq!(consume(&events).await, events.len());
+    }
+    assert_eq!(
+        events,
+        vec![
+            Start(BytesStart::new("root")),
+            Text(BytesText::new("text")),
+            End(BytesEnd::new("root")),
+        ]
+    );
+    // Events are borrowed from the input
+    match &events[1] {
+        Text(e) => assert!(matches!(
+            e.clone().into_inner(),
+            std::borrow::Cow::Borrowed(_)
+        )),
+        e => panic!("Expected `Text(_)`, but got `{:?}`", e),
+    }
+}
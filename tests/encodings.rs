@@ -1,7 +1,11 @@
-use encoding_rs::{UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1251};
+use encoding_rs::{SHIFT_JIS, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1251, WINDOWS_1252};
 use pretty_assertions::assert_eq;
-use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event::*};
+use quick_xml::encoding::Decoder;
+use quick_xml::events::{
+    BytesCData, BytesDecl, BytesEnd, BytesPI, BytesStart, BytesText, Event::*,
+};
 use quick_xml::reader::Reader;
+use quick_xml::writer::Writer;
 
 mod decode {
     use super::*;
@@ -259,6 +263,38 @@ fn only_one_declaration_changes_encoding() {
     assert_eq!(reader.read_event_into(&mut buf).unwrap(), Eof);
 }
 
+/// Checks that encoding forced by `set_encoding` is used instead of the declared one
+#[test]
+fn set_encoding_overrides_declaration() {
+    // Document is encoded in windows-1251, but declares UTF-8
+    let (text, _, _) = WINDOWS_1251.encode("текст");
+    let mut xml = b"<?xml encoding='UTF-8'?><tag>".to_vec();
+    xml.extend_from_slice(&text);
+    xml.extend_from_slice(b"</tag>");
+
+    let mut reader = Reader::from_reader(xml.as_ref());
+    reader.set_encoding(WINDOWS_1251);
+    let mut buf = Vec::new();
+
+    assert_eq!(reader.decoder().encoding(), WINDOWS_1251);
+    assert!(matches!(reader.read_event_into(&mut buf).unwrap(), Decl(_)));
+    assert_eq!(reader.decoder().encoding(), WINDOWS_1251);
+
+    assert_eq!(
+        reader.read_event_into(&mut buf).unwrap(),
+        Start(BytesStart::new("tag"))
+    );
+    match reader.read_event_into(&mut buf).unwrap() {
+        Text(e) => assert_eq!(e.unescape().unwrap(), "текст"),
+        e => panic!("Expected `Text`, but got `{:?}`", e),
+    }
+    assert_eq!(
+        reader.read_event_into(&mut buf).unwrap(),
+        End(BytesEnd::new("tag"))
+    );
+    assert_eq!(reader.read_event_into(&mut buf).unwrap(), Eof);
+}
+
 /// Checks that XML declaration cannot change the encoding from UTF-8 if
 /// a `Reader` was created using `from_str` method
 #[test]
@@ -271,3 +307,196 @@ fn str_always_has_utf8() {
 
     assert_eq!(reader.read_event().unwrap(), Eof);
 }
+
+#[test]
+fn read_event_into_decoded() {
+    let (xml, _, _) = WINDOWS_1251.encode(concat!(
+        r#"<?xml version="1.0" encoding="windows-1251"?>"#,
+        "<тег атрибут='значение'><?цель данные?>текст<![CDATA[данные]]></тег>",
+    ));
+    let mut reader = Reader::from_reader(xml.as_ref());
+    let mut buf = Vec::new();
+    let mut str_buf = String::new();
+
+    let mut next = || {
+        buf.clear();
+        reader
+            .read_event_into_decoded(&mut buf, &mut str_buf)
+            .unwrap()
+            .into_owned()
+    };
+    // Content is decoded, so the declared encoding is changed too
+    assert_eq!(next(), Decl(BytesDecl::new("1.0", Some("UTF-8"), None)));
+    let start = BytesStart::from_content("тег атрибут='значение'", "тег".len());
+    let attr = start.attributes().next().unwrap().unwrap();
+    assert_eq!(
+        attr.decode_and_unescape_value(Decoder::utf8()).unwrap(),
+        "значение"
+    );
+    assert_eq!(next(), Start(start));
+    assert_eq!(next(), PI(BytesPI::new("цель данные")));
+    assert_eq!(next(), Text(BytesText::new("текст")));
+    assert_eq!(next(), CData(BytesCData::new("данные")));
+    assert_eq!(next(), End(BytesEnd::new("тег")));
+    assert_eq!(next(), Eof);
+}
+
+/// Checks that names of elements and attributes are decoded using the document encoding
+mod names {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::name::QName;
+
+    #[test]
+    fn ascii_names_in_shift_jis() {
+        let (xml, _, _) = SHIFT_JIS.encode(concat!(
+            r#"<?xml version="1.0" encoding="Shift_JIS"?>"#,
+            "<root lang='日本語'>テキスト</root>",
+        ));
+        let mut reader = Reader::from_reader(xml.as_ref());
+        let mut buf = Vec::new();
+
+        assert!(matches!(reader.read_event_into(&mut buf).unwrap(), Decl(_)));
+        assert_eq!(reader.decoder().encoding(), SHIFT_JIS);
+
+        match reader.read_event_into(&mut buf).unwrap() {
+            Start(e) => {
+                // Raw comparison of ASCII names is valid in ASCII-compatible encoding
+                assert_eq!(e.name(), QName(b"root"));
+                assert_eq!(e.name().decode(reader.decoder()).unwrap(), "root");
+
+                let attr = e.attributes().next().unwrap().unwrap();
+                assert_eq!(attr.key, QName(b"lang"));
+                assert_eq!(attr.decode_key(reader.decoder()).unwrap(), "lang");
+                assert_eq!(
+                    attr.decode_and_unescape_value(reader.decoder()).unwrap(),
+                    "日本語"
+                );
+            }
+            e => panic!("Expected `Start`, but got `{:?}`", e),
+        }
+    }
+
+    #[test]
+    fn non_ascii_names_in_shift_jis() {
+        let (xml, _, _) = SHIFT_JIS.encode(concat!(
+            r#"<?xml version="1.0" encoding="Shift_JIS"?>"#,
+            "<要素 属性='値'/>",
+        ));
+        let mut reader = Reader::from_reader(xml.as_ref());
+        let mut buf = Vec::new();
+
+        assert!(matches!(reader.read_event_into(&mut buf).unwrap(), Decl(_)));
+
+        match reader.read_event_into(&mut buf).unwrap() {
+            Empty(e) => {
+                // Raw bytes are in Shift_JIS, so they differ from UTF-8
+                assert_ne!(e.name(), QName("要素".as_bytes()));
+                assert_eq!(e.name().decode(reader.decoder()).unwrap(), "要素");
+
+                let attr = e.attributes().next().unwrap().unwrap();
+                assert_eq!(attr.decode_key(reader.decoder()).unwrap(), "属性");
+            }
+            e => panic!("Expected `Empty`, but got `{:?}`", e),
+        }
+    }
+}
+
+/// Checks that a document decoded to UTF-8 events is written back byte-to-byte
+/// when writer uses the same encoding
+mod encode {
+    use super::*;
+    use encoding_rs::Encoding;
+    use pretty_assertions::assert_eq;
+
+    fn roundtrip(encoding: &'static Encoding, xml: &str) {
+        let (xml, _, unmappable) = encoding.encode(xml);
+        assert!(!unmappable);
+
+        let mut reader = Reader::from_reader(xml.as_ref());
+        let mut writer = Writer::new(Vec::new());
+        writer.set_encoding(encoding);
+        assert_eq!(writer.encoding(), encoding);
+
+        let mut buf = Vec::new();
+        let mut str_buf = String::new();
+        loop {
+            buf.clear();
+            match reader
+                .read_event_into_decoded(&mut buf, &mut str_buf)
+                .unwrap()
+            {
+                Eof => break,
+                event => writer.write_event(event).unwrap(),
+            }
+        }
+        assert_eq!(writer.into_inner(), xml.as_ref());
+    }
+
+    #[test]
+    fn windows_1251() {
+        roundtrip(
+            WINDOWS_1251,
+            concat!(
+                r#"<?xml version="1.0" encoding="windows-1251"?>"#,
+                "<тег атрибут='значение'><?цель данные?>текст<![CDATA[данные]]></тег>",
+            ),
+        );
+    }
+
+    #[test]
+    fn windows_1252() {
+        roundtrip(
+            WINDOWS_1252,
+            concat!(
+                r#"<?xml version="1.0" encoding="windows-1252" standalone="yes"?>"#,
+                "<café prix='5 €'>crème brûlée</café>",
+            ),
+        );
+    }
+
+    #[test]
+    fn shift_jis() {
+        roundtrip(
+            SHIFT_JIS,
+            concat!(
+                r#"<?xml version="1.0" encoding="Shift_JIS"?>"#,
+                "<要素 属性='値'>テキスト</要素>",
+            ),
+        );
+    }
+
+    /// Characters that cannot be represented in the output encoding are written
+    /// as numeric character references
+    #[test]
+    fn unmappable() {
+        let mut writer = Writer::new(Vec::new());
+        writer.set_encoding(WINDOWS_1252);
+        writer
+            .create_element("tag")
+            .with_attribute(("attr", "значение"))
+            .write_text_content(BytesText::new("€ и"))
+            .unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            b"<tag attr=\"&#1079;&#1085;&#1072;&#1095;&#1077;&#1085;&#1080;&#1077;\">\x80 &#1080;</tag>",
+        );
+    }
+
+    /// The declaration encoding is replaced by the output encoding
+    #[test]
+    fn declaration() {
+        let mut writer = Writer::new(Vec::new());
+        writer.set_encoding(WINDOWS_1251);
+        writer
+            .write_event(Decl(BytesDecl::new("1.0", Some("UTF-8"), Some("no"))))
+            .unwrap();
+        writer.write_bom().unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            br#"<?xml version="1.0" encoding="windows-1251" standalone="no"?>"#,
+        );
+    }
+}
@@ -51,9 +51,94 @@ fn minimal_escape() {
     assert_eq!(
         escape::minimal_escape("prefix_\"a\"b&<>c"),
         "prefix_\"a\"b&amp;&lt;>c"
+    ); // `]]>` is not allowed in text
+    assert_eq!(escape::minimal_escape("a]]>b]>c]]]>"), "a]]&gt;b]>c]]]&gt;");
+}
+
+#[test]
+fn escape_with() {
+    let amp = |ch| ch == b'&';
+    assert_eq!(escape::escape_with("test", amp), Cow::Borrowed("test"));
+    assert_eq!(escape::escape_with("<&\"'>", amp), "<&amp;\"'>");
+    assert_eq!(
+        escape::escape_with("<&\"'>", |_| true),
+        "&lt;&amp;&quot;&apos;&gt;"
+    );
+    // Characters without predefined entities are escaped as character references
+    assert_eq!(
+        escape::escape_with("a\tb=c", |ch| ch == b'\t' || ch == b'='),
+        "a&#9;b&#61;c"
+    );
+    // Non-ASCII characters are never escaped
+    assert_eq!(
+        escape::escape_with("\u{444}", |_| true),
+        Cow::Borrowed("\u{444}")
     );
 }
 
+/// Checks that characters are found in the long strings, where bulk search
+/// is used, and in their tails
+#[test]
+fn escape_long() {
+    let mut raw = String::new();
+    let mut expected = String::new();
+    for (i, (ch, replacement)) in [
+        ('<', "&lt;"),
+        ('>', "&gt;"),
+        ('&', "&amp;"),
+        ('\'', "&apos;"),
+        ('"', "&quot;"),
+    ]
+    .iter()
+    .cycle()
+    .take(50)
+    .enumerate()
+    {
+        let text = "x".repeat(i * 7);
+        raw.push_str(&text);
+        raw.push(*ch);
+        expected.push_str(&text);
+        expected.push_str(replacement);
+    }
+    raw.push_str("tail");
+    expected.push_str("tail");
+
+    assert_eq!(escape::escape(raw.as_str()), expected);
+    assert_eq!(
+        escape::partial_escape(raw.as_str()),
+        expected.replace("&apos;", "'").replace("&quot;", "\"")
+    );
+    assert_eq!(
+        escape::minimal_escape(raw.as_str()),
+        expected
+            .replace("&apos;", "'")
+            .replace("&quot;", "\"")
+            .replace("&gt;", ">")
+    );
+}
+
+#[test]
+fn needs_escaping() {
+    assert_eq!(escape::needs_escaping(b""), false);
+    assert_eq!(escape::needs_escaping(b"test"), false);
+    assert_eq!(escape::needs_escaping("тест".as_bytes()), false);
+    for special in [b"<", b">", b"&", b"'", b"\""] {
+        let mut text = b"prefix_".to_vec();
+        text.extend_from_slice(special);
+        text.extend_from_slice(b"_suffix");
+        assert_eq!(escape::needs_escaping(&text), true, "{:?}", special);
+    }
+}
+
+#[test]
+fn needs_unescaping() {
+    assert_eq!(escape::needs_unescaping(b""), false);
+    assert_eq!(escape::needs_unescaping(b"<test>\"'"), false);
+    assert_eq!(escape::needs_unescaping(b"&lt;test&gt;"), true);
+    // Malformed references still need to be processed to report an error
+    assert_eq!(escape::needs_unescaping(b"a & b"), true);
+}
+
 #[test]
 fn unescape() {
     let unchanged = escape::unescape("test");
@@ -75,6 +160,44 @@ fn unescape() {
     );
 }
 
+#[test]
+fn partial_unescape() {
+    let unchanged = escape::partial_unescape("test");
+    // assert_eq does not check that Cow is borrowed, but we explicitly use Cow
+    // because it influences diff
+    // TODO: use assert_matches! when stabilized and other features will bump MSRV
+    assert_eq!(unchanged, Ok(Cow::Borrowed("test")));
+    assert!(matches!(unchanged, Ok(Cow::Borrowed(_))));
+
+    assert_eq!(
+        escape::partial_unescape("&lt;&amp;test&apos;&quot;&gt;"),
+        Ok("<&test'\">".into())
+    );
+    assert_eq!(escape::partial_unescape("&#x30;"), Ok("0".into()));
+    assert_eq!(escape::partial_unescape("&custom;"), Ok("&custom;".into()));
+    assert_eq!(
+        escape::partial_unescape("&custom;&amp;&other;text"),
+        Ok("&custom;&&other;text".into())
+    );
+    assert_eq!(
+        escape::partial_unescape("&custom"),
+        Err(EscapeError::UnterminatedEntity(0..7))
+    );
+}
+
+#[test]
+fn partial_unescape_with() {
+    let custom_entities = |ent: &str| match ent {
+        "foo" => Some("BAR"),
+        _ => None,
+    };
+
+    assert_eq!(
+        escape::partial_unescape_with("&foo;&lt;&#48;", custom_entities),
+        Ok("BAR&lt;0".into())
+    );
+}
+
 /// XML allows any number of leading zeroes. That is not explicitly mentioned
 /// in the specification, but enforced by the conformance test suite
 /// (https://www.w3.org/XML/Test/)
@@ -0,0 +1,53 @@
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::reader::Reader;
+
+use pretty_assertions::assert_eq;
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn read() {
+    let compressed = compress(b"<root><item>text</item></root>");
+    let mut reader = Reader::from_gzip_reader(compressed.as_slice());
+    let mut buf = Vec::new();
+
+    assert_eq!(
+        reader.read_event_into(&mut buf).unwrap(),
+        Event::Start(BytesStart::new("root"))
+    );
+    assert_eq!(
+        reader.read_event_into(&mut buf).unwrap(),
+        Event::Start(BytesStart::new("item"))
+    );
+    assert_eq!(
+        reader.read_event_into(&mut buf).unwrap(),
+        Event::Text(BytesText::new("text"))
+    );
+    assert_eq!(
+        reader.read_event_into(&mut buf).unwrap(),
+        Event::End(BytesEnd::new("item"))
+    );
+    assert_eq!(
+        reader.read_event_into(&mut buf).unwrap(),
+        Event::End(BytesEnd::new("root"))
+    );
+    assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+    // Position is counted in decompressed bytes
+    assert_eq!(reader.buffer_position(), 30);
+}
+
+#[test]
+fn not_compressed() {
+    let mut reader = Reader::from_gzip_reader(b"<root/>".as_ref());
+    let mut buf = Vec::new();
+
+    assert!(reader.read_event_into(&mut buf).is_err());
+}
@@ -163,6 +163,8 @@ mod issue514 {
                 IllFormedError::MismatchedEndTag {
                     expected: "some-tag".into(),
                     found: "other-tag".into(),
+                    expected_position: 0,
+                    found_position: 26,
                 }
             ),
             x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
@@ -6,7 +6,7 @@
 //! Please keep tests sorted (exceptions are allowed if options are tightly related).
 
 use quick_xml::errors::{Error, IllFormedError};
-use quick_xml::events::{BytesCData, BytesEnd, BytesPI, BytesStart, BytesText, Event};
+use quick_xml::events::{BytesCData, BytesDecl, BytesEnd, BytesPI, BytesStart, BytesText, Event};
 use quick_xml::reader::Reader;
 
 mod allow_unmatched_ends {
@@ -57,6 +57,104 @@ mod allow_unmatched_ends {
     }
 }
 
+mod check_characters {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn false_() {
+        let mut reader = Reader::from_str("<tag>a]]>b</tag>");
+        reader.config_mut().check_characters = false;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("tag"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::from_escaped("a]]>b"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("tag"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    mod true_ {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn allowed() {
+            let mut reader = Reader::from_str("<tag>a]]b]>c></tag><![CDATA[]]>");
+            reader.config_mut().check_characters = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::from_escaped("a]]b]>c>"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::CData(BytesCData::new(""))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn cdata_end() {
+            let mut reader = Reader::from_str("<tag>a]]>b</tag>");
+            reader.config_mut().check_characters = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            match reader.read_event() {
+                Err(Error::IllFormed(cause)) => {
+                    assert_eq!(cause, IllFormedError::CDataEndInText)
+                }
+                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+            }
+            assert_eq!(reader.error_position(), 6);
+            // Parsing can be continued
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn cdata_end_at_eof() {
+            let mut buf = Vec::new();
+            let mut reader = Reader::from_str("<tag/>]]>");
+            reader.config_mut().check_characters = true;
+
+            assert_eq!(
+                reader.read_event_into(&mut buf).unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            match reader.read_event_into(&mut buf) {
+                Err(Error::IllFormed(cause)) => {
+                    assert_eq!(cause, IllFormedError::CDataEndInText)
+                }
+                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+            }
+            assert_eq!(reader.error_position(), 6);
+            assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+        }
+    }
+}
+
 mod check_comments {
     use super::*;
 
@@ -276,157 +374,1141 @@ mod check_comments {
             assert_eq!(reader.read_event().unwrap(), Event::Eof);
         }
     }
-}
+}
+
+mod check_decl_position {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn false_() {
+        let mut reader = Reader::from_str("<tag/><?xml version='1.0'?>");
+        reader.config_mut().check_decl_position = false;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Empty(BytesStart::new("tag"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Decl(BytesDecl::from_start(BytesStart::from_content(
+                "xml version='1.0'",
+                3
+            )))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    mod true_ {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn first() {
+            let mut reader = Reader::from_str("<?xml version='1.0'?><tag/>");
+            reader.config_mut().check_decl_position = true;
+
+            assert!(matches!(reader.read_event().unwrap(), Event::Decl(_)));
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// BOM that was not consumed is allowed before the declaration
+        #[test]
+        fn after_bom() {
+            let mut reader = Reader::from_str("\u{FEFF}<?xml version='1.0'?>");
+            reader.config_mut().check_decl_position = true;
+            reader.config_mut().consume_bom = false;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new("\u{FEFF}"))
+            );
+            assert!(matches!(reader.read_event().unwrap(), Event::Decl(_)));
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn after_content() {
+            let mut reader = Reader::from_str("<tag/> <?xml version='1.0'?>");
+            reader.config_mut().check_decl_position = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new(" "))
+            );
+            match reader.read_event() {
+                Err(Error::IllFormed(cause)) => {
+                    assert_eq!(cause, IllFormedError::MisplacedDeclaration)
+                }
+                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+            }
+            assert_eq!(reader.error_position(), 7);
+            // Parsing can be continued
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// Whitespaces before the declaration are not allowed even if they are
+        /// not returned as a text
+        #[test]
+        fn after_trimmed_text() {
+            let mut reader = Reader::from_str("  <?xml version='1.0'?><tag/>");
+            reader.config_mut().check_decl_position = true;
+            reader.config_mut().trim_text(true);
+
+            match reader.read_event() {
+                Err(Error::IllFormed(cause)) => {
+                    assert_eq!(cause, IllFormedError::MisplacedDeclaration)
+                }
+                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+            }
+            assert_eq!(reader.error_position(), 2);
+            // Parsing can be continued
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// Whitespaces between the BOM and the declaration are not allowed even
+        /// if they are not returned as a text
+        #[test]
+        fn after_bom_and_trimmed_text() {
+            let mut reader = Reader::from_str("\u{FEFF}  <?xml version='1.0'?>");
+            reader.config_mut().check_decl_position = true;
+            reader.config_mut().consume_bom = false;
+            reader.config_mut().trim_text(true);
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new("\u{FEFF}"))
+            );
+            match reader.read_event() {
+                Err(Error::IllFormed(cause)) => {
+                    assert_eq!(cause, IllFormedError::MisplacedDeclaration)
+                }
+                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+            }
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn second() {
+            let mut reader = Reader::from_str("<?xml version='1.0'?><?xml version='1.0'?>");
+            reader.config_mut().check_decl_position = true;
+
+            assert!(matches!(reader.read_event().unwrap(), Event::Decl(_)));
+            match reader.read_event() {
+                Err(Error::IllFormed(cause)) => {
+                    assert_eq!(cause, IllFormedError::MisplacedDeclaration)
+                }
+                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+            }
+            assert_eq!(reader.error_position(), 21);
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// Processing instructions are allowed anywhere
+        #[test]
+        fn pi() {
+            let mut reader = Reader::from_str("<tag/><?xml-stylesheet?>");
+            reader.config_mut().check_decl_position = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::PI(BytesPI::new("xml-stylesheet"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// Declaration split between reads is still the first thing in the document
+        #[test]
+        fn resumed() {
+            use quick_xml::errors::SyntaxError;
+            use std::io::Cursor;
+
+            let mut buf = Vec::new();
+            let mut reader = Reader::from_reader(Cursor::new(b"<?xml vers".to_vec()));
+            reader.config_mut().check_decl_position = true;
+            reader.config_mut().resume_incomplete_markup = true;
+
+            match reader.read_event_into(&mut buf) {
+                Err(Error::Syntax(cause)) => {
+                    assert_eq!(cause, SyntaxError::UnclosedPIOrXmlDecl)
+                }
+                x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+            }
+            reader
+                .get_mut()
+                .get_mut()
+                .extend_from_slice(b"ion='1.0'?><a/>");
+            assert_eq!(
+                reader.read_event_into(&mut buf).unwrap(),
+                Event::Decl(BytesDecl::from_start(BytesStart::from_content(
+                    "xml version='1.0'",
+                    3
+                )))
+            );
+            buf.clear();
+            assert_eq!(
+                reader.read_event_into(&mut buf).unwrap(),
+                Event::Empty(BytesStart::new("a"))
+            );
+            assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+        }
+    }
+}
+
+mod check_end_names {
+    use super::*;
+
+    mod false_ {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn matched_tags() {
+            let mut reader = Reader::from_str("<tag><tag></tag></tag>");
+            reader.config_mut().check_end_names = false;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn mismatched_tags() {
+            let mut reader = Reader::from_str("<tag><tag></mismatched></tag>");
+            reader.config_mut().check_end_names = false;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("mismatched"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+    }
+
+    mod true_ {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn matched_tags() {
+            let mut reader = Reader::from_str("<tag><tag></tag></tag>");
+            reader.config_mut().check_end_names = false;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn mismatched_tags() {
+            let mut reader = Reader::from_str("<tag><tag></mismatched></tag>");
+            reader.config_mut().check_end_names = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Start(BytesStart::new("tag"))
+            );
+            match reader.read_event() {
+                Err(Error::IllFormed(cause)) => assert_eq!(
+                    cause,
+                    IllFormedError::MismatchedEndTag {
+                        expected: "tag".into(),
+                        found: "mismatched".into(),
+                        expected_position: 5,
+                        found_position: 10,
+                    }
+                ),
+                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+            }
+            // #513: We want to continue parsing after the error
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::End(BytesEnd::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+    }
+}
+
+mod consume_bom {
+    use super::*;
+
+    const XML: &[u8] = b"\xEF\xBB\xBFtext<tag/>";
+
+    /// BOM should not be a part of the first text event
+    mod true_ {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn borrowed() {
+            let mut reader = Reader::from_reader(XML);
+            reader.config_mut().consume_bom = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new("text"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn buffered() {
+            let mut reader = Reader::from_reader(XML);
+            reader.config_mut().consume_bom = true;
+            let mut buf = Vec::new();
+
+            assert_eq!(
+                reader.read_event_into(&mut buf).unwrap(),
+                Event::Text(BytesText::new("text"))
+            );
+            assert_eq!(
+                reader.read_event_into(&mut buf).unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn str() {
+            let mut reader = Reader::from_str("\u{FEFF}text<tag/>");
+            reader.config_mut().consume_bom = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new("text"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+    }
+
+    /// BOM should be returned as the beginning of the first text event
+    mod false_ {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn borrowed() {
+            let mut reader = Reader::from_reader(XML);
+            reader.config_mut().consume_bom = false;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new("\u{FEFF}text"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn buffered() {
+            let mut reader = Reader::from_reader(XML);
+            reader.config_mut().consume_bom = false;
+            let mut buf = Vec::new();
+
+            assert_eq!(
+                reader.read_event_into(&mut buf).unwrap(),
+                Event::Text(BytesText::new("\u{FEFF}text"))
+            );
+            assert_eq!(
+                reader.read_event_into(&mut buf).unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+        }
+
+        #[test]
+        fn str() {
+            let mut reader = Reader::from_str("\u{FEFF}text<tag/>");
+            reader.config_mut().consume_bom = false;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new("\u{FEFF}text"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// Encoding still detected from the BOM
+        #[cfg(feature = "encoding")]
+        #[test]
+        fn detect_encoding() {
+            let mut reader = Reader::from_reader(XML);
+            reader.config_mut().consume_bom = false;
+
+            assert_eq!(reader.decoder().encoding(), encoding_rs::UTF_8);
+            reader.read_event().unwrap();
+            assert_eq!(reader.decoder().encoding(), encoding_rs::UTF_8);
+        }
+    }
+}
+
+mod expand_empty_elements {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Self-closed elements should be reported as one `Empty` event
+    #[test]
+    fn false_() {
+        let mut reader = Reader::from_str("<root/>");
+        reader.config_mut().expand_empty_elements = false;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Empty(BytesStart::new("root"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    /// Self-closed elements should be reported as two events
+    #[test]
+    fn true_() {
+        let mut reader = Reader::from_str("<root/>");
+        reader.config_mut().expand_empty_elements = true;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("root"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    /// Both synthesized events should report position after the self-closed tag,
+    /// because the `End` event does not consume any input
+    #[test]
+    fn positions() {
+        let mut reader = Reader::from_str("<a/><b/>");
+        reader.config_mut().expand_empty_elements = true;
+
+        assert_eq!(reader.buffer_position(), 0);
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("a"))
+        );
+        assert_eq!(reader.buffer_position(), 4);
+        assert_eq!(reader.read_event().unwrap(), Event::End(BytesEnd::new("a")));
+        assert_eq!(reader.buffer_position(), 4);
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("b"))
+        );
+        assert_eq!(reader.buffer_position(), 8);
+        assert_eq!(reader.read_event().unwrap(), Event::End(BytesEnd::new("b")));
+        assert_eq!(reader.buffer_position(), 8);
+
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        assert_eq!(reader.buffer_position(), 8);
+        assert_eq!(reader.error_position(), 0);
+    }
+}
+
+mod max_text_len {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::BufReader;
+
+    #[test]
+    fn none() {
+        let text = "x".repeat(100_000);
+        let xml = format!("<root>{}</root>", text);
+        let mut reader = Reader::from_str(&xml);
+        reader.config_mut().max_text_len = None;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::new(&text))
+        );
+    }
+
+    #[test]
+    fn borrowed() {
+        let mut reader = Reader::from_str("<root>12345</root><root>123456</root>");
+        reader.config_mut().max_text_len = Some(5);
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        // Text of exactly the maximum length is allowed
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::new("12345"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("root"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        match reader.read_event() {
+            Err(Error::IllFormed(cause)) => assert_eq!(cause, IllFormedError::TextTooLong(5)),
+            x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+        }
+        assert_eq!(reader.error_position(), 24);
+        // Reading cannot be continued after the error
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    /// The text without markup is read until the limit is exceeded,
+    /// and not until the end of input
+    #[test]
+    fn buffered() {
+        let text = "x".repeat(100_000);
+        let mut reader = Reader::from_reader(BufReader::with_capacity(16, text.as_bytes()));
+        reader.config_mut().max_text_len = Some(100);
+
+        let mut buf = Vec::new();
+        match reader.read_event_into(&mut buf) {
+            Err(Error::IllFormed(cause)) => assert_eq!(cause, IllFormedError::TextTooLong(100)),
+            x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+        }
+        assert!(buf.len() <= 116, "{}", buf.len());
+        assert_eq!(reader.error_position(), 0);
+        assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+    }
+}
+
+mod normalize_line_endings {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::borrow::Cow;
+
+    const XML: &str =
+        "<root attr='a\r\nb\rc\nd'>\r\nline1\r\nline2\rline3\nline4\r\r\n<![CDATA[x\ry]]></root>";
+
+    #[test]
+    fn false_() {
+        let mut reader = Reader::from_str(XML);
+        reader.config_mut().normalize_line_endings = false;
+
+        match reader.read_event().unwrap() {
+            Event::Start(e) => {
+                assert_eq!(e.name().as_ref(), b"root");
+                assert_eq!(
+                    e.try_get_attribute("attr").unwrap().unwrap().value,
+                    Cow::Borrowed(b"a\r\nb\rc\nd")
+                );
+            }
+            x => panic!("Expected `Start`, but got `{:?}`", x),
+        }
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::from_escaped(
+                "\r\nline1\r\nline2\rline3\nline4\r\r\n"
+            ))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::CData(BytesCData::new("x\ry"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("root"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    #[test]
+    fn true_() {
+        let mut reader = Reader::from_str(XML);
+        reader.config_mut().normalize_line_endings = true;
+
+        match reader.read_event().unwrap() {
+            Event::Start(e) => {
+                assert_eq!(e.name().as_ref(), b"root");
+                assert_eq!(
+                    e.try_get_attribute("attr").unwrap().unwrap().value,
+                    Cow::Borrowed(b"a\nb\nc\nd")
+                );
+            }
+            x => panic!("Expected `Start`, but got `{:?}`", x),
+        }
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::from_escaped("\nline1\nline2\nline3\nline4\n\n"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::CData(BytesCData::new("x\ny"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("root"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    /// Texts without `\r` borrow from the input
+    #[test]
+    fn true_borrowed() {
+        let mut reader = Reader::from_str("<root attr='a\nb'>text\nline</root>");
+        reader.config_mut().normalize_line_endings = true;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::from_content("root attr='a\nb'", 4))
+        );
+        match reader.read_event().unwrap() {
+            Event::Text(e) => assert!(matches!(e.into_inner(), Cow::Borrowed(_))),
+            x => panic!("Expected `Text`, but got `{:?}`", x),
+        }
+    }
+
+    /// Line endings in self-closed tags are also normalized, trimming of text
+    /// is applied before normalization
+    #[test]
+    fn true_empty_and_trim() {
+        let mut reader = Reader::from_str("<tag attr='\r\n'/>\r\ntext\r\n\r\n<tag/>");
+        let config = reader.config_mut();
+        config.normalize_line_endings = true;
+        config.trim_text(true);
+
+        match reader.read_event().unwrap() {
+            Event::Empty(e) => assert_eq!(
+                e.try_get_attribute("attr").unwrap().unwrap().value,
+                Cow::Borrowed(b"\n")
+            ),
+            x => panic!("Expected `Empty`, but got `{:?}`", x),
+        }
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::new("text"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Empty(BytesStart::new("tag"))
+        );
+    }
+}
+
+#[cfg(feature = "encoding")]
+mod require_bom {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::encoding::EncodingError;
+
+    #[test]
+    fn false_() {
+        let mut reader = Reader::from_reader(b"<tag/>".as_ref());
+        reader.config_mut().require_bom = false;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Empty(BytesStart::new("tag"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    mod true_ {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn with_bom() {
+            let mut reader = Reader::from_reader(b"\xEF\xBB\xBF<tag/>".as_ref());
+            reader.config_mut().require_bom = true;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// BOM is required, but is not consumed
+        #[test]
+        fn with_kept_bom() {
+            let mut reader = Reader::from_reader(b"\xEF\xBB\xBF<tag/>".as_ref());
+            reader.config_mut().require_bom = true;
+            reader.config_mut().consume_bom = false;
+
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Text(BytesText::new("\u{FEFF}"))
+            );
+            assert_eq!(
+                reader.read_event().unwrap(),
+                Event::Empty(BytesStart::new("tag"))
+            );
+            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        }
+
+        /// Encoding detected by the first bytes is not enough
+        #[test]
+        fn without_bom() {
+            let mut reader = Reader::from_reader(b"<?xml version='1.0'?><tag/>".as_ref());
+            reader.config_mut().require_bom = true;
+
+            match reader.read_event() {
+                Err(Error::Encoding(EncodingError::MissingBom)) => {}
+                x => panic!("Expected `Err(Encoding(MissingBom))`, but got `{:?}`", x),
+            }
+            // Reading can be continued after error
+            assert!(matches!(reader.read_event().unwrap(), Event::Decl(_)));
+        }
+
+        #[test]
+        fn str() {
+            let mut reader = Reader::from_str("<tag/>");
+            reader.config_mut().require_bom = true;
+
+            match reader.read_event() {
+                Err(Error::Encoding(EncodingError::MissingBom)) => {}
+                x => panic!("Expected `Err(Encoding(MissingBom))`, but got `{:?}`", x),
+            }
+        }
+    }
+}
+
+mod presets {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::reader::Config;
+
+    #[test]
+    fn strict() {
+        let mut reader = Reader::from_str_strict("<tag><!-- a--b --></other>");
+        assert_eq!(reader.config(), &Config::strict());
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("tag"))
+        );
+        match reader.read_event() {
+            Err(Error::IllFormed(cause)) => {
+                assert_eq!(cause, IllFormedError::DoubleHyphenInComment);
+            }
+            x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+        }
+        match reader.read_event() {
+            Err(Error::IllFormed(cause)) => assert_eq!(
+                cause,
+                IllFormedError::MismatchedEndTag {
+                    expected: "tag".into(),
+                    found: "other".into(),
+                    expected_position: 0,
+                    found_position: 18,
+                }
+            ),
+            x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+        }
+    }
+
+    #[test]
+    fn lenient() {
+        let mut reader = Reader::from_str("<tag><!-- a--b --></other></unmatched>");
+        *reader.config_mut() = Config::lenient();
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("tag"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Comment(BytesText::from_escaped(" a--b "))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("other"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("unmatched"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+}
+
+mod resume_incomplete_markup {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::errors::SyntaxError;
+    use std::io::Cursor;
+
+    /// Creates a reader over a growable input with the option enabled
+    fn reader(xml: &str) -> Reader<Cursor<Vec<u8>>> {
+        let mut reader = Reader::from_reader(Cursor::new(xml.as_bytes().to_vec()));
+        reader.config_mut().resume_incomplete_markup = true;
+        reader
+    }
+
+    /// Appends data to the end of input of the reader
+    fn append(reader: &mut Reader<Cursor<Vec<u8>>>, xml: &str) {
+        reader.get_mut().get_mut().extend_from_slice(xml.as_bytes());
+    }
+
+    #[test]
+    fn false_() {
+        let mut buf = Vec::new();
+        let mut reader = Reader::from_reader(Cursor::new(b"<tag attr".to_vec()));
+        reader.config_mut().resume_incomplete_markup = false;
+
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        reader.get_mut().get_mut().extend_from_slice(b"=\"value\">");
+        assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+    }
+
+    #[test]
+    fn tag() {
+        let mut buf = Vec::new();
+        let mut reader = reader("<root><tag attr");
+
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        assert_eq!(reader.error_position(), 6);
+        assert_eq!(reader.buffer_position(), 15);
+        // Not enough data yet
+        append(&mut reader, "=\"va");
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        // `>` inside the quoted value should not end the tag
+        append(&mut reader, ">\"/>");
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Empty(BytesStart::from_content(r#"tag attr="va>""#, 3))
+        );
+        assert_eq!(reader.buffer_position(), 23);
+        buf.clear();
+        assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+    }
+
+    #[test]
+    fn only_lt() {
+        let mut buf = Vec::new();
+        let mut reader = reader("<");
+
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        append(&mut reader, "tag/>");
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Empty(BytesStart::new("tag"))
+        );
+    }
+
+    #[test]
+    fn comment() {
+        let mut buf = Vec::new();
+        let mut reader = reader("<!");
+
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::InvalidBangMarkup),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        append(&mut reader, "-- comment -");
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedComment),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        append(&mut reader, "->");
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Comment(BytesText::new(" comment "))
+        );
+        assert_eq!(reader.buffer_position(), 16);
+    }
 
-mod check_end_names {
-    use super::*;
+    #[test]
+    fn pi() {
+        let mut buf = Vec::new();
+        let mut reader = reader("<?pi data?");
 
-    mod false_ {
-        use super::*;
-        use pretty_assertions::assert_eq;
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedPIOrXmlDecl),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        append(&mut reader, ">");
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::PI(BytesPI::new("pi data"))
+        );
+    }
 
-        #[test]
-        fn matched_tags() {
-            let mut reader = Reader::from_str("<tag><tag></tag></tag>");
-            reader.config_mut().check_end_names = false;
+    /// Bytes of the incomplete markup are lost if the buffer is cleared,
+    /// so reading cannot be continued
+    #[test]
+    fn cleared_buffer() {
+        let mut buf = Vec::new();
+        let mut reader = reader("<root><tag attr");
 
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::End(BytesEnd::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::End(BytesEnd::new("tag"))
-            );
-            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        append(&mut reader, "=\"value\"/>");
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput),
+            x => panic!("Expected `Err(Io(_))`, but got `{:?}`", x),
         }
+        assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+    }
 
-        #[test]
-        fn mismatched_tags() {
-            let mut reader = Reader::from_str("<tag><tag></mismatched></tag>");
-            reader.config_mut().check_end_names = false;
+    /// The same as `cleared_buffer`, but for comments, which are read by
+    /// another routine
+    #[test]
+    fn cleared_buffer_comment() {
+        let mut buf = Vec::new();
+        let mut reader = reader("<!-- comment");
 
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::End(BytesEnd::new("mismatched"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::End(BytesEnd::new("tag"))
-            );
-            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedComment),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        append(&mut reader, " -->");
+        let mut new_buf = Vec::new();
+        match reader.read_event_into(&mut new_buf) {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput),
+            x => panic!("Expected `Err(Io(_))`, but got `{:?}`", x),
         }
     }
 
-    mod true_ {
-        use super::*;
-        use pretty_assertions::assert_eq;
+    /// The buffer owned by the reader keeps the incomplete markup between calls
+    #[test]
+    fn read_event_owned() {
+        let mut reader = reader("<root");
 
-        #[test]
-        fn matched_tags() {
-            let mut reader = Reader::from_str("<tag><tag></tag></tag>");
-            reader.config_mut().check_end_names = false;
+        match reader.read_event_owned() {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        // Not enough data yet
+        match reader.read_event_owned() {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        append(&mut reader, " attr='value'>text");
+        assert_eq!(
+            reader.read_event_owned().unwrap(),
+            Event::Start(BytesStart::from_content("root attr='value'", 4))
+        );
+        assert_eq!(
+            reader.read_event_owned().unwrap(),
+            Event::Text(BytesText::new("text"))
+        );
+        assert_eq!(reader.read_event_owned().unwrap(), Event::Eof);
+    }
 
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::End(BytesEnd::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::End(BytesEnd::new("tag"))
-            );
-            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    /// Unknown symbol after `<!` cannot be fixed by more data
+    #[test]
+    fn invalid_bang() {
+        let mut buf = Vec::new();
+        let mut reader = reader("<!x");
+
+        match reader.read_event_into(&mut buf) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::InvalidBangMarkup),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
         }
+        append(&mut reader, ">");
+        assert_eq!(reader.read_event_into(&mut buf).unwrap(), Event::Eof);
+    }
 
-        #[test]
-        fn mismatched_tags() {
-            let mut reader = Reader::from_str("<tag><tag></mismatched></tag>");
-            reader.config_mut().check_end_names = true;
+    /// Option has no effect for borrowing reader, because its input cannot grow
+    #[test]
+    fn borrowed() {
+        let mut reader = Reader::from_str("<tag");
+        reader.config_mut().resume_incomplete_markup = true;
 
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::Start(BytesStart::new("tag"))
-            );
-            match reader.read_event() {
-                Err(Error::IllFormed(cause)) => assert_eq!(
-                    cause,
-                    IllFormedError::MismatchedEndTag {
-                        expected: "tag".into(),
-                        found: "mismatched".into(),
-                    }
-                ),
-                x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
-            }
-            // #513: We want to continue parsing after the error
-            assert_eq!(
-                reader.read_event().unwrap(),
-                Event::End(BytesEnd::new("tag"))
-            );
-            assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        match reader.read_event() {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
         }
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
     }
 }
 
-mod expand_empty_elements {
+mod syntax_error_context {
     use super::*;
     use pretty_assertions::assert_eq;
+    use quick_xml::errors::SyntaxError;
 
-    /// Self-closed elements should be reported as one `Empty` event
     #[test]
     fn false_() {
-        let mut reader = Reader::from_str("<root/>");
-        reader.config_mut().expand_empty_elements = false;
+        let mut reader = Reader::from_str("<root><tag attr='value'");
+        reader.config_mut().syntax_error_context = false;
 
         assert_eq!(
             reader.read_event().unwrap(),
-            Event::Empty(BytesStart::new("root"))
+            Event::Start(BytesStart::new("root"))
         );
+        match reader.read_event() {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
         assert_eq!(reader.read_event().unwrap(), Event::Eof);
     }
 
-    /// Self-closed elements should be reported as two events
     #[test]
     fn true_() {
-        let mut reader = Reader::from_str("<root/>");
-        reader.config_mut().expand_empty_elements = true;
+        let mut reader = Reader::from_str("<root><tag attr='value'");
+        reader.config_mut().syntax_error_context = true;
 
         assert_eq!(
             reader.read_event().unwrap(),
             Event::Start(BytesStart::new("root"))
         );
+        match reader.read_event() {
+            Err(Error::SyntaxWithContext(cause, context)) => {
+                assert_eq!(cause, SyntaxError::UnclosedTag);
+                assert_eq!(context, "<tag attr='value'");
+            }
+            x => panic!("Expected `Err(SyntaxWithContext(_, _))`, but got `{:?}`", x),
+        }
+        assert_eq!(reader.error_position(), 6);
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    /// Context is limited to 32 bytes
+    #[test]
+    fn long() {
+        let mut reader = Reader::from_str("<!-- a very long comment that is not closed");
+        reader.config_mut().syntax_error_context = true;
+
+        let error = reader.read_event().unwrap_err();
+        match error {
+            Error::SyntaxWithContext(cause, ref context) => {
+                assert_eq!(cause, SyntaxError::UnclosedComment);
+                assert_eq!(context, "<!-- a very long comment that is");
+            }
+            ref x => panic!("Expected `Err(SyntaxWithContext(_, _))`, but got `{:?}`", x),
+        }
         assert_eq!(
-            reader.read_event().unwrap(),
-            Event::End(BytesEnd::new("root"))
+            error.to_string(),
+            "syntax error: comment not closed: `-->` not found before end of input \
+             (near `<!-- a very long comment that is`)"
         );
-        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    /// Context is not truncated in the middle of multi-byte character
+    #[test]
+    fn multi_byte() {
+        // `é` occupies 32nd and 33rd bytes, including `<`
+        let mut reader = Reader::from_str("<tag attribute='valueeeeeeeeeeeé'");
+        reader.config_mut().syntax_error_context = true;
+
+        match reader.read_event() {
+            Err(Error::SyntaxWithContext(cause, context)) => {
+                assert_eq!(cause, SyntaxError::UnclosedTag);
+                assert_eq!(context, "<tag attribute='valueeeeeeeeeee");
+            }
+            x => panic!("Expected `Err(SyntaxWithContext(_, _))`, but got `{:?}`", x),
+        }
+    }
+
+    /// Buffered readers do not keep the input, so the option has no effect
+    #[test]
+    fn buffered() {
+        let mut reader = Reader::from_reader("<tag attr='value'".as_bytes());
+        reader.config_mut().syntax_error_context = true;
+
+        match reader.read_event_into(&mut Vec::new()) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
     }
 }
 
@@ -472,6 +1554,8 @@ mod trim_markup_names_in_closing_tags {
                     IllFormedError::MismatchedEndTag {
                         expected: "root".into(),
                         found: "root \t\r\n".into(),
+                        expected_position: 0,
+                        found_position: 6,
                     }
                 ),
                 x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
@@ -871,3 +1955,143 @@ mod trim_text_end {
         assert_eq!(reader.read_event().unwrap(), Event::Eof);
     }
 }
+
+mod warn_mid_document_bom {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::errors::Warning;
+
+    /// The first BOM is consumed (and not counted in positions), other are
+    /// kept in the text
+    const XML: &str = "\u{FEFF}<root>a\u{FEFF}b</root>\u{FEFF}";
+    //                        0     6 7     10     18
+
+    #[test]
+    fn false_() {
+        let mut reader = Reader::from_str(XML);
+        reader.config_mut().warn_mid_document_bom = false;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::new("a\u{FEFF}b"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("root"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::new("\u{FEFF}"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        assert_eq!(reader.warnings(), []);
+    }
+
+    #[test]
+    fn true_() {
+        let mut reader = Reader::from_str(XML);
+        reader.config_mut().warn_mid_document_bom = true;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        assert_eq!(reader.warnings(), []);
+        // Text is not changed
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::new("a\u{FEFF}b"))
+        );
+        assert_eq!(reader.warnings(), [Warning::MidDocumentBom(7)]);
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("root"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Text(BytesText::new("\u{FEFF}"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+        assert_eq!(
+            reader.take_warnings(),
+            [Warning::MidDocumentBom(7), Warning::MidDocumentBom(18)]
+        );
+        assert_eq!(reader.warnings(), []);
+    }
+
+    /// BOM at the start of the document is not reported even if it is not consumed.
+    /// In that case it is counted in positions
+    #[test]
+    fn not_consumed() {
+        let mut reader = Reader::from_reader(XML.as_bytes());
+        reader.config_mut().consume_bom = false;
+        reader.config_mut().warn_mid_document_bom = true;
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Text(BytesText::new("\u{FEFF}"))
+        );
+        assert_eq!(reader.warnings(), []);
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Start(BytesStart::new("root"))
+        );
+        assert_eq!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Event::Text(BytesText::new("a\u{FEFF}b"))
+        );
+        assert_eq!(reader.warnings(), [Warning::MidDocumentBom(10)]);
+    }
+}
+
+/// Configuration passed to a constructor should be applied from the first event
+mod with_config {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::reader::{Config, NsReader};
+
+    #[test]
+    fn reader() {
+        let mut config = Config::default();
+        config.trim_text(true);
+        config.expand_empty_elements = true;
+
+        let mut reader = Reader::with_config("  <tag/>  ".as_bytes(), config.clone());
+        assert_eq!(reader.config(), &config);
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("tag"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("tag"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+
+    #[test]
+    fn ns_reader() {
+        let mut config = Config::default();
+        config.trim_text(true);
+        config.expand_empty_elements = true;
+
+        let mut reader = NsReader::with_config("  <tag/>  ".as_bytes(), config.clone());
+        assert_eq!(reader.config(), &config);
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::Start(BytesStart::new("tag"))
+        );
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Event::End(BytesEnd::new("tag"))
+        );
+        assert_eq!(reader.read_event().unwrap(), Event::Eof);
+    }
+}
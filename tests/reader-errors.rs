@@ -877,16 +877,22 @@ mod ill_formed {
         //                            ^= 7
         expected: "start".to_string(),
         found: "".to_string(),
+        expected_position: 0,
+        found_position: 7,
     });
     err2!(mismatched_end_tag3("<start></end>") => 7: IllFormedError::MismatchedEndTag {
         //                            ^= 7
         expected: "start".to_string(),
         found: "end".to_string(),
+        expected_position: 0,
+        found_position: 7,
     });
     err2!(mismatched_end_tag4("<start></end >") => 7: IllFormedError::MismatchedEndTag {
         //                            ^= 7
         expected: "start".to_string(),
         found: "end".to_string(),
+        expected_position: 0,
+        found_position: 7,
     });
 
     ok!(double_hyphen_in_comment1("<!---->") => 7: Event::Comment(BytesText::new("")));
@@ -897,3 +903,111 @@ mod ill_formed {
     err!(double_hyphen_in_comment4("<!-- -- -->") => 5: IllFormedError::DoubleHyphenInComment);
     //                                   ^= 5
 }
+
+/// Errors of the underlying reader are returned as `Error::Io`
+mod io {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::error::Error as _;
+    use std::io::{self, BufReader, ErrorKind, Read};
+
+    /// A reader that fails on the first read
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "source is exhausted",
+            ))
+        }
+    }
+
+    #[test]
+    fn buffered() {
+        let mut buf = Vec::new();
+        let mut reader = Reader::from_reader(BufReader::new(Failing));
+        let err = reader.read_event_into(&mut buf).unwrap_err();
+
+        assert!(matches!(err, Error::Io(_)), "{:?}", err);
+        assert_eq!(
+            err.as_io_error().map(|e| e.kind()),
+            Some(ErrorKind::UnexpectedEof)
+        );
+
+        let source = err.source().expect("source of I/O error");
+        let io = source.downcast_ref::<io::Error>().expect("std::io::Error");
+        assert_eq!(io.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(io.to_string(), "source is exhausted");
+    }
+
+    #[test]
+    fn not_io() {
+        let mut reader = Reader::from_str("<");
+        let err = reader.read_event().unwrap_err();
+
+        assert!(err.as_io_error().is_none(), "{:?}", err);
+    }
+
+    /// Conversion to `std::io::Error` returns the original I/O error
+    #[test]
+    fn into_io_error() {
+        let mut buf = Vec::new();
+        let mut reader = Reader::from_reader(BufReader::new(Failing));
+        let err = reader.read_event_into(&mut buf).unwrap_err();
+
+        let io = io::Error::from(err);
+        assert_eq!(io.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(io.to_string(), "source is exhausted");
+    }
+
+    /// Conversion of a shared I/O error keeps its kind and message
+    #[test]
+    fn into_io_error_shared() {
+        let err = Error::from(io::Error::new(ErrorKind::UnexpectedEof, "eof"));
+        let _clone = err.clone();
+
+        let io = io::Error::from(err);
+        assert_eq!(io.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(io.to_string(), "eof");
+    }
+
+    /// Errors in the document are converted to `InvalidData` errors
+    #[test]
+    fn syntax_into_io_error() {
+        let mut reader = Reader::from_str("<");
+        let err = reader.read_event().unwrap_err();
+        let message = err.to_string();
+
+        let io = io::Error::from(err);
+        assert_eq!(io.kind(), ErrorKind::InvalidData);
+        assert_eq!(io.to_string(), message);
+
+        // Round trip
+        let inner = io.into_inner().expect("wrapped error");
+        let err = inner.downcast::<Error>().expect("quick_xml::Error");
+        assert!(
+            matches!(*err, Error::Syntax(SyntaxError::UnclosedTag)),
+            "{:?}",
+            err
+        );
+    }
+
+    /// Errors in the document can be propagated with `?` as I/O errors
+    #[test]
+    fn question_mark() {
+        fn read(xml: &str) -> io::Result<usize> {
+            let mut reader = Reader::from_str(xml);
+            reader.config_mut().check_end_names = true;
+            let mut count = 0;
+            loop {
+                match reader.read_event()? {
+                    Event::Eof => return Ok(count),
+                    _ => count += 1,
+                }
+            }
+        }
+
+        assert_eq!(read("<a></a>").unwrap(), 2);
+        assert_eq!(read("<a></b>").unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+}
@@ -499,3 +499,382 @@ fn reserved_name() {
         ),
     }
 }
+
+/// Tests for the owned events returned by `read_decoded_event()`
+mod decoded_event {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::errors::Error;
+    use quick_xml::name::NamespaceError;
+    use quick_xml::reader::DecodedEvent;
+
+    fn name(ns: Option<&str>, local: &str) -> (Option<String>, String) {
+        (ns.map(String::from), local.to_string())
+    }
+
+    #[test]
+    fn events() {
+        let mut r = NsReader::from_str(
+            r#"<?xml version="1.0"?><a xmlns="www1" b:attr='&amp;' xmlns:b="www2"><b:c/>text &lt;<![CDATA[<cdata>]]><!--comment--><?pi?></a>"#,
+        );
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            r.read_decoded_event().unwrap(),
+            DecodedEvent::Decl(r#"xml version="1.0""#.to_string())
+        );
+        assert_eq!(
+            r.read_decoded_event().unwrap(),
+            DecodedEvent::Start {
+                name: name(Some("www1"), "a"),
+                attributes: vec![
+                    (name(None, "xmlns"), "www1".to_string()),
+                    (name(Some("www2"), "attr"), "&".to_string()),
+                    (
+                        name(Some("http://www.w3.org/2000/xmlns/"), "b"),
+                        "www2".to_string()
+                    ),
+                ],
+            }
+        );
+        assert_eq!(
+            r.read_decoded_event_into(&mut buf).unwrap(),
+            DecodedEvent::Empty {
+                name: name(Some("www2"), "c"),
+                attributes: vec![],
+            }
+        );
+        assert_eq!(
+            r.read_decoded_event().unwrap(),
+            DecodedEvent::Text("text <".to_string())
+        );
+        assert_eq!(
+            r.read_decoded_event().unwrap(),
+            DecodedEvent::CData("<cdata>".to_string())
+        );
+        assert_eq!(
+            r.read_decoded_event().unwrap(),
+            DecodedEvent::Comment("comment".to_string())
+        );
+        assert_eq!(
+            r.read_decoded_event().unwrap(),
+            DecodedEvent::PI("pi".to_string())
+        );
+        assert_eq!(
+            r.read_decoded_event().unwrap(),
+            DecodedEvent::End {
+                name: name(Some("www1"), "a"),
+            }
+        );
+        assert_eq!(r.read_decoded_event().unwrap(), DecodedEvent::Eof);
+    }
+
+    #[test]
+    fn unknown_prefix() {
+        let mut r = NsReader::from_str("<unknown:tag/>");
+
+        match r.read_decoded_event() {
+            Err(Error::Namespace(NamespaceError::UnknownPrefix(prefix))) => {
+                assert_eq!(prefix, b"unknown")
+            }
+            x => panic!(
+                "Expected `Err(Namespace(UnknownPrefix(_)))`, but got `{:?}`",
+                x
+            ),
+        }
+    }
+}
+
+/// Tests for resolution of the `xml:base` attributes
+mod xml_base {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Returns base URI of the element with the `xml:base="{reference}"`
+    /// nested in the element with the `xml:base="{base}"`
+    fn resolve(base: &str, reference: &str) -> String {
+        let xml = format!(
+            r#"<parent xml:base="{}"><child xml:base="{}"/></parent>"#,
+            base, reference
+        );
+        let mut reader = NsReader::from_str(&xml);
+        reader.config_mut().track_xml_base = true;
+
+        assert!(matches!(reader.read_event().unwrap(), Start(_)));
+        assert_eq!(reader.base_uri(), Some(base));
+        assert!(matches!(reader.read_event().unwrap(), Empty(_)));
+        let result = reader.base_uri().unwrap().to_string();
+        assert!(matches!(reader.read_event().unwrap(), End(_)));
+        assert_eq!(reader.base_uri(), Some(base));
+        result
+    }
+
+    #[test]
+    fn disabled() {
+        let mut reader = NsReader::from_str(r#"<root xml:base="http://example.com/"/>"#);
+
+        assert!(matches!(reader.read_event().unwrap(), Empty(_)));
+        assert_eq!(reader.base_uri(), None);
+    }
+
+    #[test]
+    fn inherited() {
+        let mut reader = NsReader::from_str(
+            r#"<root xml:base="http://example.com/"><child/><other xml:base="sub/"/></root>"#,
+        );
+        reader.config_mut().track_xml_base = true;
+
+        assert!(matches!(reader.read_event().unwrap(), Start(_)));
+        assert_eq!(reader.base_uri(), Some("http://example.com/"));
+        assert!(matches!(reader.read_event().unwrap(), Empty(_)));
+        assert_eq!(reader.base_uri(), Some("http://example.com/"));
+        assert!(matches!(reader.read_event().unwrap(), Empty(_)));
+        assert_eq!(reader.base_uri(), Some("http://example.com/sub/"));
+        assert!(matches!(reader.read_event().unwrap(), End(_)));
+        assert_eq!(reader.base_uri(), Some("http://example.com/"));
+        assert_eq!(reader.read_event().unwrap(), Eof);
+        assert_eq!(reader.base_uri(), None);
+    }
+
+    /// Error in the `xml:base` attribute does not break tracking of base URIs
+    /// of the following elements
+    #[test]
+    fn invalid() {
+        let mut reader = NsReader::from_str(
+            r#"<root xml:base="http://example.com/"><bad xml:base="&unknown;"></bad><child/></root>"#,
+        );
+        reader.config_mut().track_xml_base = true;
+
+        assert!(matches!(reader.read_event().unwrap(), Start(_)));
+        assert_eq!(reader.base_uri(), Some("http://example.com/"));
+        assert!(reader.read_event().is_err());
+        assert!(matches!(reader.read_event().unwrap(), End(_)));
+        assert!(matches!(reader.read_event().unwrap(), Empty(_)));
+        assert_eq!(reader.base_uri(), Some("http://example.com/"));
+        assert!(matches!(reader.read_event().unwrap(), End(_)));
+        assert_eq!(reader.base_uri(), Some("http://example.com/"));
+        assert_eq!(reader.read_event().unwrap(), Eof);
+        assert_eq!(reader.base_uri(), None);
+    }
+
+    /// Examples from https://www.rfc-editor.org/rfc/rfc3986#section-5.4
+    #[test]
+    fn rfc3986() {
+        const BASE: &str = "http://a/b/c/d;p?q";
+
+        // Normal examples
+        assert_eq!(resolve(BASE, "g:h"), "g:h");
+        assert_eq!(resolve(BASE, "g"), "http://a/b/c/g");
+        assert_eq!(resolve(BASE, "./g"), "http://a/b/c/g");
+        assert_eq!(resolve(BASE, "g/"), "http://a/b/c/g/");
+        assert_eq!(resolve(BASE, "/g"), "http://a/g");
+        assert_eq!(resolve(BASE, "//g"), "http://g");
+        assert_eq!(resolve(BASE, "?y"), "http://a/b/c/d;p?y");
+        assert_eq!(resolve(BASE, "g?y"), "http://a/b/c/g?y");
+        assert_eq!(resolve(BASE, "#s"), "http://a/b/c/d;p?q#s");
+        assert_eq!(resolve(BASE, "g#s"), "http://a/b/c/g#s");
+        assert_eq!(resolve(BASE, "g?y#s"), "http://a/b/c/g?y#s");
+        assert_eq!(resolve(BASE, ";x"), "http://a/b/c/;x");
+        assert_eq!(resolve(BASE, "g;x"), "http://a/b/c/g;x");
+        assert_eq!(resolve(BASE, "g;x?y#s"), "http://a/b/c/g;x?y#s");
+        assert_eq!(resolve(BASE, ""), "http://a/b/c/d;p?q");
+        assert_eq!(resolve(BASE, "."), "http://a/b/c/");
+        assert_eq!(resolve(BASE, "./"), "http://a/b/c/");
+        assert_eq!(resolve(BASE, ".."), "http://a/b/");
+        assert_eq!(resolve(BASE, "../"), "http://a/b/");
+        assert_eq!(resolve(BASE, "../g"), "http://a/b/g");
+        assert_eq!(resolve(BASE, "../.."), "http://a/");
+        assert_eq!(resolve(BASE, "../../"), "http://a/");
+        assert_eq!(resolve(BASE, "../../g"), "http://a/g");
+
+        // Abnormal examples
+        assert_eq!(resolve(BASE, "../../../g"), "http://a/g");
+        assert_eq!(resolve(BASE, "../../../../g"), "http://a/g");
+        assert_eq!(resolve(BASE, "/./g"), "http://a/g");
+        assert_eq!(resolve(BASE, "/../g"), "http://a/g");
+        assert_eq!(resolve(BASE, "g."), "http://a/b/c/g.");
+        assert_eq!(resolve(BASE, ".g"), "http://a/b/c/.g");
+        assert_eq!(resolve(BASE, "g.."), "http://a/b/c/g..");
+        assert_eq!(resolve(BASE, "..g"), "http://a/b/c/..g");
+        assert_eq!(resolve(BASE, "./../g"), "http://a/b/g");
+        assert_eq!(resolve(BASE, "./g/."), "http://a/b/c/g/");
+        assert_eq!(resolve(BASE, "g/./h"), "http://a/b/c/g/h");
+        assert_eq!(resolve(BASE, "g/../h"), "http://a/b/c/h");
+        assert_eq!(resolve(BASE, "g;x=1/./y"), "http://a/b/c/g;x=1/y");
+        assert_eq!(resolve(BASE, "g;x=1/../y"), "http://a/b/c/y");
+        assert_eq!(resolve(BASE, "g?y/./x"), "http://a/b/c/g?y/./x");
+        assert_eq!(resolve(BASE, "g?y/../x"), "http://a/b/c/g?y/../x");
+        assert_eq!(resolve(BASE, "g#s/./x"), "http://a/b/c/g#s/./x");
+        assert_eq!(resolve(BASE, "g#s/../x"), "http://a/b/c/g#s/../x");
+        assert_eq!(resolve(BASE, "http:g"), "http:g");
+    }
+}
+
+mod attach_namespaces {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn disabled() {
+        let mut reader = NsReader::from_str(r#"<root xmlns="urn:default"/>"#);
+
+        match reader.read_event().unwrap() {
+            Empty(e) => assert_eq!(e.namespace(), None),
+            e => panic!("Expected Empty, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn enabled() {
+        let mut reader = NsReader::from_str(
+            r#"<root xmlns="urn:default"><p:a xmlns:p="urn:p"><b/><unknown:c/></p:a></root>"#,
+        );
+        reader.config_mut().attach_namespaces = true;
+
+        match reader.read_event().unwrap() {
+            Start(e) => assert_eq!(e.namespace(), Some(Namespace(b"urn:default"))),
+            e => panic!("Expected Start, got {:?}", e),
+        }
+        match reader.read_event().unwrap() {
+            Start(e) => assert_eq!(e.namespace(), Some(Namespace(b"urn:p"))),
+            e => panic!("Expected Start, got {:?}", e),
+        }
+        match reader.read_event().unwrap() {
+            Empty(e) => assert_eq!(e.namespace(), Some(Namespace(b"urn:default"))),
+            e => panic!("Expected Empty, got {:?}", e),
+        }
+        // Unknown prefixes are not bound to any namespace
+        match reader.read_event().unwrap() {
+            Empty(e) => assert_eq!(e.namespace(), None),
+            e => panic!("Expected Empty, got {:?}", e),
+        }
+        match reader.read_event().unwrap() {
+            End(e) => assert_eq!(e.namespace(), Some(Namespace(b"urn:p"))),
+            e => panic!("Expected End, got {:?}", e),
+        }
+        match reader.read_event().unwrap() {
+            End(e) => assert_eq!(e.namespace(), Some(Namespace(b"urn:default"))),
+            e => panic!("Expected End, got {:?}", e),
+        }
+        assert_eq!(reader.read_event().unwrap(), Eof);
+    }
+
+    /// Namespace lives as long as the event, even after the reader moves forward
+    #[test]
+    fn owned() {
+        let mut reader = NsReader::from_reader(r#"<p:a xmlns:p="urn:p"><p:b/></p:a>"#.as_bytes());
+        reader.config_mut().attach_namespaces = true;
+        let mut buf = Vec::new();
+
+        let start = match reader.read_event_into(&mut buf).unwrap() {
+            Start(e) => e.into_owned(),
+            e => panic!("Expected Start, got {:?}", e),
+        };
+        buf.clear();
+        assert!(matches!(
+            reader.read_event_into(&mut buf).unwrap(),
+            Empty(_)
+        ));
+
+        assert_eq!(start.namespace(), Some(Namespace(b"urn:p")));
+        assert_eq!(start.to_end().namespace(), Some(Namespace(b"urn:p")));
+
+        let mut renamed = start.clone();
+        renamed.set_name(b"other");
+        assert_eq!(renamed.namespace(), None);
+    }
+
+    /// Attached namespace does not participate in the comparison of events
+    #[test]
+    fn equality() {
+        use quick_xml::events::{BytesEnd, BytesStart};
+
+        let mut reader = NsReader::from_str(r#"<p:a xmlns:p="urn:p"></p:a>"#);
+        reader.config_mut().attach_namespaces = true;
+
+        assert_eq!(
+            reader.read_event().unwrap(),
+            Start(BytesStart::from_content(r#"p:a xmlns:p="urn:p""#, 3))
+        );
+        assert_eq!(reader.read_event().unwrap(), End(BytesEnd::new("p:a")));
+        assert_eq!(reader.read_event().unwrap(), Eof);
+    }
+}
+
+/// Raw bytes of markup are available also when reading through the `NsReader`
+#[test]
+fn last_event_raw() {
+    let mut reader = NsReader::from_str("<a x='1'><b/>text</a >");
+
+    assert!(matches!(reader.read_event().unwrap(), Start(_)));
+    assert_eq!(reader.last_event_raw(), b"<a x='1'>");
+
+    assert!(matches!(
+        reader.read_resolved_event().unwrap(),
+        (_, Empty(_))
+    ));
+    assert_eq!(reader.last_event_raw(), b"<b/>");
+
+    assert!(matches!(reader.read_event().unwrap(), Text(_)));
+    assert_eq!(reader.last_event_raw(), b"");
+
+    assert!(matches!(reader.read_event().unwrap(), End(_)));
+    assert_eq!(reader.last_event_raw(), b"</a >");
+}
+
+/// Checks conversions between `Reader` and `NsReader` in the middle of a document
+#[test]
+fn conversion() {
+    use quick_xml::events::{BytesEnd, BytesStart};
+    use quick_xml::reader::Reader;
+    use std::io::BufReader;
+
+    let xml = r#"<root><ns:a xmlns:ns="urn:ns"><ns:b/></ns:a><c/></root>"#;
+    // Small buffer to ensure that data buffered in `BufReader` is not lost
+    let mut r = Reader::from_reader(BufReader::with_capacity(4, xml.as_bytes()));
+    let mut buf = Vec::new();
+
+    assert_eq!(
+        r.read_event_into(&mut buf).unwrap(),
+        Start(BytesStart::new("root"))
+    );
+
+    let mut r = r.into_ns_reader();
+    assert_eq!(r.buffer_position(), 6);
+    match r.read_resolved_event_into(&mut buf) {
+        Ok((ns, Start(e))) => {
+            assert_eq!(ns, Bound(Namespace(b"urn:ns")));
+            assert_eq!(e.name(), QName(b"ns:a"));
+        }
+        e => panic!("Expected Start, got {:?}", e),
+    }
+    match r.read_resolved_event_into(&mut buf) {
+        Ok((ns, Empty(e))) => {
+            assert_eq!(ns, Bound(Namespace(b"urn:ns")));
+            assert_eq!(e.name(), QName(b"ns:b"));
+        }
+        e => panic!("Expected Empty, got {:?}", e),
+    }
+    match r.read_resolved_event_into(&mut buf) {
+        Ok((ns, End(e))) => {
+            assert_eq!(ns, Bound(Namespace(b"urn:ns")));
+            assert_eq!(e.name(), QName(b"ns:a"));
+        }
+        e => panic!("Expected End, got {:?}", e),
+    }
+    match r.read_resolved_event_into(&mut buf) {
+        Ok((ns, Empty(e))) => {
+            assert_eq!(ns, Unbound);
+            assert_eq!(e.name(), QName(b"c"));
+        }
+        e => panic!("Expected Empty, got {:?}", e),
+    }
+
+    let mut r = r.into_reader();
+    assert_eq!(r.buffer_position(), 48);
+    assert_eq!(
+        r.read_event_into(&mut buf).unwrap(),
+        End(BytesEnd::new("root"))
+    );
+    assert_eq!(r.read_event_into(&mut buf).unwrap(), Eof);
+}
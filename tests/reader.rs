@@ -335,6 +335,108 @@ mod read_to_end {
     }
 }
 
+/// Checks that skip_element() finds the matching end tag without producing events
+mod skip_element {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::errors::{Error, IllFormedError, SyntaxError};
+
+    #[test]
+    fn span() {
+        let mut r = Reader::from_str("<tag> <nested/> </tag>");
+        //                            ^0   ^5         ^16
+        r.config_mut().trim_text(true);
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tag")));
+        assert_eq!(r.skip_element(QName(b"tag")).unwrap(), 5..16);
+        assert_eq!(r.buffer_position(), 22);
+        assert_eq!(r.read_event().unwrap(), Eof);
+    }
+
+    #[test]
+    fn nested() {
+        let mut r = Reader::from_str(
+            "<tag><tag><tag/><tag attr='</tag>'>text</tag ></tag><other></other></tag><tail/>",
+        );
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tag")));
+        r.skip_element(QName(b"tag")).unwrap();
+        assert_eq!(r.read_event().unwrap(), Empty(BytesStart::new("tail")));
+        assert_eq!(r.read_event().unwrap(), Eof);
+    }
+
+    #[test]
+    fn markup_with_brackets() {
+        let mut r = Reader::from_str(
+            "<tag><!-- </tag> --><![CDATA[</tag>]]><?pi </tag>?><!DOCTYPE x></tag><tail/>",
+        );
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tag")));
+        r.skip_element(QName(b"tag")).unwrap();
+        assert_eq!(r.read_event().unwrap(), Empty(BytesStart::new("tail")));
+        assert_eq!(r.read_event().unwrap(), Eof);
+    }
+
+    #[test]
+    fn expanded_empty() {
+        let mut r = Reader::from_str("<tag/><tail/>");
+        r.config_mut().expand_empty_elements = true;
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tag")));
+        assert_eq!(r.skip_element(QName(b"tag")).unwrap(), 6..6);
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tail")));
+        assert_eq!(r.read_event().unwrap(), End(BytesEnd::new("tail")));
+        assert_eq!(r.read_event().unwrap(), Eof);
+    }
+
+    #[test]
+    fn missed_end() {
+        let mut r = Reader::from_str("<tag><tag></tag>");
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tag")));
+        match r.skip_element(QName(b"tag")) {
+            Err(Error::IllFormed(IllFormedError::MissingEndTag(tag))) => assert_eq!(tag, "tag"),
+            x => panic!(
+                "Expected `Err(IllFormed(MissingEndTag(_)))`, but got `{:?}`",
+                x
+            ),
+        }
+        assert_eq!(r.buffer_position(), 16);
+    }
+
+    #[test]
+    fn unclosed_comment() {
+        let mut r = Reader::from_str("<tag>text<!-- </tag>");
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tag")));
+        match r.skip_element(QName(b"tag")) {
+            Err(Error::Syntax(SyntaxError::UnclosedComment)) => {}
+            x => panic!("Expected `Err(Syntax(UnclosedComment))`, but got `{:?}`", x),
+        }
+        assert_eq!(r.error_position(), 9);
+    }
+
+    #[test]
+    fn mismatched_end() {
+        let mut r = Reader::from_str("<outer><tag></inner></tag></outer>");
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("outer")));
+        // Skip with the wrong name; the final end tag is still checked
+        match r.skip_element(QName(b"inner")) {
+            Err(Error::IllFormed(IllFormedError::MismatchedEndTag {
+                expected, found, ..
+            })) => {
+                assert_eq!(expected, "outer");
+                assert_eq!(found, "inner");
+            }
+            x => panic!(
+                "Expected `Err(IllFormed(MismatchedEndTag {{ .. }}))`, but got `{:?}`",
+                x
+            ),
+        }
+    }
+}
+
 /// This tests checks that read_text() correctly returns text even when
 /// text is trimmed from both sides
 mod read_text {
@@ -361,3 +463,490 @@ mod read_text {
         assert_eq!(r.read_event().unwrap(), Eof);
     }
 }
+
+mod index_ids {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::reader::index_ids;
+
+    #[test]
+    fn nested() {
+        let xml = r#"
+            <root xml:id="root">
+                <item xml:id="first"><item xml:id="inner"/></item>
+                <item>no id</item>
+                <item xml:id="a&amp;b"/>
+            </root>
+        "#;
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+        reader.config_mut().expand_empty_elements = true;
+
+        let ids = index_ids(&mut reader).unwrap();
+        let span = |id: &str| &xml[ids[id].start as usize..ids[id].end as usize];
+
+        assert_eq!(ids.len(), 4);
+        assert!(span("root").starts_with(r#"<root xml:id="root">"#));
+        assert!(span("root").ends_with("</root>"));
+        assert_eq!(
+            span("first"),
+            r#"<item xml:id="first"><item xml:id="inner"/></item>"#
+        );
+        assert_eq!(span("inner"), r#"<item xml:id="inner"/>"#);
+        assert_eq!(span("a&b"), r#"<item xml:id="a&amp;b"/>"#);
+        // Configuration is restored
+        assert_eq!(reader.config().expand_empty_elements, true);
+    }
+
+    /// Spans are not affected by the normalization of line endings inside tags
+    #[test]
+    fn normalize_line_endings() {
+        let xml =
+            "<doc>\r\n <p\r\n xml:id=\"intro\">a\r\nb</p\r\n>\r\n <img\r\n xml:id=\"logo\"/></doc>";
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().normalize_line_endings = true;
+        reader.config_mut().trim_text(true);
+
+        let ids = index_ids(&mut reader).unwrap();
+        let span = |id: &str| &xml[ids[id].start as usize..ids[id].end as usize];
+
+        assert_eq!(span("intro"), "<p\r\n xml:id=\"intro\">a\r\nb</p\r\n>");
+        assert_eq!(span("logo"), "<img\r\n xml:id=\"logo\"/>");
+        // Configuration is restored
+        assert_eq!(reader.config().trim_text_start, true);
+    }
+
+    #[test]
+    fn duplicated() {
+        let xml = r#"<root><a xml:id="id"/><b xml:id="id"/></root>"#;
+        let mut reader = Reader::from_str(xml);
+
+        let ids = index_ids(&mut reader).unwrap();
+
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids["id"], 6..22);
+    }
+}
+
+mod peek_event {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn borrowed() {
+        let mut r = Reader::from_str("<tag>text</tag>");
+
+        assert_eq!(r.peek_event().unwrap(), Start(BytesStart::new("tag")));
+        assert_eq!(r.peek_event().unwrap(), Start(BytesStart::new("tag")));
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("tag")));
+
+        assert_eq!(r.peek_event().unwrap(), Text(BytesText::new("text")));
+        assert_eq!(r.read_event().unwrap(), Text(BytesText::new("text")));
+        assert_eq!(r.read_event().unwrap(), End(BytesEnd::new("tag")));
+        assert_eq!(r.peek_event().unwrap(), Eof);
+        assert_eq!(r.read_event().unwrap(), Eof);
+    }
+
+    #[test]
+    fn buffered() {
+        let mut r = Reader::from_reader("<tag>text</tag>".as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            r.peek_event_into(&mut buf).unwrap(),
+            &Start(BytesStart::new("tag"))
+        );
+        assert_eq!(
+            r.peek_event_into(&mut buf).unwrap(),
+            &Start(BytesStart::new("tag"))
+        );
+        assert_eq!(r.buffer_position(), 5);
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            Start(BytesStart::new("tag"))
+        );
+
+        assert_eq!(
+            r.peek_event_into(&mut buf).unwrap(),
+            &Text(BytesText::new("text"))
+        );
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            Text(BytesText::new("text"))
+        );
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            End(BytesEnd::new("tag"))
+        );
+        assert_eq!(r.peek_event_into(&mut buf).unwrap(), &Eof);
+        assert_eq!(r.read_event_into(&mut buf).unwrap(), Eof);
+    }
+
+    /// Peeked event is consumed by `read_to_end_into`
+    #[test]
+    fn read_to_end() {
+        let mut r = Reader::from_reader("<tag><inner/></tag>".as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            Start(BytesStart::new("tag"))
+        );
+        assert_eq!(
+            r.peek_event_into(&mut buf).unwrap(),
+            &Empty(BytesStart::new("inner"))
+        );
+        assert_eq!(r.read_to_end_into(QName(b"tag"), &mut buf).unwrap(), 5..13);
+        assert_eq!(r.read_event_into(&mut buf).unwrap(), Eof);
+    }
+
+    /// Span returned by `read_to_end_into` includes the peeked `Start` event
+    #[test]
+    fn read_to_end_after_start() {
+        let mut r = Reader::from_reader("<a><a></a></a>".as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            Start(BytesStart::new("a"))
+        );
+        assert_eq!(
+            r.peek_event_into(&mut buf).unwrap(),
+            &Start(BytesStart::new("a"))
+        );
+        assert_eq!(r.buffer_position(), 6);
+        assert_eq!(r.read_to_end_into(QName(b"a"), &mut buf).unwrap(), 3..10);
+        assert_eq!(r.read_event_into(&mut buf).unwrap(), Eof);
+    }
+}
+
+mod read_subtree_events {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::errors::{Error, IllFormedError};
+
+    /// Nested elements with the same name are included in the result
+    #[test]
+    fn nested() {
+        let mut r = Reader::from_reader("<a><a>1</a><b/></a><c/>".as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            r.read_subtree_events(QName(b"a"), &mut buf).unwrap(),
+            [
+                Start(BytesStart::new("a")),
+                Start(BytesStart::new("a")),
+                Text(BytesText::new("1")),
+                End(BytesEnd::new("a")),
+                Empty(BytesStart::new("b")),
+                End(BytesEnd::new("a")),
+            ]
+        );
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            Empty(BytesStart::new("c"))
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let mut r = Reader::from_reader("<a/><c/>".as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            r.read_subtree_events(QName(b"a"), &mut buf).unwrap(),
+            [Empty(BytesStart::new("a"))]
+        );
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            Empty(BytesStart::new("c"))
+        );
+    }
+
+    /// Nothing is consumed if the next event is not a start of the element
+    #[test]
+    fn other() {
+        let mut r = Reader::from_reader("<c/>".as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(r.read_subtree_events(QName(b"a"), &mut buf).unwrap(), []);
+        assert_eq!(
+            r.read_event_into(&mut buf).unwrap(),
+            Empty(BytesStart::new("c"))
+        );
+    }
+
+    #[test]
+    fn missing_end() {
+        let mut r = Reader::from_reader("<a><b/>".as_bytes());
+        let mut buf = Vec::new();
+
+        match r.read_subtree_events(QName(b"a"), &mut buf) {
+            Err(Error::IllFormed(cause)) => {
+                assert_eq!(cause, IllFormedError::MissingEndTag("a".into()))
+            }
+            x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+        }
+    }
+}
+
+/// The buffer owned by the reader is cleared between events
+#[test]
+fn read_event_owned() {
+    let mut r = Reader::from_reader("<tag>text</tag><long-tag-name/>".as_bytes());
+    let mut events = Vec::new();
+    loop {
+        match r.read_event_owned().unwrap() {
+            Eof => break,
+            event => events.push(event),
+        }
+    }
+    assert_eq!(
+        events,
+        [
+            Start(BytesStart::new("tag")),
+            Text(BytesText::new("text")),
+            End(BytesEnd::new("tag")),
+            Empty(BytesStart::new("long-tag-name")),
+        ]
+    );
+    assert_eq!(r.read_event_owned().unwrap(), Eof);
+}
+
+mod parse_with {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::reader::{parse_with, EventHandler};
+    use std::ops::ControlFlow;
+
+    /// Collects names of elements, stopping after the element `stop` is closed
+    #[derive(Default)]
+    struct Names {
+        names: Vec<String>,
+        stop: Option<&'static str>,
+        finished: bool,
+    }
+
+    impl EventHandler for Names {
+        fn start_element(&mut self, e: &BytesStart) -> ControlFlow<()> {
+            self.names
+                .push(from_utf8(e.name().as_ref()).unwrap().to_owned());
+            ControlFlow::Continue(())
+        }
+
+        fn end_element(&mut self, e: &BytesEnd) -> ControlFlow<()> {
+            self.names
+                .push(format!("/{}", from_utf8(e.name().as_ref()).unwrap()));
+            match self.stop {
+                Some(stop) if e.name().as_ref() == stop.as_bytes() => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        }
+
+        fn end_document(&mut self) {
+            self.finished = true;
+        }
+    }
+
+    #[test]
+    fn collect_names() {
+        let mut r = Reader::from_str("<root><a>text</a><b/><!-- c --></root>");
+        let mut handler = Names::default();
+
+        assert_eq!(
+            parse_with(&mut r, &mut Vec::new(), &mut handler).unwrap(),
+            ControlFlow::Continue(())
+        );
+        assert_eq!(handler.names, ["root", "a", "/a", "b", "/b", "/root"]);
+        assert_eq!(handler.finished, true);
+    }
+
+    #[test]
+    fn early_break() {
+        let mut r = Reader::from_str("<root><a/><b/></root>");
+        let mut handler = Names {
+            stop: Some("a"),
+            ..Names::default()
+        };
+
+        assert_eq!(
+            parse_with(&mut r, &mut Vec::new(), &mut handler).unwrap(),
+            ControlFlow::Break(())
+        );
+        assert_eq!(handler.names, ["root", "a", "/a"]);
+        assert_eq!(handler.finished, false);
+        // The reader is positioned after the event that stopped parsing
+        assert_eq!(r.read_event().unwrap(), Empty(BytesStart::new("b")));
+    }
+
+    /// Parsing can be continued after an incomplete markup when more data
+    /// is available, if the same buffer is passed
+    #[test]
+    fn resume_incomplete_markup() {
+        use quick_xml::errors::{Error, SyntaxError};
+        use std::io::Cursor;
+
+        let mut r = Reader::from_reader(Cursor::new(b"<root><a".to_vec()));
+        r.config_mut().resume_incomplete_markup = true;
+        let mut buf = Vec::new();
+        let mut handler = Names::default();
+
+        match parse_with(&mut r, &mut buf, &mut handler) {
+            Err(Error::Syntax(cause)) => assert_eq!(cause, SyntaxError::UnclosedTag),
+            x => panic!("Expected `Err(Syntax(_))`, but got `{:?}`", x),
+        }
+        r.get_mut().get_mut().extend_from_slice(b" x='1'/></root>");
+        assert_eq!(
+            parse_with(&mut r, &mut buf, &mut handler).unwrap(),
+            ControlFlow::Continue(())
+        );
+        assert_eq!(handler.names, ["root", "a", "/a", "/root"]);
+        assert_eq!(handler.finished, true);
+    }
+}
+
+mod read_until {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::BufReader;
+
+    /// Delimiter split between chunks of the underlying reader is found
+    #[test]
+    fn small_buffer() {
+        let xml = "<!-- comment - with -- dashes -->rest";
+        for capacity in 1..xml.len() {
+            let mut r = Reader::from_reader(BufReader::with_capacity(capacity, xml.as_bytes()));
+            let mut buf = Vec::new();
+
+            assert_eq!(
+                r.read_until(b"<!--", &mut buf).unwrap(),
+                (b"".as_ref(), true),
+                "capacity: {}",
+                capacity
+            );
+            buf.clear();
+            assert_eq!(
+                r.read_until(b"-->", &mut buf).unwrap(),
+                (b" comment - with -- dashes ".as_ref(), true),
+                "capacity: {}",
+                capacity
+            );
+            assert_eq!(r.buffer_position(), 33);
+
+            // The rest of the input is parsed as usual
+            buf.clear();
+            assert_eq!(
+                r.read_event_into(&mut buf).unwrap(),
+                Text(BytesText::new("rest"))
+            );
+        }
+    }
+
+    #[test]
+    fn not_found() {
+        let mut r = Reader::from_reader("text --".as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            r.read_until(b"-->", &mut buf).unwrap(),
+            (b"text --".as_ref(), false)
+        );
+        assert_eq!(r.buffer_position(), 7);
+        assert_eq!(r.read_event_into(&mut buf).unwrap(), Eof);
+    }
+}
+
+mod last_event_raw {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Reads all events and returns raw bytes of each of them
+    fn raw_events(r: &mut Reader<&[u8]>) -> Vec<String> {
+        let mut raw = Vec::new();
+        loop {
+            match r.read_event().unwrap() {
+                Eof => break,
+                _ => raw.push(from_utf8(r.last_event_raw()).unwrap().to_owned()),
+            }
+        }
+        assert_eq!(r.last_event_raw(), b"");
+        raw
+    }
+
+    #[test]
+    fn all_markup() {
+        let mut r = Reader::from_str(
+            "\u{FEFF}<?xml version='1.0'?><!DOCTYPE root><root a = \"1\" >\
+            <!-- comment --><![CDATA[ <cdata> ]]>text<?pi data?><empty\t/></root >",
+        );
+        assert_eq!(
+            raw_events(&mut r),
+            vec![
+                "<?xml version='1.0'?>",
+                "<!DOCTYPE root>",
+                "<root a = \"1\" >",
+                "<!-- comment -->",
+                "<![CDATA[ <cdata> ]]>",
+                "",
+                "<?pi data?>",
+                "<empty\t/>",
+                "</root >",
+            ]
+        );
+    }
+
+    #[test]
+    fn trim_text() {
+        let mut r = Reader::from_str("  <root>  <a/>\n  text  </root>  ");
+        r.config_mut().trim_text(true);
+        assert_eq!(raw_events(&mut r), vec!["<root>", "<a/>", "", "</root>"]);
+    }
+
+    /// The expanded empty element returns the same raw bytes for both events
+    #[test]
+    fn expand_empty_elements() {
+        let mut r = Reader::from_str("<root><a x='1'/></root>");
+        r.config_mut().expand_empty_elements = true;
+        assert_eq!(
+            raw_events(&mut r),
+            vec!["<root>", "<a x='1'/>", "<a x='1'/>", "</root>"]
+        );
+    }
+
+    #[test]
+    fn reset_by_read_to_end() {
+        let mut r = Reader::from_str("<root><a>text</a><b/></root>");
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("root")));
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("a")));
+        assert_eq!(r.last_event_raw(), b"<a>");
+
+        r.read_to_end(QName(b"a")).unwrap();
+        assert_eq!(r.last_event_raw(), b"");
+
+        assert_eq!(r.read_event().unwrap(), Empty(BytesStart::new("b")));
+        assert_eq!(r.last_event_raw(), b"<b/>");
+    }
+
+    /// The peeked event was not read by `read_event()`, so its raw bytes are unknown
+    #[test]
+    fn reset_by_peek_event_into() {
+        let mut r = Reader::from_str("<root><a/><b/></root>");
+        let mut buf = Vec::new();
+
+        assert_eq!(r.read_event().unwrap(), Start(BytesStart::new("root")));
+        assert_eq!(r.last_event_raw(), b"<root>");
+
+        assert_eq!(
+            r.peek_event_into(&mut buf).unwrap(),
+            &Empty(BytesStart::new("a"))
+        );
+        assert_eq!(r.last_event_raw(), b"");
+
+        assert_eq!(r.read_event().unwrap(), Empty(BytesStart::new("a")));
+        assert_eq!(r.last_event_raw(), b"");
+
+        assert_eq!(r.read_event().unwrap(), Empty(BytesStart::new("b")));
+        assert_eq!(r.last_event_raw(), b"<b/>");
+    }
+}
@@ -1122,9 +1122,9 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `item`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "item"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `item`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("item"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -1163,9 +1163,9 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `outer`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "outer"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `outer`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("outer"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -1256,11 +1256,11 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => {
-                        assert_eq!(e, "duplicate field `item`")
+                    Err(DeError::NonContiguousSequence(name)) => {
+                        assert_eq!(name, "item")
                     }
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `item`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("item"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -1301,9 +1301,9 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `outer`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "outer"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `outer`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("outer"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -1394,11 +1394,11 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => {
-                        assert_eq!(e, "duplicate field `item`")
+                    Err(DeError::NonContiguousSequence(name)) => {
+                        assert_eq!(name, "item")
                     }
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `item`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("item"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -1439,9 +1439,9 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `outer`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "outer"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `outer`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("outer"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -1509,9 +1509,9 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `item`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "item"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `item`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("item"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -1551,9 +1551,9 @@ mod fixed_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `outer`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "outer"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `outer`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("outer"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -3205,9 +3205,9 @@ mod variable_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `$value`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "$value"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -3249,11 +3249,11 @@ mod variable_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => {
-                        assert_eq!(e, "duplicate field `$value`")
+                    Err(DeError::NonContiguousSequence(name)) => {
+                        assert_eq!(name, "$value")
                     }
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -3345,9 +3345,9 @@ mod variable_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => assert_eq!(e, "duplicate field `$value`"),
+                    Err(DeError::NonContiguousSequence(name)) => assert_eq!(name, "$value"),
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -3389,11 +3389,11 @@ mod variable_name {
 
                 #[cfg(not(feature = "overlapped-lists"))]
                 match data {
-                    Err(DeError::Custom(e)) => {
-                        assert_eq!(e, "duplicate field `$value`")
+                    Err(DeError::NonContiguousSequence(name)) => {
+                        assert_eq!(name, "$value")
                     }
                     e => panic!(
-                        r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                        r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                         e
                     ),
                 }
@@ -3508,11 +3508,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `element`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "element")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `element`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("element"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -3545,11 +3545,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `$value`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "$value")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -3585,11 +3585,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `element`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "element")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `element`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("element"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -3625,11 +3625,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `$value`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "$value")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -3740,11 +3740,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `element`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "element")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `element`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("element"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -3777,11 +3777,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `$value`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "$value")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -3817,11 +3817,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `element`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "element")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `element`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("element"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -3857,11 +3857,11 @@ mod variable_name {
 
                         #[cfg(not(feature = "overlapped-lists"))]
                         match data {
-                            Err(DeError::Custom(e)) => {
-                                assert_eq!(e, "duplicate field `$value`")
+                            Err(DeError::NonContiguousSequence(name)) => {
+                                assert_eq!(name, "$value")
                             }
                             e => panic!(
-                                r#"Expected `Err(Custom("duplicate field `$value`"))`, but got `{:?}`"#,
+                                r#"Expected `Err(NonContiguousSequence("$value"))`, but got `{:?}`"#,
                                 e
                             ),
                         }
@@ -55,6 +55,50 @@ mod text {
         );
     }
 
+    /// Text, interrupted by comments and processing instructions, is concatenated
+    #[test]
+    fn interrupted() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Item {
+            #[serde(rename = "$text")]
+            content: String,
+        }
+
+        let item: Item =
+            from_str(r#"<root>Hello<!--x--><?pi?>World<!--y--><![CDATA[ & more]]></root>"#)
+                .unwrap();
+
+        assert_eq!(
+            item,
+            Item {
+                content: "HelloWorld & more".into()
+            }
+        );
+    }
+
+    /// Text, interrupted by comments and processing instructions, is concatenated
+    /// also when it is captured by a `$value` field
+    #[test]
+    fn interrupted_value() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Item {
+            #[serde(rename = "@attr")]
+            attr: String,
+            #[serde(rename = "$value")]
+            content: String,
+        }
+
+        let item: Item = from_str(r#"<root attr="a">Hello<!--x-->World<?pi?>!</root>"#).unwrap();
+
+        assert_eq!(
+            item,
+            Item {
+                attr: "a".into(),
+                content: "HelloWorld!".into()
+            }
+        );
+    }
+
     #[test]
     fn without() {
         #[derive(Debug, Deserialize, PartialEq)]
@@ -667,6 +711,8 @@ macro_rules! maplike_errors {
                         IllFormedError::MismatchedEndTag {
                             expected: "root".into(),
                             found: "mismatched".into(),
+                            expected_position: 0,
+                            found_position: 6,
                         }
                     ),
                     x => panic!(
@@ -689,6 +735,8 @@ macro_rules! maplike_errors {
                         IllFormedError::MismatchedEndTag {
                             expected: "root".into(),
                             found: "mismatched".into(),
+                            expected_position: 0,
+                            found_position: 33,
                         }
                     ),
                     x => panic!(
@@ -711,6 +759,8 @@ macro_rules! maplike_errors {
                         IllFormedError::MismatchedEndTag {
                             expected: "root".into(),
                             found: "mismatched".into(),
+                            expected_position: 0,
+                            found_position: 40,
                         }
                     ),
                     x => panic!(
@@ -733,6 +783,8 @@ macro_rules! maplike_errors {
                         IllFormedError::MismatchedEndTag {
                             expected: "string".into(),
                             found: "mismatched".into(),
+                            expected_position: 17,
+                            found_position: 31,
                         }
                     ),
                     x => panic!(
@@ -885,6 +937,36 @@ mod struct_ {
         );
     }
 
+    /// Leading and trailing whitespaces are allowed in numbers and booleans,
+    /// but should be kept in strings
+    #[test]
+    fn attributes_with_spaces() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Numbers {
+            #[serde(rename = "@count")]
+            count: i64,
+            #[serde(rename = "@delta")]
+            delta: i32,
+            #[serde(rename = "@flag")]
+            flag: bool,
+            #[serde(rename = "@string")]
+            string: String,
+        }
+
+        let data: Numbers =
+            from_str("<root count=\" 42 \" delta=\"\n-3\t\" flag=\" true \" string=\" 42 \"/>")
+                .unwrap();
+        assert_eq!(
+            data,
+            Numbers {
+                count: 42,
+                delta: -3,
+                flag: true,
+                string: " 42 ".into(),
+            }
+        );
+    }
+
     #[test]
     fn excess_attributes() {
         let data: Attributes = from_str(
@@ -920,6 +1002,226 @@ mod struct_ {
         );
     }
 
+    /// Attributes should be available together with the `$value` field that
+    /// captures text content of an element
+    #[test]
+    fn attribute_and_value() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Measurement {
+            #[serde(rename = "@unit")]
+            unit: String,
+            #[serde(rename = "$value")]
+            value: f64,
+        }
+
+        let data: Measurement = from_str(
+            // Comment for prevent unnecessary formatting - we use the same style in all tests
+            r#"<measurement unit="kg">3.5</measurement>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Measurement {
+                unit: "kg".into(),
+                value: 3.5,
+            }
+        );
+    }
+
+    /// Element with an attribute and a scalar text is a common shape in financial
+    /// and measurement XML. Check that it works when such element is nested or
+    /// is a sequence item. Note, that the attribute field should be renamed with
+    /// the `@` prefix, otherwise it is looked up among the child elements
+    #[test]
+    fn attribute_and_value_nested() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Price {
+            #[serde(rename = "@currency")]
+            currency: String,
+            #[serde(rename = "$value")]
+            amount: f64,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Order {
+            price: Price,
+            total: Vec<Price>,
+            quantity: u32,
+        }
+
+        let data: Price = from_str(
+            // Comment for prevent unnecessary formatting - we use the same style in all tests
+            r#"<price currency="USD">42.00</price>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Price {
+                currency: "USD".into(),
+                amount: 42.0,
+            }
+        );
+
+        let data: Order = from_str(
+            r#"
+            <order>
+                <price currency="USD">42.00</price>
+                <total currency="USD">84.00</total>
+                <total currency="EUR">77.50</total>
+                <quantity>2</quantity>
+            </order>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Order {
+                price: Price {
+                    currency: "USD".into(),
+                    amount: 42.0,
+                },
+                total: vec![
+                    Price {
+                        currency: "USD".into(),
+                        amount: 84.0,
+                    },
+                    Price {
+                        currency: "EUR".into(),
+                        amount: 77.5,
+                    },
+                ],
+                quantity: 2,
+            }
+        );
+    }
+
+    /// The same as `attribute_and_value`, but the `$value` field declared before
+    /// the attribute field
+    #[test]
+    fn value_and_attribute() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Measurement {
+            #[serde(rename = "$value")]
+            value: f64,
+            #[serde(rename = "@unit")]
+            unit: String,
+        }
+
+        let data: Measurement = from_str(
+            // Comment for prevent unnecessary formatting - we use the same style in all tests
+            r#"<measurement unit="kg">3.5</measurement>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Measurement {
+                value: 3.5,
+                unit: "kg".into(),
+            }
+        );
+    }
+
+    /// Missing attributes and elements with `#[serde(default)]` should get
+    /// their default values instead of a "missing field" error
+    #[test]
+    fn missing_default_fields() {
+        fn default_float() -> f64 {
+            4.2
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Defaults {
+            #[serde(rename = "@float", default = "default_float")]
+            float: f64,
+            #[serde(rename = "@present")]
+            present: u32,
+            #[serde(default)]
+            string: String,
+            #[serde(default)]
+            list: Vec<u32>,
+            #[serde(default)]
+            element: Option<u32>,
+        }
+
+        let data: Defaults = from_str(
+            // Comment for prevent unnecessary formatting - we use the same style in all tests
+            r#"<root present="1"><other>2</other></root>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Defaults {
+                float: 4.2,
+                present: 1,
+                string: "".into(),
+                list: vec![],
+                element: None,
+            }
+        );
+
+        let data: Defaults = from_str(
+            // Comment for prevent unnecessary formatting - we use the same style in all tests
+            r#"<root present="1"/>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Defaults {
+                float: 4.2,
+                present: 1,
+                string: "".into(),
+                list: vec![],
+                element: None,
+            }
+        );
+    }
+
+    /// The same as `missing_default_fields`, but `#[serde(default)]` is applied
+    /// to the whole struct
+    #[test]
+    fn missing_fields_with_struct_default() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(default)]
+        struct Defaults {
+            #[serde(rename = "@float")]
+            float: f64,
+            string: String,
+        }
+        impl Default for Defaults {
+            fn default() -> Self {
+                Self {
+                    float: 4.2,
+                    string: "default".into(),
+                }
+            }
+        }
+
+        let data: Defaults = from_str(
+            // Comment for prevent unnecessary formatting - we use the same style in all tests
+            r#"<root float="42"/>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Defaults {
+                float: 42.0,
+                string: "default".into(),
+            }
+        );
+
+        let data: Defaults = from_str(
+            // Comment for prevent unnecessary formatting - we use the same style in all tests
+            r#"<root><string>answer</string></root>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Defaults {
+                float: 4.2,
+                string: "answer".into(),
+            }
+        );
+    }
+
     #[test]
     fn namespaces() {
         let data: Elements = from_str(
@@ -1097,7 +1399,6 @@ mod flatten_struct {
     use pretty_assertions::assert_eq;
 
     #[test]
-    #[ignore = "Prime cause: deserialize_any under the hood + https://github.com/serde-rs/serde/issues/1183"]
     fn elements() {
         #[derive(Debug, Deserialize, PartialEq)]
         struct Struct {
@@ -1156,6 +1457,39 @@ mod flatten_struct {
             }
         );
     }
+
+    /// Flattened map captures attributes and elements that are not consumed
+    /// by other fields
+    #[test]
+    fn map() {
+        use std::collections::BTreeMap;
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Struct {
+            #[serde(rename = "@id")]
+            id: String,
+            name: String,
+            #[serde(flatten)]
+            extra: BTreeMap<String, String>,
+        }
+
+        let data: Struct = from_str(
+            r#"<root id="1" lang="en"><name>answer</name><color>red</color><size> 42 </size></root>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data,
+            Struct {
+                id: "1".into(),
+                name: "answer".into(),
+                extra: BTreeMap::from([
+                    ("@lang".to_string(), "en".to_string()),
+                    ("color".to_string(), "red".to_string()),
+                    ("size".to_string(), "42".to_string()),
+                ]),
+            }
+        );
+    }
 }
 
 // enum tests are so big, so it in the separate file serde-de-seq.rs to speed-up compilation
@@ -1328,6 +1662,54 @@ mod xml_schema_lists {
             list!(unit: () = "<root>1 second  false</root>" => vec![(), (), ()]);
         }
     }
+
+    /// Checks that tuples and arrays can be deserialized from lists with
+    /// the same number of items
+    mod tuple {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Svg {
+            #[serde(rename = "@viewBox")]
+            view_box: (f32, f32, f32, f32),
+            #[serde(rename = "$text")]
+            names: [String; 2],
+        }
+
+        #[test]
+        fn numbers_and_strings() {
+            let data: Svg =
+                from_str("<svg viewBox='0 0\t100.5\n100'> first\n second </svg>").unwrap();
+            assert_eq!(
+                data,
+                Svg {
+                    view_box: (0.0, 0.0, 100.5, 100.0),
+                    names: ["first".to_string(), "second".to_string()],
+                }
+            );
+        }
+
+        #[test]
+        fn too_short() {
+            match from_str::<Svg>("<svg viewBox='0 0 100'>first second</svg>") {
+                Err(DeError::Custom(e)) => {
+                    assert_eq!(e, "invalid length 3, expected a tuple of size 4")
+                }
+                x => panic!("Expected `Err(Custom(_))`, but got `{:?}`", x),
+            }
+        }
+
+        #[test]
+        fn too_long() {
+            match from_str::<Svg>("<svg viewBox='0 0 100 100'>first second third</svg>") {
+                Err(DeError::Custom(e)) => {
+                    assert_eq!(e, "invalid length 3, expected a tuple of size 2")
+                }
+                x => panic!("Expected `Err(Custom(_))`, but got `{:?}`", x),
+            }
+        }
+    }
 }
 
 /// Test for https://github.com/tafia/quick-xml/issues/324
@@ -1639,3 +2021,464 @@ mod xml_prolog {
         );
     }
 }
+
+/// Tests for tracking path to the element in which error was occurred
+mod track_path {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        price: u32,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Items {
+        item: Vec<Item>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Root {
+        items: Items,
+    }
+
+    const XML: &str = "\
+        <root>\
+            <items>\
+                <item><price>1</price></item>\
+                <item><price>x</price></item>\
+            </items>\
+        </root>";
+
+    #[test]
+    fn disabled() {
+        let mut de = Deserializer::from_str(XML);
+        match Root::deserialize(&mut de) {
+            Err(DeError::Custom(e)) => assert_eq!(e, "invalid type: string \"x\", expected u32"),
+            e => panic!("Expected `Err(Custom(_))`, but got `{:?}`", e),
+        }
+    }
+
+    #[test]
+    fn enabled() {
+        let mut de = Deserializer::from_str(XML);
+        de.track_path(true);
+        let err = Root::deserialize(&mut de).unwrap_err();
+        match err {
+            DeError::AtPath(ref path, ref e) => {
+                assert_eq!(path, "/root/items/item[2]/price");
+                assert!(
+                    matches!(**e, DeError::Custom(ref e) if e == "invalid type: string \"x\", expected u32"),
+                    "{:?}",
+                    e
+                );
+            }
+            ref e => panic!("Expected `Err(AtPath(_, _))`, but got `{:?}`", e),
+        }
+        assert_eq!(
+            err.to_string(),
+            "error at /root/items/item[2]/price: invalid type: string \"x\", expected u32"
+        );
+    }
+
+    #[test]
+    fn missing_field() {
+        let mut de = Deserializer::from_str(
+            "<root><items><item><price>1</price></item><item/></items></root>",
+        );
+        de.track_path(true);
+        assert_eq!(
+            Root::deserialize(&mut de).unwrap_err().to_string(),
+            "error at /root/items/item[2]: missing field `price`"
+        );
+    }
+
+    #[test]
+    fn success() {
+        let mut de = Deserializer::from_str(
+            "<root><items><item><price>1</price></item><item><price>2</price></item></items></root>",
+        );
+        de.track_path(true);
+        assert_eq!(
+            Root::deserialize(&mut de).unwrap(),
+            Root {
+                items: Items {
+                    item: vec![Item { price: 1 }, Item { price: 2 }],
+                },
+            }
+        );
+    }
+}
+
+mod deny_unknown_fields {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Item {
+        #[serde(rename = "@id")]
+        id: u32,
+        name: String,
+        price: u32,
+    }
+
+    #[test]
+    fn element() {
+        let err = from_str::<Item>(
+            "<item id='1'><name>apple</name><color>red</color><price>2</price></item>",
+        )
+        .unwrap_err();
+        match err {
+            DeError::UnknownField(ref field, expected) => {
+                assert_eq!(field, "color");
+                assert_eq!(expected, ["@id", "name", "price"]);
+            }
+            ref e => panic!("Expected `Err(UnknownField(_, _))`, but got `{:?}`", e),
+        }
+        assert_eq!(
+            err.to_string(),
+            "unknown field `color`, expected one of `@id`, `name`, `price`"
+        );
+    }
+
+    #[test]
+    fn attribute() {
+        let err =
+            from_str::<Item>("<item id='1' color='red'><name>apple</name><price>2</price></item>")
+                .unwrap_err();
+        match err {
+            DeError::UnknownField(ref field, expected) => {
+                assert_eq!(field, "@color");
+                assert_eq!(expected, ["@id", "name", "price"]);
+            }
+            ref e => panic!("Expected `Err(UnknownField(_, _))`, but got `{:?}`", e),
+        }
+        assert_eq!(
+            err.to_string(),
+            "unknown attribute `color`, expected one of `@id`, `name`, `price`"
+        );
+    }
+
+    /// Path tracking adds the location of the struct with an unknown field
+    #[test]
+    fn track_path() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Root {
+            item: Vec<Item>,
+        }
+
+        let mut de = Deserializer::from_str(
+            "<root>\
+                <item id='1'><name>apple</name><price>2</price></item>\
+                <item id='2'><weight>1</weight><name>pear</name><price>3</price></item>\
+            </root>",
+        );
+        de.track_path(true);
+        assert_eq!(
+            Root::deserialize(&mut de).unwrap_err().to_string(),
+            "error at /root/item[2]: unknown field `weight`, expected one of `@id`, `name`, `price`"
+        );
+    }
+}
+
+/// Without `overlapped-lists` feature elements of sequences should be contiguous
+#[cfg(not(feature = "overlapped-lists"))]
+mod non_contiguous_sequence {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Root {
+        item: Vec<u32>,
+        other: (),
+    }
+
+    #[test]
+    fn error() {
+        let err =
+            from_str::<Root>("<root><item>1</item><other/><item>2</item></root>").unwrap_err();
+        match err {
+            DeError::NonContiguousSequence(ref name) => assert_eq!(name, "item"),
+            ref e => panic!(
+                "Expected `Err(NonContiguousSequence(_))`, but got `{:?}`",
+                e
+            ),
+        }
+        assert_eq!(
+            err.to_string(),
+            "elements of sequence `item` are interleaved with other elements; \
+             activate the `overlapped-lists` feature to deserialize such sequences"
+        );
+    }
+
+    /// Contiguous sequences are not affected
+    #[test]
+    fn contiguous() {
+        assert_eq!(
+            from_str::<Root>("<root><item>1</item><item>2</item><other/></root>").unwrap(),
+            Root {
+                item: vec![1, 2],
+                other: (),
+            }
+        );
+    }
+}
+
+mod empty_element_as_none {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Root {
+        tag: Option<String>,
+        #[serde(default)]
+        after: Option<u32>,
+    }
+
+    fn de(xml: &str, enable: bool) -> Root {
+        let mut de = Deserializer::from_str(xml);
+        de.empty_element_as_none(enable);
+        Root::deserialize(&mut de).unwrap()
+    }
+
+    macro_rules! cases {
+        ($($name:ident: $xml:literal => $disabled:expr, $enabled:expr;)*) => {
+            mod disabled {
+                use super::*;
+                use pretty_assertions::assert_eq;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(de($xml, false), Root { tag: $disabled, after: Some(1) });
+                    }
+                )*
+            }
+
+            mod enabled {
+                use super::*;
+                use pretty_assertions::assert_eq;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(de($xml, true), Root { tag: $enabled, after: Some(1) });
+                    }
+                )*
+            }
+        };
+    }
+
+    cases!(
+        absent: "<root><after>1</after></root>" => None, None;
+        self_closed: "<root><tag/><after>1</after></root>" => Some("".into()), None;
+        start_end: "<root><tag></tag><after>1</after></root>" => Some("".into()), None;
+        text: "<root><tag>text</tag><after>1</after></root>" => Some("text".into()), Some("text".into());
+        with_attribute: "<root><tag attr=\"\"/><after>1</after></root>" => Some("".into()), Some("".into());
+    );
+
+    #[test]
+    fn top_level() {
+        let mut de = Deserializer::from_str("<root/>");
+        assert_eq!(
+            Option::<String>::deserialize(&mut de).unwrap(),
+            Some("".into())
+        );
+
+        let mut de = Deserializer::from_str("<root/>");
+        de.empty_element_as_none(true);
+        assert_eq!(Option::<String>::deserialize(&mut de).unwrap(), None);
+    }
+}
+
+mod positional_tuples {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Root<T> {
+        point: T,
+        after: u32,
+    }
+
+    fn de<'de, T: Deserialize<'de>>(xml: &'de str) -> Result<T, DeError> {
+        let mut de = Deserializer::from_str(xml);
+        de.positional_tuples(true);
+        T::deserialize(&mut de)
+    }
+
+    #[test]
+    fn disabled() {
+        match from_str::<(i32, i32)>("<point><x>1</x><y>2</y></point>") {
+            Err(DeError::UnexpectedStart(tag)) => assert_eq!(tag, b"x"),
+            x => panic!("Expected `Err(UnexpectedStart(_))`, but got `{:?}`", x),
+        }
+        assert_eq!(
+            from_str::<(i32, i32)>("<point>1</point><point>2</point>").unwrap(),
+            (1, 2)
+        );
+    }
+
+    #[test]
+    fn top_level() {
+        let xml = "<point><x>1</x><y>2</y></point>";
+        assert_eq!(de::<(i32, i32)>(xml).unwrap(), (1, 2));
+        assert_eq!(
+            de::<(String, String)>(xml).unwrap(),
+            ("1".to_string(), "2".to_string())
+        );
+    }
+
+    #[test]
+    fn field() {
+        let xml = "<root><point><x>1</x><y>2</y></point><after>3</after></root>";
+        assert_eq!(
+            de::<Root<(i32, i32)>>(xml).unwrap(),
+            Root {
+                point: (1, 2),
+                after: 3
+            }
+        );
+        assert_eq!(
+            de::<Root<(String, String)>>(xml).unwrap(),
+            Root {
+                point: ("1".to_string(), "2".to_string()),
+                after: 3
+            }
+        );
+    }
+
+    /// Children names are ignored, excess children are skipped
+    #[test]
+    fn excess() {
+        let xml = "<root><point><y>1</y><x>2</x><z>3</z></point><after>4</after></root>";
+        assert_eq!(
+            de::<Root<(i32, i32)>>(xml).unwrap(),
+            Root {
+                point: (1, 2),
+                after: 4
+            }
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let xml = "<root><point><a><x>1</x><y>2</y></a><b>3</b></point><after>4</after></root>";
+        assert_eq!(
+            de::<Root<((i32, i32), i32)>>(xml).unwrap(),
+            Root {
+                point: ((1, 2), 3),
+                after: 4
+            }
+        );
+    }
+
+    #[test]
+    fn not_enough() {
+        match de::<(i32, i32)>("<point><x>1</x></point>") {
+            Err(DeError::Custom(e)) => {
+                assert_eq!(e, "invalid length 1, expected a tuple of size 2")
+            }
+            x => panic!("Expected `Err(Custom(_))`, but got `{:?}`", x),
+        }
+    }
+}
+
+mod xsi_type {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Shape {
+        Circle { radius: f64 },
+        Square { side: f64 },
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Drawing {
+        shape: Vec<Shape>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Single {
+        shape: Shape,
+        after: u32,
+    }
+
+    fn de<'de, T: Deserialize<'de>>(xml: &'de str) -> Result<T, DeError> {
+        let mut de = Deserializer::from_str(xml);
+        de.xsi_type(true);
+        T::deserialize(&mut de)
+    }
+
+    /// Without the option variants are selected by element names
+    #[test]
+    fn disabled() {
+        let xml = r#"<Circle xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Square"><radius>1</radius></Circle>"#;
+        assert_eq!(
+            from_str::<Shape>(xml).unwrap(),
+            Shape::Circle { radius: 1.0 }
+        );
+    }
+
+    #[test]
+    fn top_level() {
+        let xml = r#"<shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Square"><side>2</side></shape>"#;
+        assert_eq!(de::<Shape>(xml).unwrap(), Shape::Square { side: 2.0 });
+    }
+
+    /// Elements without `xsi:type` are still deserialized by their names
+    #[test]
+    fn fallback_to_name() {
+        assert_eq!(
+            de::<Shape>("<Circle><radius>1</radius></Circle>").unwrap(),
+            Shape::Circle { radius: 1.0 }
+        );
+    }
+
+    #[test]
+    fn field() {
+        let xml = r#"
+            <root xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+                <shape xsi:type="Circle"><radius>1</radius></shape>
+                <after>3</after>
+            </root>"#;
+        assert_eq!(
+            de::<Single>(xml).unwrap(),
+            Single {
+                shape: Shape::Circle { radius: 1.0 },
+                after: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn sequence() {
+        let xml = r#"
+            <drawing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:g="urn:geometry">
+                <shape xsi:type="g:Circle"><radius>1</radius></shape>
+                <shape xsi:type=" g:Square "><side>2</side></shape>
+            </drawing>"#;
+        assert_eq!(
+            de::<Drawing>(xml).unwrap(),
+            Drawing {
+                shape: vec![Shape::Circle { radius: 1.0 }, Shape::Square { side: 2.0 }],
+            }
+        );
+    }
+
+    /// The prefix bound to the XML Schema instance namespace on the element is used
+    #[test]
+    fn bound_prefix() {
+        let xml = r#"<shape xmlns:i="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Circle" i:type="Square"><side>2</side></shape>"#;
+        assert_eq!(de::<Shape>(xml).unwrap(), Shape::Square { side: 2.0 });
+    }
+
+    /// The `xsi` prefix bound to other namespace is not recognized
+    #[test]
+    fn rebound_prefix() {
+        let xml = r#"<Circle xmlns:xsi="urn:other" xsi:type="Square"><radius>1</radius></Circle>"#;
+        assert_eq!(de::<Shape>(xml).unwrap(), Shape::Circle { radius: 1.0 });
+    }
+}
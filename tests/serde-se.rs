@@ -243,54 +243,80 @@ mod without_root {
         };
     }
 
-    err!(false_: false => Unsupported("cannot serialize `bool` without defined root tag"));
-    err!(true_:  true  => Unsupported("cannot serialize `bool` without defined root tag"));
+    err!(false_: false => Unsupported("cannot serialize `bool` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(true_:  true  => Unsupported("cannot serialize `bool` without defined root tag, use `Serializer::with_root` to define it"));
 
-    err!(i8_:    -42i8                => Unsupported("cannot serialize `i8` without defined root tag"));
-    err!(i16_:   -4200i16             => Unsupported("cannot serialize `i16` without defined root tag"));
-    err!(i32_:   -42000000i32         => Unsupported("cannot serialize `i32` without defined root tag"));
-    err!(i64_:   -42000000000000i64   => Unsupported("cannot serialize `i64` without defined root tag"));
-    err!(isize_: -42000000000000isize => Unsupported("cannot serialize `i64` without defined root tag"));
+    err!(i8_:    -42i8                => Unsupported("cannot serialize `i8` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(i16_:   -4200i16             => Unsupported("cannot serialize `i16` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(i32_:   -42000000i32         => Unsupported("cannot serialize `i32` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(i64_:   -42000000000000i64   => Unsupported("cannot serialize `i64` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(isize_: -42000000000000isize => Unsupported("cannot serialize `i64` without defined root tag, use `Serializer::with_root` to define it"));
 
-    err!(u8_:    42u8                => Unsupported("cannot serialize `u8` without defined root tag"));
-    err!(u16_:   4200u16             => Unsupported("cannot serialize `u16` without defined root tag"));
-    err!(u32_:   42000000u32         => Unsupported("cannot serialize `u32` without defined root tag"));
-    err!(u64_:   42000000000000u64   => Unsupported("cannot serialize `u64` without defined root tag"));
-    err!(usize_: 42000000000000usize => Unsupported("cannot serialize `u64` without defined root tag"));
+    err!(u8_:    42u8                => Unsupported("cannot serialize `u8` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(u16_:   4200u16             => Unsupported("cannot serialize `u16` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(u32_:   42000000u32         => Unsupported("cannot serialize `u32` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(u64_:   42000000000000u64   => Unsupported("cannot serialize `u64` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(usize_: 42000000000000usize => Unsupported("cannot serialize `u64` without defined root tag, use `Serializer::with_root` to define it"));
 
     serde_if_integer128! {
-        err!(i128_: -420000000000000000000000000000i128 => Unsupported("cannot serialize `i128` without defined root tag"));
-        err!(u128_:  420000000000000000000000000000u128 => Unsupported("cannot serialize `u128` without defined root tag"));
+        err!(i128_: -420000000000000000000000000000i128 => Unsupported("cannot serialize `i128` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(u128_:  420000000000000000000000000000u128 => Unsupported("cannot serialize `u128` without defined root tag, use `Serializer::with_root` to define it"));
     }
 
-    err!(f32_: 4.2f32 => Unsupported("cannot serialize `f32` without defined root tag"));
-    err!(f64_: 4.2f64 => Unsupported("cannot serialize `f64` without defined root tag"));
+    err!(f32_: 4.2f32 => Unsupported("cannot serialize `f32` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(f64_: 4.2f64 => Unsupported("cannot serialize `f64` without defined root tag, use `Serializer::with_root` to define it"));
 
-    err!(char_non_escaped: 'h'  => Unsupported("cannot serialize `char` without defined root tag"));
-    err!(char_lt:          '<'  => Unsupported("cannot serialize `char` without defined root tag"));
-    err!(char_gt:          '>'  => Unsupported("cannot serialize `char` without defined root tag"));
-    err!(char_amp:         '&'  => Unsupported("cannot serialize `char` without defined root tag"));
-    err!(char_apos:        '\'' => Unsupported("cannot serialize `char` without defined root tag"));
-    err!(char_quot:        '"'  => Unsupported("cannot serialize `char` without defined root tag"));
-    err!(char_space:       ' '  => Unsupported("cannot serialize `char` without defined root tag"));
+    err!(char_non_escaped: 'h'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(char_lt:          '<'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(char_gt:          '>'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(char_amp:         '&'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(char_apos:        '\'' => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(char_quot:        '"'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(char_space:       ' '  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
 
-    err!(str_non_escaped: "non-escaped string" => Unsupported("cannot serialize `&str` without defined root tag"));
-    err!(str_escaped:  "<\"escaped & string'>" => Unsupported("cannot serialize `&str` without defined root tag"));
+    err!(str_non_escaped: "non-escaped string" => Unsupported("cannot serialize `&str` without defined root tag, use `Serializer::with_root` to define it"));
+    err!(str_escaped:  "<\"escaped & string'>" => Unsupported("cannot serialize `&str` without defined root tag, use `Serializer::with_root` to define it"));
 
-    err!(bytes: Bytes(b"<\"escaped & bytes'>") => Unsupported("cannot serialize `&[u8]` without defined root tag"));
+    err!(bytes: Bytes(b"<\"escaped & bytes'>") => Unsupported("cannot serialize `&[u8]` without defined root tag, use `Serializer::with_root` to define it"));
 
     serialize_as!(option_none: Option::<Unit>::None => "");
     serialize_as!(option_some: Some(Unit) => "<Unit/>");
 
-    err!(unit: () => Unsupported("cannot serialize `()` without defined root tag"));
+    err!(unit: () => Unsupported("cannot serialize `()` without defined root tag, use `Serializer::with_root` to define it"));
     serialize_as!(unit_struct: Unit => "<Unit/>");
 
     serialize_as!(newtype: Newtype(true) => "<Newtype>true</Newtype>");
 
-    err!(seq: vec![1, 2, 3] => Unsupported("cannot serialize sequence without defined root tag"));
+    /// Newtype is transparent, so it can be serialized as an attribute value
+    mod newtype_attribute {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        struct Id(u32);
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        struct Item {
+            #[serde(rename = "@id")]
+            id: Id,
+            #[serde(rename = "@parent")]
+            parent: Option<Id>,
+            name: String,
+        }
+
+        serialize_as!(attributes:
+            Item {
+                id: Id(5),
+                parent: Some(Id(1)),
+                name: "answer".into(),
+            }
+            => r#"<Item id="5" parent="1"><name>answer</name></Item>"#);
+    }
+
+    err!(seq: vec![1, 2, 3] => Unsupported("cannot serialize sequence without defined root tag, use `Serializer::with_root` to define it"));
     err!(tuple:
         ("<\"&'>", "with\t\r\n spaces", 3usize)
-        => Unsupported("cannot serialize unnamed tuple without defined root tag"));
+        => Unsupported("cannot serialize unnamed tuple without defined root tag, use `Serializer::with_root` to define it"));
     serialize_as!(tuple_struct:
         Tuple(42.0, "answer")
         => "<Tuple>42</Tuple>\
@@ -298,7 +324,7 @@ mod without_root {
 
     err!(map:
         BTreeMap::from([("$text", 1), ("_2", 3)])
-        => Unsupported("cannot serialize map without defined root tag"));
+        => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
     serialize_as!(struct_:
         Struct {
             float: 42.0,
@@ -326,7 +352,7 @@ mod without_root {
             nested: Nested { float: 42.0 },
             string: "answer",
         }
-        => Unsupported("cannot serialize map without defined root tag"));
+        => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
     serialize_as!(empty_struct:
         Empty {}
         => "<Empty/>");
@@ -1082,7 +1108,7 @@ mod without_root {
                     nested: Nested { float: 42.0 },
                     string: "answer",
                 }
-                => Unsupported("cannot serialize map without defined root tag"));
+                => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
             serialize_as!(empty_struct:
                 InternallyTagged::Empty {}
                 => "<InternallyTagged>\
@@ -1193,11 +1219,11 @@ mod without_root {
             // Until https://github.com/serde-rs/serde/pull/2288 will be merged,
             // some results can be confusing
             err!(unit: Untagged::Unit
-                => Unsupported("cannot serialize `()` without defined root tag"));
+                => Unsupported("cannot serialize `()` without defined root tag, use `Serializer::with_root` to define it"));
             err!(newtype: Untagged::Newtype(true)
-                => Unsupported("cannot serialize `bool` without defined root tag"));
+                => Unsupported("cannot serialize `bool` without defined root tag, use `Serializer::with_root` to define it"));
             err!(tuple: Untagged::Tuple(42.0, "answer")
-                => Unsupported("cannot serialize unnamed tuple without defined root tag"));
+                => Unsupported("cannot serialize unnamed tuple without defined root tag, use `Serializer::with_root` to define it"));
             // NOTE: Cannot be deserialized in roundtrip due to
             // https://github.com/serde-rs/serde/issues/1183
             serialize_as_only!(struct_:
@@ -1229,7 +1255,7 @@ mod without_root {
                     nested: Nested { float: 42.0 },
                     string: "answer",
                 }
-                => Unsupported("cannot serialize map without defined root tag"));
+                => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
             serialize_as!(empty_struct:
                 Untagged::Empty {}
                 => "<Untagged/>");
@@ -1289,53 +1315,53 @@ mod without_root {
             };
         }
 
-        err!(false_: false => Unsupported("cannot serialize `bool` without defined root tag"));
-        err!(true_:  true  => Unsupported("cannot serialize `bool` without defined root tag"));
+        err!(false_: false => Unsupported("cannot serialize `bool` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(true_:  true  => Unsupported("cannot serialize `bool` without defined root tag, use `Serializer::with_root` to define it"));
 
-        err!(i8_:    -42i8                => Unsupported("cannot serialize `i8` without defined root tag"));
-        err!(i16_:   -4200i16             => Unsupported("cannot serialize `i16` without defined root tag"));
-        err!(i32_:   -42000000i32         => Unsupported("cannot serialize `i32` without defined root tag"));
-        err!(i64_:   -42000000000000i64   => Unsupported("cannot serialize `i64` without defined root tag"));
-        err!(isize_: -42000000000000isize => Unsupported("cannot serialize `i64` without defined root tag"));
+        err!(i8_:    -42i8                => Unsupported("cannot serialize `i8` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(i16_:   -4200i16             => Unsupported("cannot serialize `i16` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(i32_:   -42000000i32         => Unsupported("cannot serialize `i32` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(i64_:   -42000000000000i64   => Unsupported("cannot serialize `i64` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(isize_: -42000000000000isize => Unsupported("cannot serialize `i64` without defined root tag, use `Serializer::with_root` to define it"));
 
-        err!(u8_:    42u8                => Unsupported("cannot serialize `u8` without defined root tag"));
-        err!(u16_:   4200u16             => Unsupported("cannot serialize `u16` without defined root tag"));
-        err!(u32_:   42000000u32         => Unsupported("cannot serialize `u32` without defined root tag"));
-        err!(u64_:   42000000000000u64   => Unsupported("cannot serialize `u64` without defined root tag"));
-        err!(usize_: 42000000000000usize => Unsupported("cannot serialize `u64` without defined root tag"));
+        err!(u8_:    42u8                => Unsupported("cannot serialize `u8` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(u16_:   4200u16             => Unsupported("cannot serialize `u16` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(u32_:   42000000u32         => Unsupported("cannot serialize `u32` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(u64_:   42000000000000u64   => Unsupported("cannot serialize `u64` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(usize_: 42000000000000usize => Unsupported("cannot serialize `u64` without defined root tag, use `Serializer::with_root` to define it"));
 
         serde_if_integer128! {
-            err!(i128_: -420000000000000000000000000000i128 => Unsupported("cannot serialize `i128` without defined root tag"));
-            err!(u128_:  420000000000000000000000000000u128 => Unsupported("cannot serialize `u128` without defined root tag"));
+            err!(i128_: -420000000000000000000000000000i128 => Unsupported("cannot serialize `i128` without defined root tag, use `Serializer::with_root` to define it"));
+            err!(u128_:  420000000000000000000000000000u128 => Unsupported("cannot serialize `u128` without defined root tag, use `Serializer::with_root` to define it"));
         }
 
-        err!(f32_: 4.2f32 => Unsupported("cannot serialize `f32` without defined root tag"));
-        err!(f64_: 4.2f64 => Unsupported("cannot serialize `f64` without defined root tag"));
+        err!(f32_: 4.2f32 => Unsupported("cannot serialize `f32` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(f64_: 4.2f64 => Unsupported("cannot serialize `f64` without defined root tag, use `Serializer::with_root` to define it"));
 
-        err!(char_non_escaped: 'h'  => Unsupported("cannot serialize `char` without defined root tag"));
-        err!(char_lt:          '<'  => Unsupported("cannot serialize `char` without defined root tag"));
-        err!(char_gt:          '>'  => Unsupported("cannot serialize `char` without defined root tag"));
-        err!(char_amp:         '&'  => Unsupported("cannot serialize `char` without defined root tag"));
-        err!(char_apos:        '\'' => Unsupported("cannot serialize `char` without defined root tag"));
-        err!(char_quot:        '"'  => Unsupported("cannot serialize `char` without defined root tag"));
+        err!(char_non_escaped: 'h'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(char_lt:          '<'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(char_gt:          '>'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(char_amp:         '&'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(char_apos:        '\'' => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(char_quot:        '"'  => Unsupported("cannot serialize `char` without defined root tag, use `Serializer::with_root` to define it"));
 
-        err!(str_non_escaped: "non-escaped string" => Unsupported("cannot serialize `&str` without defined root tag"));
-        err!(str_escaped:  "<\"escaped & string'>" => Unsupported("cannot serialize `&str` without defined root tag"));
+        err!(str_non_escaped: "non-escaped string" => Unsupported("cannot serialize `&str` without defined root tag, use `Serializer::with_root` to define it"));
+        err!(str_escaped:  "<\"escaped & string'>" => Unsupported("cannot serialize `&str` without defined root tag, use `Serializer::with_root` to define it"));
 
-        err!(bytes: Bytes(b"<\"escaped & bytes'>") => Unsupported("cannot serialize `&[u8]` without defined root tag"));
+        err!(bytes: Bytes(b"<\"escaped & bytes'>") => Unsupported("cannot serialize `&[u8]` without defined root tag, use `Serializer::with_root` to define it"));
 
         serialize_as!(option_none: Option::<Unit>::None => "");
         serialize_as!(option_some: Some(Unit) => "<Unit/>");
 
-        err!(unit: () => Unsupported("cannot serialize `()` without defined root tag"));
+        err!(unit: () => Unsupported("cannot serialize `()` without defined root tag, use `Serializer::with_root` to define it"));
         serialize_as!(unit_struct: Unit => "<Unit/>");
 
         serialize_as!(newtype: Newtype(true) => "<Newtype>true</Newtype>");
 
-        err!(seq: vec![1, 2, 3] => Unsupported("cannot serialize sequence without defined root tag"));
+        err!(seq: vec![1, 2, 3] => Unsupported("cannot serialize sequence without defined root tag, use `Serializer::with_root` to define it"));
         err!(tuple:
             ("<\"&'>", "with\t\r\n spaces", 3usize)
-            => Unsupported("cannot serialize unnamed tuple without defined root tag"));
+            => Unsupported("cannot serialize unnamed tuple without defined root tag, use `Serializer::with_root` to define it"));
         serialize_as!(tuple_struct:
             Tuple(42.0, "answer")
             => "<Tuple>42</Tuple>\n\
@@ -1343,7 +1369,7 @@ mod without_root {
 
         err!(map:
             BTreeMap::from([("$text", 1), ("_2", 3)])
-            => Unsupported("cannot serialize map without defined root tag"));
+            => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
         serialize_as!(struct_:
             Struct {
                 float: 42.0,
@@ -1371,7 +1397,7 @@ mod without_root {
                 nested: Nested { float: 42.0 },
                 string: "answer",
             }
-            => Unsupported("cannot serialize map without defined root tag"));
+            => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
         serialize_as!(empty_struct:
             Empty {}
             => "<Empty/>");
@@ -1528,7 +1554,7 @@ mod without_root {
                         nested: Nested { float: 42.0 },
                         string: "answer",
                     }
-                    => Unsupported("cannot serialize map without defined root tag"));
+                    => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
                 serialize_as!(empty_struct:
                     InternallyTagged::Empty {}
                     => "<InternallyTagged>\n  \
@@ -1629,11 +1655,11 @@ mod without_root {
                 use pretty_assertions::assert_eq;
 
                 err!(unit: Untagged::Unit
-                    => Unsupported("cannot serialize `()` without defined root tag"));
+                    => Unsupported("cannot serialize `()` without defined root tag, use `Serializer::with_root` to define it"));
                 err!(newtype: Untagged::Newtype(true)
-                    => Unsupported("cannot serialize `bool` without defined root tag"));
+                    => Unsupported("cannot serialize `bool` without defined root tag, use `Serializer::with_root` to define it"));
                 err!(tuple: Untagged::Tuple(42.0, "answer")
-                    => Unsupported("cannot serialize unnamed tuple without defined root tag"));
+                    => Unsupported("cannot serialize unnamed tuple without defined root tag, use `Serializer::with_root` to define it"));
                 serialize_as!(struct_:
                     Untagged::Struct {
                         float: 42.0,
@@ -1659,7 +1685,7 @@ mod without_root {
                         nested: Nested { float: 42.0 },
                         string: "answer",
                     }
-                    => Unsupported("cannot serialize map without defined root tag"));
+                    => Unsupported("cannot serialize map without defined root tag, use `Serializer::with_root` to define it"));
                 serialize_as!(empty_struct:
                     Untagged::Empty {}
                     => "<Untagged/>");
@@ -1912,6 +1938,12 @@ mod with_root {
                 1\
                 <_2>3</_2>\
             </root>");
+    err!(map_invalid_key:
+        BTreeMap::from([("valid", 1), ("in valid", 2)])
+        => Unsupported("character ` ` is not allowed in an XML name `in valid`"));
+    err!(map_number_key:
+        BTreeMap::from([(1, 1)])
+        => Unsupported("character `1` is not allowed at the start of an XML name `1`"));
     serialize_as!(struct_:
         Struct {
             float: 42.0,
@@ -2294,3 +2326,325 @@ mod with_root {
         }
     }
 }
+
+mod prologue {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn without_indent() {
+        let mut buffer = String::new();
+        let mut ser = Serializer::with_root(&mut buffer, Some("root")).unwrap();
+        ser.write_prologue_comment(" comment ")
+            .unwrap()
+            .write_prologue_pi("target", "")
+            .unwrap()
+            .write_prologue_pi("xml-stylesheet", "href=\"style.css\"")
+            .unwrap();
+
+        42.serialize(ser).unwrap();
+        assert_eq!(
+            buffer,
+            "<!-- comment --><?target?><?xml-stylesheet href=\"style.css\"?><root>42</root>"
+        );
+    }
+
+    #[test]
+    fn with_indent() {
+        let mut buffer = String::new();
+        let mut ser = Serializer::new(&mut buffer);
+        ser.indent(' ', 2);
+        ser.write_prologue_comment(" first ").unwrap();
+        ser.write_prologue_comment(" second ").unwrap();
+
+        Struct {
+            float: 42.0,
+            string: "answer",
+        }
+        .serialize(ser)
+        .unwrap();
+        assert_eq!(
+            buffer,
+            "<!-- first -->\n\
+             <!-- second -->\n\
+             <Struct>\n  \
+               <float>42</float>\n  \
+               <string>answer</string>\n\
+             </Struct>"
+        );
+    }
+
+    #[test]
+    fn invalid_comment() {
+        let mut buffer = String::new();
+        let mut ser = Serializer::new(&mut buffer);
+        match ser.write_prologue_comment("a--b") {
+            Err(SeError::Unsupported(e)) => {
+                assert_eq!(e, "comment cannot contain `--` or end with `-`: `a--b`")
+            }
+            e => panic!(
+                "Expected `Err(Unsupported(_))`, but got `{:?}`",
+                e.map(|_| ())
+            ),
+        }
+        match ser.write_prologue_comment("a-") {
+            Err(SeError::Unsupported(e)) => {
+                assert_eq!(e, "comment cannot contain `--` or end with `-`: `a-`")
+            }
+            e => panic!(
+                "Expected `Err(Unsupported(_))`, but got `{:?}`",
+                e.map(|_| ())
+            ),
+        }
+        assert_eq!(buffer, "");
+    }
+
+    #[test]
+    fn invalid_pi() {
+        let mut buffer = String::new();
+        let mut ser = Serializer::new(&mut buffer);
+        match ser.write_prologue_pi("XML", "version=\"1.0\"") {
+            Err(SeError::Unsupported(e)) => {
+                assert_eq!(e, "processing instruction target cannot be `xml`")
+            }
+            e => panic!(
+                "Expected `Err(Unsupported(_))`, but got `{:?}`",
+                e.map(|_| ())
+            ),
+        }
+        match ser.write_prologue_pi("target", "?>") {
+            Err(SeError::Unsupported(e)) => {
+                assert_eq!(e, "processing instruction cannot contain `?>`: `?>`")
+            }
+            e => panic!(
+                "Expected `Err(Unsupported(_))`, but got `{:?}`",
+                e.map(|_| ())
+            ),
+        }
+        assert!(matches!(
+            ser.write_prologue_pi("1target", ""),
+            Err(SeError::Unsupported(_))
+        ));
+        assert_eq!(buffer, "");
+    }
+}
+
+/// Tests for `Comment` and `Pi` marker types
+mod markup {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::se::{to_string, Comment, Pi};
+
+    #[derive(Serialize)]
+    struct Document {
+        #[serde(rename = "@attr")]
+        attr: u32,
+        pi: Pi,
+        first: u32,
+        comment: Comment,
+        second: u32,
+        comments: Vec<Comment>,
+    }
+
+    fn document() -> Document {
+        Document {
+            attr: 1,
+            pi: Pi {
+                target: "target".into(),
+                data: "some data".into(),
+            },
+            first: 2,
+            comment: Comment(" comment ".into()),
+            second: 3,
+            comments: vec![Comment("a".into()), Comment("b".into())],
+        }
+    }
+
+    #[test]
+    fn without_indent() {
+        assert_eq!(
+            to_string(&document()).unwrap(),
+            "<Document attr=\"1\">\
+                <?target some data?>\
+                <first>2</first>\
+                <!-- comment -->\
+                <second>3</second>\
+                <!--a--><!--b-->\
+            </Document>"
+        );
+    }
+
+    #[test]
+    fn with_indent() {
+        let mut buffer = String::new();
+        let mut ser = Serializer::new(&mut buffer);
+        ser.indent(' ', 2);
+
+        document().serialize(ser).unwrap();
+        assert_eq!(
+            buffer,
+            "<Document attr=\"1\">\n  \
+                <?target some data?>\n  \
+                <first>2</first>\n  \
+                <!-- comment -->\n  \
+                <second>3</second>\n  \
+                <!--a-->\n  \
+                <!--b-->\n\
+            </Document>"
+        );
+    }
+
+    #[test]
+    fn pi_without_data() {
+        #[derive(Serialize)]
+        struct Root {
+            pi: Pi,
+        }
+
+        let data = Root {
+            pi: Pi {
+                target: "target".into(),
+                data: String::new(),
+            },
+        };
+        assert_eq!(to_string(&data).unwrap(), "<Root><?target?></Root>");
+    }
+
+    #[test]
+    fn in_value() {
+        #[derive(Serialize)]
+        struct Root {
+            #[serde(rename = "$value")]
+            value: Vec<Comment>,
+        }
+
+        let data = Root {
+            value: vec![Comment("a".into()), Comment("b".into())],
+        };
+        assert_eq!(to_string(&data).unwrap(), "<Root><!--a--><!--b--></Root>");
+    }
+
+    #[test]
+    fn invalid_comment() {
+        #[derive(Serialize)]
+        struct Root {
+            comment: Comment,
+        }
+
+        let data = Root {
+            comment: Comment("a--b".into()),
+        };
+        match to_string(&data) {
+            Err(SeError::Unsupported(e)) => {
+                assert_eq!(e, "comment cannot contain `--` or end with `-`: `a--b`")
+            }
+            e => panic!("Expected `Err(Unsupported(_))`, but got `{:?}`", e),
+        }
+    }
+
+    #[test]
+    fn invalid_pi() {
+        #[derive(Serialize)]
+        struct Root {
+            pi: Pi,
+        }
+
+        let data = Root {
+            pi: Pi {
+                target: "target".into(),
+                data: "?>".into(),
+            },
+        };
+        match to_string(&data) {
+            Err(SeError::Unsupported(e)) => {
+                assert_eq!(e, "processing instruction cannot contain `?>`: `?>`")
+            }
+            e => panic!("Expected `Err(Unsupported(_))`, but got `{:?}`", e),
+        }
+
+        let data = Root {
+            pi: Pi {
+                target: "xml".into(),
+                data: String::new(),
+            },
+        };
+        match to_string(&data) {
+            Err(SeError::Unsupported(e)) => {
+                assert_eq!(e, "processing instruction target cannot be `xml`")
+            }
+            e => panic!("Expected `Err(Unsupported(_))`, but got `{:?}`", e),
+        }
+
+        // Space in the target is not confused with the separator before the data
+        let data = Root {
+            pi: Pi {
+                target: "target data".into(),
+                data: String::new(),
+            },
+        };
+        match to_string(&data) {
+            Err(SeError::Custom(e)) => assert_eq!(
+                e,
+                "character ` ` is not allowed in an XML name `target data`"
+            ),
+            e => panic!("Expected `Err(Custom(_))`, but got `{:?}`", e),
+        }
+    }
+}
+
+/// Checks that sequences are written to the writer item by item without
+/// buffering them
+mod streaming {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::se::to_writer_with_root;
+    use serde::ser::SerializeSeq;
+    use std::cell::Cell;
+    use std::fmt::{self, Write};
+
+    /// Writer that only counts written bytes and does not store anything
+    struct CountingWriter<'a>(&'a Cell<usize>);
+
+    impl<'a> Write for CountingWriter<'a> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.set(self.0.get() + s.len());
+            Ok(())
+        }
+    }
+
+    /// Sequence generated on the fly that checks that each previous item
+    /// already reached the writer when the next one is serialized
+    struct Items<'a> {
+        count: usize,
+        written: &'a Cell<usize>,
+    }
+
+    impl<'a> Serialize for Items<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(None)?;
+            let mut last = self.written.get();
+            for i in 0..self.count {
+                seq.serialize_element(&i)?;
+
+                let written = self.written.get();
+                assert!(written > last, "item {} was not written immediately", i);
+                last = written;
+            }
+            seq.end()
+        }
+    }
+
+    #[test]
+    fn large_sequence() {
+        let written = Cell::new(0);
+        let items = Items {
+            count: 100_000,
+            written: &written,
+        };
+
+        to_writer_with_root(CountingWriter(&written), "item", &items).unwrap();
+        // `<item>` + `</item>` for each item + numbers: 10 one-digit, 90 two-digit, ...
+        let digits = 10 + 90 * 2 + 900 * 3 + 9000 * 4 + 90000 * 5;
+        assert_eq!(written.get(), 100_000 * 13 + digits);
+    }
+}
@@ -1,5 +1,9 @@
-use quick_xml::{de::from_str, se::to_string};
+use quick_xml::{
+    de::{from_str, Deserializer},
+    se::{to_string, Serializer},
+};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use pretty_assertions::assert_eq;
 
@@ -51,3 +55,225 @@ fn round_trip_list_of_enums() {
     let deserialized_nodes: Nodes = from_str(serialized_nodes.as_str()).unwrap();
     assert_eq!(deserialized_nodes, nodes);
 }
+
+/// Fields renamed with `@` prefix are mapped to attributes, other fields
+/// are mapped to child elements
+#[test]
+fn attributes_and_elements() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Item {
+        #[serde(rename = "@id")]
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Catalog {
+        #[serde(rename = "@id")]
+        id: String,
+        #[serde(rename = "@version")]
+        version: Option<u8>,
+        title: String,
+        item: Vec<Item>,
+    }
+
+    let catalog = Catalog {
+        id: "main".to_string(),
+        version: Some(2),
+        title: "Books".to_string(),
+        item: vec![
+            Item {
+                id: 1,
+                name: "first".to_string(),
+            },
+            Item {
+                id: 2,
+                name: "second".to_string(),
+            },
+        ],
+    };
+
+    let xml = to_string(&catalog).unwrap();
+    assert_eq!(
+        xml,
+        r#"<Catalog id="main" version="2"><title>Books</title><item id="1"><name>first</name></item><item id="2"><name>second</name></item></Catalog>"#
+    );
+
+    let deserialized: Catalog = from_str(&xml).unwrap();
+    assert_eq!(deserialized, catalog);
+}
+
+/// Maps are serialized as a sequence of child elements named by keys
+#[test]
+fn map_as_child_elements() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Config {
+        values: BTreeMap<String, u32>,
+    }
+
+    let config = Config {
+        values: vec![("height".to_string(), 10), ("width".to_string(), 20)]
+            .into_iter()
+            .collect(),
+    };
+
+    let xml = to_string(&config).unwrap();
+    assert_eq!(
+        xml,
+        "<Config><values><height>10</height><width>20</width></values></Config>"
+    );
+
+    let deserialized: Config = from_str(&xml).unwrap();
+    assert_eq!(deserialized, config);
+}
+
+/// Text interleaved with elements is kept in order when `$value` field
+/// contains a sequence of enums with a `$text` variant
+#[test]
+fn mixed_content() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Inline {
+        #[serde(rename = "$text")]
+        Text(String),
+        #[serde(rename = "b")]
+        Bold(String),
+        #[serde(rename = "br")]
+        Break,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Paragraph {
+        #[serde(rename = "$value")]
+        content: Vec<Inline>,
+    }
+
+    let paragraph = Paragraph {
+        content: vec![
+            Inline::Text("Some ".to_string()),
+            Inline::Bold("bold".to_string()),
+            Inline::Text(" text".to_string()),
+            Inline::Break,
+            Inline::Text("and a new line".to_string()),
+        ],
+    };
+
+    let xml = to_string(&paragraph).unwrap();
+    assert_eq!(
+        xml,
+        "<Paragraph>Some <b>bold</b> text<br/>and a new line</Paragraph>"
+    );
+
+    // Spaces around texts are significant in mixed content
+    let mut de = Deserializer::from_str(&xml);
+    de.trim_text(false);
+    let deserialized = Paragraph::deserialize(&mut de).unwrap();
+    assert_eq!(deserialized, paragraph);
+}
+
+/// Names of all struct fields are converted by the function passed to
+/// `rename_fields`, but map keys and special fields are kept as is
+#[test]
+fn renamed_fields() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Title {
+        #[serde(rename = "@xml_lang")]
+        xml_lang: String,
+        #[serde(rename = "$text")]
+        text_content: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Book {
+        #[serde(rename = "@page_count")]
+        page_count: u32,
+        book_title: Title,
+        co_author: Vec<String>,
+        extra_info: BTreeMap<String, u32>,
+    }
+
+    let book = Book {
+        page_count: 42,
+        book_title: Title {
+            xml_lang: "en".to_string(),
+            text_content: "Guide".to_string(),
+        },
+        co_author: vec!["Arthur".to_string(), "Ford".to_string()],
+        extra_info: vec![("first_edition".to_string(), 1979)]
+            .into_iter()
+            .collect(),
+    };
+
+    let mut xml = String::new();
+    let mut ser = Serializer::with_root(&mut xml, Some("book")).unwrap();
+    ser.rename_fields(|name| name.replace('_', "-"));
+    book.serialize(ser).unwrap();
+    assert_eq!(
+        xml,
+        "<book page-count=\"42\">\
+            <book-title xml-lang=\"en\">Guide</book-title>\
+            <co-author>Arthur</co-author>\
+            <co-author>Ford</co-author>\
+            <extra-info><first_edition>1979</first_edition></extra-info>\
+        </book>"
+    );
+
+    let mut de = Deserializer::from_str(&xml);
+    de.rename_fields(|name| name.replace('_', "-"));
+    let deserialized = Book::deserialize(&mut de).unwrap();
+    assert_eq!(deserialized, book);
+
+    // Without renaming fields are not found
+    assert!(from_str::<Book>(&xml).is_err());
+}
+
+/// Fields of structs marked with `#[serde(flatten)]` that are renamed with
+/// `@` prefix are serialized as attributes of the parent element, so
+/// attribute sets can be composed from several structs
+#[test]
+fn flattened_attributes() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Common {
+        #[serde(rename = "@id")]
+        id: String,
+        #[serde(rename = "@class")]
+        class: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Link {
+        #[serde(flatten)]
+        common: Common,
+        #[serde(rename = "@href")]
+        href: String,
+        #[serde(rename = "$text")]
+        text: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Document {
+        title: String,
+        #[serde(rename = "a")]
+        link: Link,
+    }
+
+    let document = Document {
+        title: "Links".to_string(),
+        link: Link {
+            common: Common {
+                id: "home".to_string(),
+                class: "nav".to_string(),
+            },
+            href: "/".to_string(),
+            text: "Home".to_string(),
+        },
+    };
+
+    let xml = to_string(&document).unwrap();
+    assert_eq!(
+        xml,
+        r#"<Document><title>Links</title><a id="home" class="nav" href="/">Home</a></Document>"#
+    );
+
+    let deserialized: Document = from_str(&xml).unwrap();
+    assert_eq!(deserialized, document);
+}
@@ -550,3 +550,281 @@ mod in_attributes_multi {
         );
     }
 }
+
+#[test]
+fn crlf_newlines() {
+    let mut buffer = Vec::new();
+    let mut writer = Writer::new_with_indent_and_newline(&mut buffer, b'\t', 1, "\r\n");
+
+    writer
+        .create_element("outer")
+        .write_inner_content(|writer| {
+            writer
+                .create_element("inner")
+                .with_attribute(("attr1", "value1"))
+                .new_line()
+                .with_attribute(("attr2", "value2"))
+                .write_text_content(BytesText::new("text"))?;
+            writer.write_event(Event::Empty(BytesStart::new("empty")))?;
+            Ok(())
+        })
+        .expect("write tag failed");
+
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        concat!(
+            "<outer>\r\n",
+            "\t<inner attr1=\"value1\"\r\n",
+            "       attr2=\"value2\">text</inner>\r\n",
+            "\t<empty/>\r\n",
+            "</outer>",
+        )
+    );
+}
+
+#[test]
+fn pretty() {
+    let mut buffer = Vec::new();
+    let mut writer = Writer::pretty(&mut buffer);
+
+    writer
+        .create_element("outer")
+        .write_inner_content(|writer| {
+            writer
+                .create_element("inner")
+                .with_attribute(("attr", "value"))
+                .write_inner_content(|writer| {
+                    writer.write_event(Event::Empty(BytesStart::new("empty")))?;
+                    Ok(())
+                })?;
+            writer
+                .create_element("mixed")
+                .write_inner_content(|writer| {
+                    writer.write_event(Event::Text(BytesText::new("text")))?;
+                    writer.write_event(Event::Empty(BytesStart::new("b")))?;
+                    Ok(())
+                })?;
+            Ok(())
+        })
+        .expect("write tag failed");
+
+    assert_eq!(writer.get_indent(), Some((b' ', 2)));
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        concat!(
+            "<outer>\n",
+            "  <inner attr=\"value\">\n",
+            "    <empty/>\n",
+            "  </inner>\n",
+            "  <mixed>text<b/>\n",
+            "  </mixed>\n",
+            "</outer>",
+        )
+    );
+}
+
+#[test]
+fn compact() {
+    let mut buffer = Vec::new();
+    let mut writer = Writer::compact(&mut buffer);
+
+    writer
+        .create_element("outer")
+        .write_inner_content(|writer| {
+            writer
+                .create_element("inner")
+                .with_attribute(("attr", "value"))
+                .new_line()
+                .write_empty()?;
+            writer.write_event(Event::Text(BytesText::new("text")))?;
+            Ok(())
+        })
+        .expect("write tag failed");
+
+    assert_eq!(writer.get_indent(), None);
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"<outer><inner attr="value"/>text</outer>"#
+    );
+}
+
+mod set_indent {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn disable_for_subtree() {
+        let mut buffer = Vec::new();
+        let mut writer = Writer::new_with_indent(&mut buffer, b' ', 4);
+
+        writer
+            .create_element("outer")
+            .write_inner_content(|writer| {
+                writer.write_event(Event::Empty(BytesStart::new("before")))?;
+
+                assert_eq!(writer.get_indent(), Some((b' ', 4)));
+                writer.write_indent()?;
+                writer.set_indent(None);
+                assert_eq!(writer.get_indent(), None);
+
+                writer
+                    .create_element("compact")
+                    .write_inner_content(|writer| {
+                        writer
+                            .create_element("inner")
+                            .write_text_content(BytesText::new("text"))?;
+                        writer.write_event(Event::Empty(BytesStart::new("empty")))?;
+                        Ok(())
+                    })?;
+
+                writer.set_indent(Some((b' ', 4)));
+                writer.write_event(Event::Empty(BytesStart::new("after")))?;
+                Ok(())
+            })
+            .expect("write tag failed");
+
+        assert_eq!(
+            std::str::from_utf8(&buffer).unwrap(),
+            concat!(
+                "<outer>\n",
+                "    <before/>\n",
+                "    <compact><inner>text</inner><empty/></compact>\n",
+                "    <after/>\n",
+                "</outer>",
+            )
+        );
+    }
+
+    #[test]
+    fn enable_inside_element() {
+        let mut buffer = Vec::new();
+        let mut writer = Writer::new(&mut buffer);
+        assert_eq!(writer.get_indent(), None);
+
+        writer
+            .create_element("outer")
+            .write_inner_content(|writer| {
+                writer
+                    .create_element("compact")
+                    .write_inner_content(|writer| {
+                        writer.set_indent(Some((b'\t', 1)));
+                        writer.write_event(Event::Empty(BytesStart::new("inner")))?;
+                        Ok(())
+                    })?;
+                Ok(())
+            })
+            .expect("write tag failed");
+
+        assert_eq!(
+            std::str::from_utf8(&buffer).unwrap(),
+            concat!(
+                "<outer><compact>\n",
+                "\t\t<inner/>\n",
+                "\t</compact>\n",
+                "</outer>",
+            )
+        );
+    }
+
+    #[test]
+    fn change_size() {
+        let mut buffer = Vec::new();
+        let mut writer = Writer::new_with_indent_and_newline(&mut buffer, b' ', 4, "\r\n");
+
+        writer
+            .create_element("outer")
+            .write_inner_content(|writer| {
+                writer.write_event(Event::Empty(BytesStart::new("wide")))?;
+                writer.set_indent(Some((b' ', 1)));
+                assert_eq!(writer.get_indent(), Some((b' ', 1)));
+                writer.write_event(Event::Empty(BytesStart::new("narrow")))?;
+                Ok(())
+            })
+            .expect("write tag failed");
+
+        assert_eq!(
+            std::str::from_utf8(&buffer).unwrap(),
+            concat!(
+                "<outer>\r\n",
+                "    <wide/>\r\n",
+                " <narrow/>\r\n",
+                "</outer>",
+            )
+        );
+    }
+}
+
+mod reformat {
+    use pretty_assertions::assert_eq;
+    use quick_xml::errors::{Error, IllFormedError};
+    use quick_xml::reformat;
+
+    #[test]
+    fn reindent() {
+        let xml = concat!(
+            "<?xml version=\"1.0\"?>\n",
+            "<!-- header -->\n",
+            "<root>\n",
+            "        <a>\n",
+            "  <b attr=\"1\"/>\n",
+            "                </a>\n",
+            "  <?pi data?>\n",
+            "</root>\n",
+        );
+        let expected = concat!(
+            "<?xml version=\"1.0\"?>\n",
+            "<!-- header -->\n",
+            "<root>\n",
+            "\t<a>\n",
+            "\t\t<b attr=\"1\"/>\n",
+            "\t</a>\n",
+            "\t<?pi data?>\n",
+            "</root>",
+        );
+        assert_eq!(reformat(xml, b'\t', 1).unwrap(), expected);
+        // Reformatting is idempotent
+        assert_eq!(reformat(expected, b'\t', 1).unwrap(), expected);
+    }
+
+    /// Content of elements with text or CDATA should be written as is
+    #[test]
+    fn mixed_content() {
+        let xml = concat!(
+            "<root>",
+            "<p>Some <b>bold</b> <i>and <u>underlined</u></i> text<br/></p>",
+            "<code><![CDATA[<tag>]]></code>",
+            "<text>  spaces  </text>",
+            "<list>  <item/>  </list>",
+            "</root>",
+        );
+        assert_eq!(
+            reformat(xml, b' ', 4).unwrap(),
+            concat!(
+                "<root>\n",
+                "    <p>Some <b>bold</b> <i>and <u>underlined</u></i> text<br/></p>\n",
+                "    <code><![CDATA[<tag>]]></code>\n",
+                "    <text>  spaces  </text>\n",
+                "    <list>\n",
+                "        <item/>\n",
+                "    </list>\n",
+                "</root>",
+            )
+        );
+    }
+
+    #[test]
+    fn ill_formed() {
+        match reformat("<root></other>", b' ', 4) {
+            Err(Error::IllFormed(cause)) => assert_eq!(
+                cause,
+                IllFormedError::MismatchedEndTag {
+                    expected: "root".into(),
+                    found: "other".into(),
+                    expected_position: 0,
+                    found_position: 6,
+                }
+            ),
+            x => panic!("Expected `Err(IllFormed(_))`, but got `{:?}`", x),
+        }
+    }
+}
@@ -90,6 +90,49 @@ mod declaration {
             "writer output (LHS)"
         );
     }
+
+    #[test]
+    fn write_declaration() {
+        let mut writer = Writer::new(Vec::new());
+        writer
+            .write_declaration("1.1", None, Some("no"))
+            .expect("writing xml decl should succeed");
+        writer
+            .write_event(Empty(BytesStart::new("root")))
+            .expect("writing empty tag should succeed");
+
+        let result = writer.into_inner();
+        assert_eq!(
+            String::from_utf8(result).expect("utf-8 output"),
+            "<?xml version=\"1.1\" standalone=\"no\"?><root/>",
+            "writer output (LHS)"
+        );
+    }
+
+    /// The declaration is followed by a newline when indentation is enabled
+    #[test]
+    fn write_default_declaration_indented() {
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 4);
+        writer
+            .write_default_declaration()
+            .expect("writing xml decl should succeed");
+        writer
+            .write_event(Start(BytesStart::new("root")))
+            .expect("writing start tag should succeed");
+        writer
+            .write_event(Empty(BytesStart::new("child")))
+            .expect("writing empty tag should succeed");
+        writer
+            .write_event(End(BytesEnd::new("root")))
+            .expect("writing end tag should succeed");
+
+        let result = writer.into_inner();
+        assert_eq!(
+            String::from_utf8(result).expect("utf-8 output"),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<root>\n    <child/>\n</root>",
+            "writer output (LHS)"
+        );
+    }
 }
 
 #[test]
@@ -124,6 +167,356 @@ fn empty() {
     );
 }
 
+/// Events written by reference are written in the same way as owned ones
+#[test]
+fn write_event_ref() {
+    let events = [
+        Start(BytesStart::new("paired").with_attributes([("attr", "value")])),
+        Empty(BytesStart::new("empty")),
+        Text(BytesText::new("text")),
+        CData(BytesCData::new("cdata")),
+        End(BytesEnd::new("paired")),
+    ];
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    for event in &events {
+        writer
+            .write_event_ref(event)
+            .expect("writing event should succeed");
+    }
+
+    let result = writer.into_inner();
+    assert_eq!(
+        String::from_utf8(result).expect("utf-8 output"),
+        "<paired attr=\"value\">\n  <empty/>text<![CDATA[cdata]]></paired>",
+        "writer output (LHS)"
+    );
+}
+
+#[test]
+fn write_empty() {
+    let mut writer = Writer::new(Vec::new());
+    writer
+        .write_empty("game", [("publisher", "Blizzard & Co"), ("year", "2004")])
+        .expect("writing empty tag should succeed");
+    writer
+        .write_empty("br", [])
+        .expect("writing empty tag should succeed");
+
+    let result = writer.into_inner();
+    assert_eq!(
+        String::from_utf8(result).expect("utf-8 output"),
+        r#"<game publisher="Blizzard &amp; Co" year="2004"/><br/>"#,
+        "writer output (LHS)"
+    );
+}
+
+mod bytes_written {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn write_document<W: std::io::Write>(writer: &mut Writer<W>) {
+        writer
+            .write_default_declaration()
+            .expect("writing xml decl should succeed");
+        writer
+            .create_element("root")
+            .with_attribute(("attr", "a & b"))
+            .write_inner_content(|writer| {
+                writer.write_event(Text(BytesText::new("text < 1")))?;
+                writer.write_event(CData(BytesCData::new("cdata")))?;
+                writer.write_empty("empty", [])?;
+                writer.write_event(Comment(BytesText::new("comment")))?;
+                writer.write_event(PI(BytesPI::new("pi")))?;
+                writer
+                    .create_element("child")
+                    .write_text_content(BytesText::new("inner"))?;
+                Ok(())
+            })
+            .expect("writing element should succeed");
+    }
+
+    #[test]
+    fn new() {
+        let mut writer = Writer::new(Vec::new());
+        assert_eq!(writer.bytes_written(), 0);
+
+        write_document(&mut writer);
+
+        let count = writer.bytes_written();
+        assert_eq!(count, writer.into_inner().len() as u64);
+    }
+
+    /// Indentation is counted
+    #[test]
+    fn indented() {
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 4);
+
+        write_document(&mut writer);
+        writer
+            .write_indent()
+            .expect("writing indent should succeed");
+
+        let count = writer.bytes_written();
+        assert_eq!(count, writer.into_inner().len() as u64);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn serializable() {
+        #[derive(serde::Serialize)]
+        struct Data {
+            #[serde(rename = "@attr")]
+            attr: &'static str,
+            item: Vec<u32>,
+        }
+
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 4);
+
+        writer
+            .write_event(Start(BytesStart::new("root")))
+            .expect("writing start tag should succeed");
+        writer
+            .write_serializable(
+                "data",
+                &Data {
+                    attr: "a & b",
+                    item: vec![1, 2],
+                },
+            )
+            .expect("writing serializable should succeed");
+        writer
+            .write_event(End(BytesEnd::new("root")))
+            .expect("writing end tag should succeed");
+
+        let count = writer.bytes_written();
+        assert_eq!(count, writer.into_inner().len() as u64);
+    }
+}
+
+/// Attributes without values are allowed in HTML
+mod check_names {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn disabled_by_default() {
+        let mut writer = Writer::new(Vec::new());
+        writer
+            .write_event(Empty(BytesStart::new("1bad")))
+            .expect("writing event should succeed");
+        assert_eq!(writer.into_inner(), b"<1bad/>");
+    }
+
+    #[test]
+    fn valid() {
+        let mut writer = Writer::new(Vec::new());
+        writer.check_names(true);
+        writer
+            .write_event(Start(
+                BytesStart::new("ns:élément")
+                    .with_attributes([("xmlns:ns", "urn"), ("_a-1.b", "")]),
+            ))
+            .expect("writing event should succeed");
+        writer
+            .write_event(End(BytesEnd::new("ns:élément")))
+            .expect("writing event should succeed");
+        assert_eq!(
+            writer.into_inner(),
+            r#"<ns:élément xmlns:ns="urn" _a-1.b=""></ns:élément>"#.as_bytes()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let mut writer = Writer::new(Vec::new());
+        writer.check_names(true);
+
+        for event in [
+            Start(BytesStart::new("1bad")),
+            Empty(BytesStart::new("-bad")),
+            Empty(BytesStart::new("")),
+            Empty(BytesStart::new("tag").with_attributes([("a<b", "")])),
+        ] {
+            let error = writer.write_event(event).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        }
+        let error = writer.write_event(End(BytesEnd::new(" "))).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), "invalid XML name ` `");
+
+        assert_eq!(writer.into_inner(), b"");
+    }
+}
+
+mod comment {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::reader::Reader;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn valid() {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_comment(" a - b ").unwrap();
+        writer.write_comment("").unwrap();
+        writer.write_comment_safe(" a - b ").unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            b"<!-- a - b --><!----><!-- a - b -->".as_ref()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let mut writer = Writer::new(Vec::new());
+        for text in ["a--b", "--", "a-", "-"] {
+            let error = writer.write_comment(text).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput, "{:?}", text);
+        }
+        assert_eq!(writer.into_inner(), b"");
+    }
+
+    #[test]
+    fn safe() {
+        for (text, expected) in [
+            ("a--b", "<!--a- -b-->"),
+            ("a---b", "<!--a- - -b-->"),
+            ("--", "<!--- - -->"),
+            ("a-", "<!--a- -->"),
+            ("-", "<!--- -->"),
+            ("a -- b -", "<!--a - - b - -->"),
+        ] {
+            let mut writer = Writer::new(Vec::new());
+            writer.write_comment_safe(text).unwrap();
+            let result = writer.into_inner();
+            assert_eq!(String::from_utf8(result.clone()).unwrap(), expected);
+
+            // The written comment is well-formed
+            let mut reader = Reader::from_reader(result.as_slice());
+            reader.config_mut().check_comments = true;
+            assert!(
+                matches!(reader.read_event().unwrap(), Comment(_)),
+                "{:?}",
+                text
+            );
+            assert_eq!(reader.read_event().unwrap(), Eof);
+        }
+    }
+}
+
+mod attribute_quote {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::writer::Quote;
+
+    /// Writes an element with attributes which values contain different quotes
+    fn write(quote: Option<Quote>) -> String {
+        let mut writer = Writer::new(Vec::new());
+        if let Some(quote) = quote {
+            writer.attribute_quote(quote);
+        }
+        writer
+            .create_element("tag")
+            .with_attribute(("none", "&"))
+            .with_attributes([
+                ("double", r#"say "hi""#),
+                ("single", "it's"),
+                ("both", r#"it's "hi""#),
+            ])
+            .write_empty()
+            .expect("writing element should succeed");
+        String::from_utf8(writer.into_inner()).expect("utf-8 output")
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(
+            write(None),
+            r#"<tag none="&amp;" double="say &quot;hi&quot;" single="it&apos;s" both="it&apos;s &quot;hi&quot;"/>"#
+        );
+    }
+
+    #[test]
+    fn double() {
+        assert_eq!(
+            write(Some(Quote::Double)),
+            r#"<tag none="&amp;" double='say "hi"' single="it's" both="it's &quot;hi&quot;"/>"#
+        );
+    }
+
+    #[test]
+    fn single() {
+        assert_eq!(
+            write(Some(Quote::Single)),
+            r#"<tag none='&amp;' double='say "hi"' single="it's" both='it&apos;s "hi"'/>"#
+        );
+    }
+
+    #[test]
+    fn indented() {
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        writer.attribute_quote(Quote::Single);
+        writer
+            .create_element("tag")
+            .new_line()
+            .with_attribute(("a", "1"))
+            .new_line()
+            .with_attribute(("b", "it's"))
+            .write_empty()
+            .expect("writing element should succeed");
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).expect("utf-8 output"),
+            "<tag\n  a='1'\n  b=\"it's\"/>"
+        );
+    }
+
+    #[test]
+    fn write_empty() {
+        let mut writer = Writer::new(Vec::new());
+        writer.attribute_quote(Quote::Single);
+        writer
+            .write_empty("tag", [("a", r#""1""#), ("b", "'2'")])
+            .expect("writing empty tag should succeed");
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).expect("utf-8 output"),
+            r#"<tag a='"1"' b="'2'"/>"#
+        );
+    }
+
+    /// Attributes of events are written as is
+    #[test]
+    fn write_event() {
+        let mut writer = Writer::new(Vec::new());
+        writer.attribute_quote(Quote::Single);
+        writer
+            .write_event(Empty(BytesStart::new("tag").with_attributes([("a", "\"")])))
+            .expect("writing event should succeed");
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).expect("utf-8 output"),
+            r#"<tag a="&quot;"/>"#
+        );
+    }
+}
+
+#[test]
+fn start_with_attribute_flag() {
+    let mut start = BytesStart::new("input");
+    start.push_attribute_flag("disabled");
+
+    let mut writer = Writer::new(Vec::new());
+    writer
+        .write_event(Start(start))
+        .expect("writing start tag should succeed");
+
+    let result = writer.into_inner();
+    assert_eq!(
+        String::from_utf8(result).expect("utf-8 output"),
+        "<input disabled>",
+        "writer output (LHS)"
+    );
+}
+
 #[test]
 fn start() {
     let mut writer = Writer::new(Vec::new());
@@ -173,6 +566,44 @@ fn text() {
     );
 }
 
+#[test]
+fn write_escaped_text() {
+    let mut writer = Writer::new(Vec::new());
+    writer
+        .write_escaped_text("Kerrigan &amp; Raynor: &lt;Z[erg]&gt;")
+        .expect("writing text should succeed");
+
+    let result = writer.into_inner();
+    assert_eq!(
+        String::from_utf8(result).expect("utf-8 output"),
+        "Kerrigan &amp; Raynor: &lt;Z[erg]&gt;",
+        "writer output (LHS)"
+    );
+}
+
+/// `]]>` is not allowed in a text, so it should be escaped
+#[test]
+fn text_with_cdata_end() {
+    let mut writer = Writer::new(Vec::new());
+    writer
+        .write_event(Text(BytesText::new("a]]>b")))
+        .expect("writing text should succeed");
+    writer
+        .write_event(Text(
+            BytesCData::new("c]]>d")
+                .minimal_escape()
+                .expect("utf-8 input"),
+        ))
+        .expect("writing text should succeed");
+
+    let result = writer.into_inner();
+    assert_eq!(
+        String::from_utf8(result).expect("utf-8 output"),
+        "a]]&gt;bc]]&gt;d",
+        "writer output (LHS)"
+    );
+}
+
 #[test]
 fn cdata() {
     let mut writer = Writer::new(Vec::new());
@@ -234,3 +665,77 @@ fn eof() {
         "writer output (LHS)"
     );
 }
+
+mod event_sink {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use quick_xml::reader::Reader;
+    use quick_xml::writer::{EventSink, WriteEvent};
+
+    /// Events are stored as owned copies, so they outlive the reader
+    #[test]
+    fn collect() {
+        let mut sink = EventSink::new();
+        {
+            let mut reader = Reader::from_str("<root attr='1'>text<![CDATA[data]]><empty/></root>");
+            loop {
+                match reader.read_event().unwrap() {
+                    Eof => break,
+                    Text(e) => sink.write_event_ref(&Text(e)).unwrap(),
+                    e => sink.write_event(e).unwrap(),
+                }
+            }
+        }
+        sink.write_event(Comment(BytesText::new("end"))).unwrap();
+
+        assert_eq!(sink.events().len(), 6);
+        assert_eq!(
+            sink.into_events(),
+            [
+                Start(BytesStart::from_content("root attr='1'", 4)),
+                Text(BytesText::new("text")),
+                CData(BytesCData::new("data")),
+                Empty(BytesStart::new("empty")),
+                End(BytesEnd::new("root")),
+                Comment(BytesText::new("end")),
+            ]
+        );
+    }
+
+    /// Wraps all written events into the `<wrapper>` element
+    fn wrap<W: WriteEvent>(
+        out: &mut W,
+        events: &[quick_xml::events::Event],
+    ) -> std::io::Result<()> {
+        out.write_event(Start(BytesStart::new("wrapper")))?;
+        for event in events {
+            out.write_event_ref(event)?;
+        }
+        out.write_event(End(BytesEnd::new("wrapper")))
+    }
+
+    /// The same code can write to the sink and to the writer
+    #[test]
+    fn generic() {
+        let events = [Empty(BytesStart::new("item")), Text(BytesText::new("a<b"))];
+
+        let mut sink = EventSink::new();
+        wrap(&mut sink, &events).unwrap();
+        assert_eq!(
+            sink.into_events(),
+            [
+                Start(BytesStart::new("wrapper")),
+                Empty(BytesStart::new("item")),
+                Text(BytesText::new("a<b")),
+                End(BytesEnd::new("wrapper")),
+            ]
+        );
+
+        let mut writer = Writer::new(Vec::new());
+        wrap(&mut writer, &events).unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            b"<wrapper><item/>a&lt;b</wrapper>".as_ref()
+        );
+    }
+}